@@ -0,0 +1,45 @@
+//! Compares binary snapshots of values with the JSON path on a large document.
+//!
+//! Run with `cargo bench --features binary,json`.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use egui_any::Value;
+use indexmap::IndexMap;
+
+/// Builds a list of `len` records, each a map of the usual scalar kinds and a short list.
+fn document(len: usize) -> Value {
+    Value::List(
+        (0..len as i64)
+            .map(|idx| {
+                let mut record = IndexMap::new();
+                record.insert("id".to_owned(), Value::Int(idx));
+                record.insert("name".to_owned(), Value::String(format!("record-{idx}")));
+                record.insert("enabled".to_owned(), Value::Bool(idx % 2 == 0));
+                record.insert("weight".to_owned(), Value::Float(idx as f64 * 0.25));
+                record.insert("tags".to_owned(), Value::List((0..4).map(|n| Value::Int(idx * n)).collect()));
+                Value::Map(record)
+            })
+            .collect(),
+    )
+}
+
+fn snapshots(c: &mut Criterion) {
+    let value = document(10_000);
+    let bytes = value.to_bytes();
+    let json = serde_json::to_vec(&value.to_json()).unwrap();
+
+    let mut group = c.benchmark_group("encode");
+    group.bench_function("binary", |b| b.iter(|| black_box(&value).to_bytes()));
+    group.bench_function("json", |b| b.iter(|| serde_json::to_vec(&black_box(&value).to_json()).unwrap()));
+    group.finish();
+
+    let mut group = c.benchmark_group("decode");
+    group.bench_function("binary", |b| b.iter(|| Value::from_bytes(black_box(&bytes)).unwrap()));
+    group.bench_function("json", |b| {
+        b.iter(|| Value::from_json(&serde_json::from_slice(black_box(&json)).unwrap()))
+    });
+    group.finish();
+}
+
+criterion_group!(benches, snapshots);
+criterion_main!(benches);
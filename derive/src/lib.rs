@@ -0,0 +1,114 @@
+use proc_macro2::TokenStream;
+use quote::quote;
+use syn::{parse_macro_input, parse_quote, Data, DeriveInput, Fields, GenericParam};
+
+/// Derives `egui_any::DescribeValue` for structs with named fields and enums with unit variants.
+///
+/// Structs are described as maps with field names as keys,
+/// enums as strings with variant names as variants.
+#[proc_macro_derive(DescribeValue)]
+pub fn derive_describe_value(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match describe_value(input) {
+        Ok(tokens) => tokens.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn describe_value(mut input: DeriveInput) -> syn::Result<TokenStream> {
+    for param in &mut input.generics.params {
+        if let GenericParam::Type(param) = param {
+            param.bounds.push(parse_quote!(::egui_any::DescribeValue));
+        }
+    }
+
+    let ident = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let (describe, to_value, from_value) = match &input.data {
+        Data::Struct(data) => {
+            let Fields::Named(fields) = &data.fields else {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "`DescribeValue` can be derived only for structs with named fields",
+                ));
+            };
+
+            let idents: Vec<_> = fields.named.iter().map(|f| f.ident.as_ref().unwrap()).collect();
+            let names: Vec<_> = idents.iter().map(|ident| ident.to_string()).collect();
+            let tys: Vec<_> = fields.named.iter().map(|f| &f.ty).collect();
+
+            (
+                quote! {
+                    ::egui_any::__private::struct_desc(::std::vec![
+                        #((#names, <#tys as ::egui_any::DescribeValue>::describe()),)*
+                    ])
+                },
+                quote! {
+                    ::egui_any::__private::struct_value(::std::vec![
+                        #((#names, ::egui_any::DescribeValue::to_value(&self.#idents)),)*
+                    ])
+                },
+                quote! {
+                    let fields = ::egui_any::__private::struct_fields(value)?;
+                    ::std::option::Option::Some(#ident {
+                        #(#idents: ::egui_any::__private::field(fields, #names)?,)*
+                    })
+                },
+            )
+        }
+        Data::Enum(data) => {
+            let mut idents = Vec::new();
+            for variant in &data.variants {
+                if !matches!(variant.fields, Fields::Unit) {
+                    return Err(syn::Error::new_spanned(
+                        variant,
+                        "`DescribeValue` can be derived only for enums with unit variants",
+                    ));
+                }
+                idents.push(&variant.ident);
+            }
+            let names: Vec<_> = idents.iter().map(|ident| ident.to_string()).collect();
+
+            (
+                quote! {
+                    ::egui_any::__private::enum_desc(&[#(#names),*])
+                },
+                quote! {
+                    let name = match self {
+                        #(#ident::#idents => #names,)*
+                    };
+                    ::egui_any::Value::String(name.to_owned())
+                },
+                quote! {
+                    match ::egui_any::__private::enum_variant(value)? {
+                        #(#names => ::std::option::Option::Some(#ident::#idents),)*
+                        _ => ::std::option::Option::None,
+                    }
+                },
+            )
+        }
+        Data::Union(_) => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "`DescribeValue` can't be derived for unions",
+            ))
+        }
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::egui_any::DescribeValue for #ident #ty_generics #where_clause {
+            fn describe() -> ::egui_any::Desc {
+                #describe
+            }
+
+            fn to_value(&self) -> ::egui_any::Value {
+                #to_value
+            }
+
+            fn from_value(value: &::egui_any::Value) -> ::std::option::Option<Self> {
+                #from_value
+            }
+        }
+    })
+}
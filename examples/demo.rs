@@ -1,5 +1,7 @@
 use egui_probe::Probe;
-use egui_any::{Desc, Value, ValueProbe};
+use egui_any::{
+    Breadcrumb, ChangeLog, DefaultPreview, Desc, History, LayoutMode, Outline, ProbeConfig, ScrollSync, Value, ValueProbe,
+};
 
 fn main() {
     let native_options = eframe::NativeOptions::default();
@@ -14,6 +16,11 @@ fn main() {
 struct EguiValueDemoApp {
     desc: Option<Desc>,
     value: Value,
+    flat: bool,
+    config: ProbeConfig,
+    history: History,
+    changelog: ChangeLog,
+    scroll: ScrollSync,
 }
 
 impl EguiValueDemoApp {
@@ -21,6 +28,11 @@ impl EguiValueDemoApp {
         EguiValueDemoApp {
             desc: None,
             value: Value::Int(42),
+            flat: false,
+            config: ProbeConfig::default(),
+            history: History::new(),
+            changelog: ChangeLog::new(),
+            scroll: ScrollSync::new(),
         }
     }
 }
@@ -28,18 +40,113 @@ impl EguiValueDemoApp {
 impl eframe::App for EguiValueDemoApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
         egui::TopBottomPanel::top("header").show(ctx, |ui| {
-            egui::widgets::global_dark_light_mode_switch(ui);
+            ui.horizontal(|ui| {
+                egui::widgets::global_dark_light_mode_switch(ui);
+                ui.checkbox(&mut self.flat, "Flat");
+                ui.selectable_value(&mut self.config.layout, LayoutMode::Tree, "Tree");
+                ui.selectable_value(&mut self.config.layout, LayoutMode::Columns, "Columns");
+
+                if ui.button("3x3 matrix").clicked() {
+                    let desc = Desc::Matrix {
+                        rows: 3,
+                        cols: 3,
+                        min: None,
+                        max: None,
+                        identity: true,
+                    };
+                    self.value = desc.default_value();
+                    self.desc = Some(desc);
+                }
+
+                if ui.button("Optional int").clicked() {
+                    let desc = Desc::Optional {
+                        inner: Box::new(Desc::Int {
+                            min: Some(0),
+                            max: Some(100),
+                            min_path: None,
+                            max_path: None,
+                            humanize: None,
+                            stepper: None,
+                            step: None,
+                        }),
+                    };
+                    self.value = desc.default_value();
+                    self.desc = Some(desc);
+                }
+
+                if ui.button("String list").on_hover_text("Drag items by their grip to reorder").clicked() {
+                    let desc = Desc::List {
+                        elem_desc: Some(Box::new(Desc::String {
+                            variants: None,
+                            trim: false,
+                            collapse_whitespace: false,
+                            min_len: None,
+                            max_len: None,
+                            pattern: None,
+                            multiline: false,
+                        })),
+                        empty_hint: None,
+                        collapsed_by_default: false,
+                        min_len: None,
+                        max_len: None,
+                        multi_select: false,
+                        unique_by: None,
+                    };
+                    let names = ["alpha", "beta", "gamma", "delta", "epsilon"];
+                    self.value = Value::List(names.iter().map(|name| Value::String(name.to_string())).collect());
+                    self.desc = Some(desc);
+                }
+
+                if ui.button("Infer desc").on_hover_text("Describe the current value").clicked() {
+                    self.desc = Some(Desc::infer(&self.value));
+                }
+
+                if ui.add_enabled(self.history.can_undo(), egui::Button::new("Undo")).clicked() {
+                    self.history.undo(&mut self.value);
+                }
+                if ui.add_enabled(self.history.can_redo(), egui::Button::new("Redo")).clicked() {
+                    self.history.redo(&mut self.value);
+                }
+            });
         });
 
         egui::SidePanel::left("desc").show(ctx, |ui| {
-            Probe::new("Desc", &mut self.desc).show(ui);
+            self.scroll.show(ui, 0, |ui| {
+                Probe::new("Desc", &mut self.desc).show(ui);
+
+                if let Some(desc) = &self.desc {
+                    ui.separator();
+                    DefaultPreview::new(desc, "demo-default").show(ui);
+                }
+            });
+        });
+
+        egui::SidePanel::right("outline").show(ctx, |ui| {
+            Outline::new(&self.value, "demo-value").show(ui);
+        });
+
+        egui::TopBottomPanel::bottom("changelog").show(ctx, |ui| {
+            egui::ScrollArea::vertical().max_height(120.0).show(ui, |ui| {
+                self.changelog.show(ui);
+            });
         });
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            egui::ScrollArea::vertical().show(ui, |ui|{
-                let mut value_probe = ValueProbe::new(self.desc.as_ref(), &mut self.value, "demo-value");
-                Probe::new("Value", &mut value_probe).show(ui);
+            Breadcrumb::new("demo-value").show(ui);
+            ui.separator();
+
+            self.scroll.show(ui, 1, |ui| {
+                let mut value_probe =
+                    ValueProbe::new(self.desc.as_ref(), &mut self.value, "demo-value").with_config(&self.config);
+                if self.flat {
+                    value_probe.show_flat(ui);
+                } else {
+                    value_probe.show(ui, "Value");
+                }
             });
         });
+
+        self.history.update(ctx, &self.value);
+        self.changelog.update(ctx, &self.value);
     }
 }
@@ -0,0 +1,50 @@
+use egui_any::{Desc, Value, ValueProbe};
+
+const DOCUMENT: &str = r#"{
+    "name": "egui-any",
+    "version": 1,
+    "ratio": 0.5,
+    "enabled": true,
+    "tags": ["gui", "schema"],
+    "window": { "width": 800, "height": 600 }
+}"#;
+
+fn main() {
+    let native_options = eframe::NativeOptions::default();
+    eframe::run_native(
+        "egui-any json example",
+        native_options,
+        Box::new(|_cc| Box::new(JsonApp::new())),
+    )
+    .unwrap();
+}
+
+struct JsonApp {
+    desc: Desc,
+    value: Value,
+}
+
+impl JsonApp {
+    fn new() -> Self {
+        let json: serde_json::Value = serde_json::from_str(DOCUMENT).unwrap();
+        let value = Value::from(json);
+
+        JsonApp {
+            desc: Desc::infer(&value),
+            value,
+        }
+    }
+}
+
+impl eframe::App for JsonApp {
+    fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        egui::SidePanel::right("json").show(ctx, |ui| {
+            let json = serde_json::Value::from(self.value.clone());
+            ui.monospace(serde_json::to_string_pretty(&json).unwrap());
+        });
+
+        egui::CentralPanel::default().show(ctx, |ui| {
+            ValueProbe::new(Some(&self.desc), &mut self.value, "json-value").show(ui, "Document");
+        });
+    }
+}
@@ -0,0 +1,135 @@
+use crate::{validate::validate, Change, Desc, ProbeError, Severity, Value};
+
+/// Kind of change of a single path.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Modified,
+}
+
+/// Effect of applying a staged value to a target, see `Value::dry_run_apply`.
+#[derive(Clone, Debug, Default)]
+pub struct ApplyReport {
+    /// Paths that would change, in the order reported by `Value::diff`.
+    pub changes: Vec<(String, ChangeKind)>,
+
+    /// Errors the staged value has against the descriptor.
+    pub errors: Vec<ProbeError>,
+}
+
+impl ApplyReport {
+    /// Returns `true` if applying would change nothing.
+    pub fn is_unchanged(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns `true` if the staged value has errors.
+    /// Warnings and infos don't block applying.
+    pub fn is_blocked(&self) -> bool {
+        self.errors.iter().any(|err| err.severity() == Severity::Error)
+    }
+}
+
+impl Value {
+    /// Reports what replacing `target` with this staged value would change,
+    /// and which errors the result would have against `desc`.
+    ///
+    /// Neither value is modified.
+    pub fn dry_run_apply(&self, target: &Value, desc: Option<&Desc>) -> ApplyReport {
+        let mut report = ApplyReport::default();
+
+        self.diff(target, &mut |change| {
+            let kind = match change {
+                Change::Added { .. } => ChangeKind::Added,
+                Change::Removed { .. } => ChangeKind::Removed,
+                Change::Modified { .. } => ChangeKind::Modified,
+            };
+            report.changes.push((change.path().to_owned(), kind));
+        });
+
+        if let Some(desc) = desc {
+            validate(desc, self, self, "", &mut report.errors);
+        }
+
+        report
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::int_range, StructField};
+
+    fn custom(severity: Severity) -> ProbeError {
+        ProbeError::Custom {
+            path: "name".to_owned(),
+            message: "check".to_owned(),
+            severity,
+        }
+    }
+
+    #[test]
+    fn only_errors_block() {
+        let mut report = ApplyReport {
+            changes: Vec::new(),
+            errors: vec![custom(Severity::Warning), custom(Severity::Info)],
+        };
+        assert!(!report.is_blocked());
+
+        report.errors.push(custom(Severity::Error));
+        assert!(report.is_blocked());
+    }
+
+
+    fn settings(retries: i64, name: &str) -> Value {
+        [("retries".to_owned(), Value::Int(retries)), ("name".to_owned(), Value::from(name))]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn same_value_is_unchanged() {
+        let report = settings(1, "a").dry_run_apply(&settings(1, "a"), None);
+        assert!(report.is_unchanged());
+        assert!(!report.is_blocked());
+    }
+
+    #[test]
+    fn changes_are_listed_by_path() {
+        let mut staged = settings(2, "a");
+        if let Value::Map(values) = &mut staged {
+            values.shift_remove("name");
+            values.insert("extra".to_owned(), Value::Bool(true));
+        }
+
+        let report = staged.dry_run_apply(&settings(1, "a"), None);
+        assert_eq!(
+            report.changes,
+            [
+                ("extra".to_owned(), ChangeKind::Added),
+                ("name".to_owned(), ChangeKind::Removed),
+                ("retries".to_owned(), ChangeKind::Modified),
+            ]
+        );
+    }
+
+    #[test]
+    fn staged_errors_block() {
+        let desc = Desc::Struct {
+            fields: vec![
+                StructField::new("retries", int_range(Some(0), Some(5))),
+                StructField::new("name", Desc::from_value(&Value::from("x"))),
+            ],
+        };
+
+        let report = settings(9, "a").dry_run_apply(&settings(1, "a"), Some(&desc));
+        assert_eq!(report.changes, [("retries".to_owned(), ChangeKind::Modified)]);
+        assert!(report.is_blocked());
+        assert_eq!(report.errors.len(), 1);
+        assert_eq!(report.errors[0].path(), "retries");
+
+        let report = settings(3, "a").dry_run_apply(&settings(9, "a"), Some(&desc));
+        assert!(!report.is_blocked());
+    }
+}
@@ -0,0 +1,285 @@
+use std::fmt;
+
+use indexmap::IndexMap;
+
+use crate::Value;
+
+const BOOL_FALSE: u8 = 0;
+const BOOL_TRUE: u8 = 1;
+const INT: u8 = 2;
+const FLOAT: u8 = 3;
+const STRING: u8 = 4;
+const LIST: u8 = 5;
+const MAP: u8 = 6;
+const ENUM: u8 = 7;
+const NULL: u8 = 8;
+const BYTES: u8 = 9;
+const COLOR: u8 = 10;
+const UINT: u8 = 11;
+
+/// Error returned by `Value::from_bytes`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum DecodeError {
+    /// The input ended in the middle of a value.
+    UnexpectedEnd,
+
+    /// A value starts with an unknown kind tag.
+    UnknownTag(u8),
+
+    /// A string or map key is not valid UTF-8.
+    InvalidUtf8,
+
+    /// A number doesn't fit its type.
+    Overflow,
+
+    /// The input continues after the value.
+    TrailingBytes,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEnd => f.write_str("Unexpected end of input"),
+            DecodeError::UnknownTag(tag) => write!(f, "Unknown value tag {tag}"),
+            DecodeError::InvalidUtf8 => f.write_str("String is not valid UTF-8"),
+            DecodeError::Overflow => f.write_str("Number is too large"),
+            DecodeError::TrailingBytes => f.write_str("Unexpected bytes after the value"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+impl Value {
+    /// Encodes the value into a compact self-describing binary snapshot.
+    ///
+    /// Every value starts with a one-byte kind tag.
+    /// Integers and lengths are LEB128 varints, signed integers zigzag-encoded first,
+    /// floats are 8 little-endian bytes, colors are 4 bytes in RGBA order,
+    /// and strings and byte blobs are prefixed with their length.
+    /// Map entries are written in key order, so equal values encode to equal bytes.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut out = Vec::new();
+        encode(self, &mut out);
+        out
+    }
+
+    /// Decodes a snapshot produced by `Value::to_bytes`.
+    pub fn from_bytes(bytes: &[u8]) -> Result<Value, DecodeError> {
+        let mut input = bytes;
+        let value = decode(&mut input)?;
+        match input.is_empty() {
+            true => Ok(value),
+            false => Err(DecodeError::TrailingBytes),
+        }
+    }
+}
+
+fn encode(value: &Value, out: &mut Vec<u8>) {
+    match value {
+        Value::Bool(false) => out.push(BOOL_FALSE),
+        Value::Bool(true) => out.push(BOOL_TRUE),
+        Value::Int(value) => {
+            out.push(INT);
+            write_varint(((value << 1) ^ (value >> 63)) as u64, out);
+        }
+        Value::Uint(value) => {
+            out.push(UINT);
+            write_varint(*value, out);
+        }
+        Value::Float(value) => {
+            out.push(FLOAT);
+            out.extend_from_slice(&value.to_le_bytes());
+        }
+        Value::String(value) => {
+            out.push(STRING);
+            write_str(value, out);
+        }
+        Value::Bytes(bytes) => {
+            out.push(BYTES);
+            write_varint(bytes.len() as u64, out);
+            out.extend_from_slice(bytes);
+        }
+        Value::Color(rgba) => {
+            out.push(COLOR);
+            out.extend_from_slice(rgba);
+        }
+        Value::List(elems) => {
+            out.push(LIST);
+            write_varint(elems.len() as u64, out);
+            for elem in elems {
+                encode(elem, out);
+            }
+        }
+        Value::Map(values) => {
+            out.push(MAP);
+            write_varint(values.len() as u64, out);
+
+            let mut keys: Vec<&String> = values.keys().collect();
+            keys.sort();
+            for key in keys {
+                write_str(key, out);
+                encode(&values[key], out);
+            }
+        }
+        Value::Enum { variant, value } => {
+            out.push(ENUM);
+            write_str(variant, out);
+            encode(value, out);
+        }
+        Value::Null => out.push(NULL),
+    }
+}
+
+fn write_varint(mut n: u64, out: &mut Vec<u8>) {
+    while n >= 0x80 {
+        out.push(n as u8 | 0x80);
+        n >>= 7;
+    }
+    out.push(n as u8);
+}
+
+fn write_str(s: &str, out: &mut Vec<u8>) {
+    write_varint(s.len() as u64, out);
+    out.extend_from_slice(s.as_bytes());
+}
+
+fn decode(input: &mut &[u8]) -> Result<Value, DecodeError> {
+    let value = match take(input, 1)?[0] {
+        BOOL_FALSE => Value::Bool(false),
+        BOOL_TRUE => Value::Bool(true),
+        INT => {
+            let n = read_varint(input)?;
+            Value::Int((n >> 1) as i64 ^ -((n & 1) as i64))
+        }
+        UINT => Value::Uint(read_varint(input)?),
+        FLOAT => {
+            let bytes = take(input, 8)?.try_into().unwrap();
+            Value::Float(f64::from_le_bytes(bytes))
+        }
+        STRING => Value::String(read_str(input)?),
+        BYTES => {
+            let len = read_len(input)?;
+            Value::Bytes(take(input, len)?.to_vec())
+        }
+        COLOR => Value::Color(take(input, 4)?.try_into().unwrap()),
+        LIST => {
+            let len = read_len(input)?;
+            let mut elems = Vec::with_capacity(len.min(input.len()));
+            for _ in 0..len {
+                elems.push(decode(input)?);
+            }
+            Value::List(elems)
+        }
+        MAP => {
+            let len = read_len(input)?;
+            let mut values = IndexMap::with_capacity(len.min(input.len()));
+            for _ in 0..len {
+                let key = read_str(input)?;
+                values.insert(key, decode(input)?);
+            }
+            Value::Map(values)
+        }
+        ENUM => {
+            let variant = read_str(input)?;
+            let value = Box::new(decode(input)?);
+            Value::Enum { variant, value }
+        }
+        NULL => Value::Null,
+        tag => return Err(DecodeError::UnknownTag(tag)),
+    };
+    Ok(value)
+}
+
+fn take<'a>(input: &mut &'a [u8], len: usize) -> Result<&'a [u8], DecodeError> {
+    if input.len() < len {
+        return Err(DecodeError::UnexpectedEnd);
+    }
+    let (head, rest) = input.split_at(len);
+    *input = rest;
+    Ok(head)
+}
+
+fn read_varint(input: &mut &[u8]) -> Result<u64, DecodeError> {
+    let mut n = 0u64;
+    for shift in (0..64).step_by(7) {
+        let byte = take(input, 1)?[0];
+        let bits = u64::from(byte & 0x7f);
+        if shift == 63 && bits > 1 {
+            return Err(DecodeError::Overflow);
+        }
+        n |= bits << shift;
+        if byte & 0x80 == 0 {
+            return Ok(n);
+        }
+    }
+    Err(DecodeError::Overflow)
+}
+
+fn read_len(input: &mut &[u8]) -> Result<usize, DecodeError> {
+    usize::try_from(read_varint(input)?).map_err(|_| DecodeError::Overflow)
+}
+
+fn read_str(input: &mut &[u8]) -> Result<String, DecodeError> {
+    let len = read_len(input)?;
+    let bytes = take(input, len)?;
+    String::from_utf8(bytes.to_vec()).map_err(|_| DecodeError::InvalidUtf8)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn every_kind() -> Value {
+        let map: Value = [
+            ("b".to_owned(), Value::Bool(true)),
+            ("a".to_owned(), Value::Bool(false)),
+            ("é".to_owned(), Value::from("ünïcode")),
+        ]
+        .into_iter()
+        .collect();
+        Value::List(vec![
+            Value::Int(0),
+            Value::Int(-1),
+            Value::Int(i64::MIN),
+            Value::Int(i64::MAX),
+            Value::Uint(u64::MAX),
+            Value::Float(-0.5),
+            Value::Float(f64::INFINITY),
+            Value::String(String::new()),
+            Value::Bytes(vec![0, 255, 128]),
+            Value::Color([1, 2, 3, 4]),
+            Value::List(Vec::new()),
+            map,
+            Value::Enum {
+                variant: "Some".to_owned(),
+                value: Box::new(Value::Null),
+            },
+            Value::Null,
+        ])
+    }
+
+    #[test]
+    fn round_trips_every_kind() {
+        let value = every_kind();
+        assert_eq!(Value::from_bytes(&value.to_bytes()), Ok(value));
+    }
+
+    #[test]
+    fn map_order_does_not_change_bytes() {
+        let ab: Value = [("a".to_owned(), Value::Int(1)), ("b".to_owned(), Value::Int(2))].into_iter().collect();
+        let ba: Value = [("b".to_owned(), Value::Int(2)), ("a".to_owned(), Value::Int(1))].into_iter().collect();
+        assert_eq!(ab.to_bytes(), ba.to_bytes());
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        let bytes = every_kind().to_bytes();
+        assert_eq!(Value::from_bytes(&bytes[..bytes.len() - 1]), Err(DecodeError::UnexpectedEnd));
+        assert_eq!(Value::from_bytes(&[NULL, NULL]), Err(DecodeError::TrailingBytes));
+        assert_eq!(Value::from_bytes(&[200]), Err(DecodeError::UnknownTag(200)));
+        assert_eq!(Value::from_bytes(&[STRING, 2, 0xC3, 0x28]), Err(DecodeError::InvalidUtf8));
+        assert_eq!(Value::from_bytes(&[UINT, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x01]), Err(DecodeError::Overflow));
+        assert_eq!(Value::from_bytes(&[]), Err(DecodeError::UnexpectedEnd));
+    }
+}
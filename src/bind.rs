@@ -0,0 +1,138 @@
+use crate::{path, DescribeValue, Value};
+
+/// Rust variables bound to nested values, kept in sync both ways.
+///
+/// Call [`Bindings::sync`] around showing the value:
+/// bound variables are written into the value before it is shown,
+/// and edits of the value are written back into the variables afterwards.
+/// Bindings to paths that don't exist in the value are skipped.
+#[derive(Default)]
+pub struct Bindings<'a> {
+    binds: Vec<(String, &'a mut dyn Bind)>,
+}
+
+impl<'a> Bindings<'a> {
+    pub fn new() -> Self {
+        Bindings::default()
+    }
+
+    /// Binds `var` to the value at `path`, e.g. `servers[0].port`.
+    pub fn bind<T: DescribeValue>(mut self, path: impl Into<String>, var: &'a mut T) -> Self {
+        self.binds.push((path.into(), var));
+        self
+    }
+
+    /// Writes bound variables into `value`.
+    pub fn read(&self, value: &mut Value) {
+        for (path, var) in &self.binds {
+            if let Some(value) = path::lookup_mut(value, path) {
+                *value = var.to_value();
+            }
+        }
+    }
+
+    /// Writes nested values back into bound variables.
+    ///
+    /// Variables are left unchanged if their value doesn't convert back,
+    /// e.g. when its kind was changed.
+    pub fn write(&mut self, value: &Value) {
+        for (path, var) in &mut self.binds {
+            if let Some(value) = path::lookup(value, path) {
+                var.set_value(value);
+            }
+        }
+    }
+
+    /// Writes bound variables into `value`, calls `f` to show it,
+    /// and writes the result back into the variables.
+    pub fn sync<R>(&mut self, value: &mut Value, f: impl FnOnce(&mut Value) -> R) -> R {
+        self.read(value);
+        let result = f(value);
+        self.write(value);
+        result
+    }
+}
+
+/// Object-safe part of `DescribeValue` used by bindings.
+trait Bind {
+    fn to_value(&self) -> Value;
+    fn set_value(&mut self, value: &Value);
+}
+
+impl<T: DescribeValue> Bind for T {
+    fn to_value(&self) -> Value {
+        DescribeValue::to_value(self)
+    }
+
+    fn set_value(&mut self, value: &Value) {
+        if let Some(value) = T::from_value(value) {
+            *self = value;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tests::{shown_texts, type_into_text},
+        Desc, StructField, ValueProbe,
+    };
+
+    #[test]
+    fn probe_edits_and_variable_stay_in_sync() {
+        let mut value: Value = [("name".to_owned(), Value::from("web")), ("port".to_owned(), Value::Int(0))]
+            .into_iter()
+            .collect();
+        let desc = Desc::Struct {
+            fields: vec![
+                StructField::new("name", Desc::from_value(&Value::from("x"))),
+                StructField::new("port", Desc::from_value(&Value::Int(0))),
+            ],
+        };
+        let mut port: i64 = 80;
+
+        // The variable is shown in place of the value.
+        let texts = shown_texts(|ui| {
+            Bindings::new().bind("port", &mut port).sync(&mut value, |value| {
+                ValueProbe::new(Some(&desc), value, "value").show_flat(ui);
+            });
+        });
+        assert!(texts.iter().any(|text| text == "80"), "{texts:?}");
+        assert_eq!(path::lookup(&value, "port"), Some(&Value::Int(80)));
+
+        // Edits in the probe are written back.
+        type_into_text(&egui::Context::default(), "80", 0, "8080", |ui| {
+            Bindings::new().bind("port", &mut port).sync(&mut value, |value| {
+                ValueProbe::new(Some(&desc), value, "value").show_flat(ui);
+            });
+        })
+        .unwrap();
+        assert_eq!(port, 8080);
+
+        // Changes of the variable win over the value on the next frame.
+        port = 443;
+        shown_texts(|ui| {
+            Bindings::new().bind("port", &mut port).sync(&mut value, |value| {
+                ValueProbe::new(Some(&desc), value, "value").show_flat(ui);
+            });
+        });
+        assert_eq!(path::lookup(&value, "port"), Some(&Value::Int(443)));
+        assert_eq!(path::lookup(&value, "name"), Some(&Value::from("web")));
+    }
+
+    #[test]
+    fn unconvertible_and_missing_values_are_skipped() {
+        let mut value: Value = [("port".to_owned(), Value::from("http"))].into_iter().collect();
+        let mut port: i64 = 80;
+        let mut missing = true;
+
+        let mut bindings = Bindings::new().bind("port", &mut port).bind("enabled", &mut missing);
+        bindings.write(&value);
+        drop(bindings);
+        assert_eq!((port, missing), (80, true));
+
+        Bindings::new().bind("enabled", &mut missing).read(&mut value);
+        assert_eq!(path::lookup(&value, "enabled"), None);
+    }
+}
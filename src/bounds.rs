@@ -0,0 +1,187 @@
+use egui::{Id, Ui};
+use hashbrown::HashMap;
+
+use crate::{path, Desc, Value};
+
+/// Values of bounds referenced by path, resolved once per frame by the root probe.
+#[derive(Clone, Default)]
+struct ResolvedBounds(HashMap<String, Option<Value>>);
+
+/// Number type a bound can be resolved to.
+///
+/// Static bounds keep their type, only values found by path are converted.
+pub(crate) trait Bound: Copy + PartialOrd + std::fmt::Display {
+    /// Converts a number found by path, saturating when it doesn't fit.
+    fn from_value(value: &Value) -> Option<Self>;
+
+    fn to_f64(self) -> f64;
+}
+
+impl Bound for i64 {
+    fn from_value(value: &Value) -> Option<i64> {
+        match *value {
+            Value::Int(bound) => Some(bound),
+            Value::Uint(bound) => Some(i64::try_from(bound).unwrap_or(i64::MAX)),
+            Value::Float(bound) if !bound.is_nan() => Some(bound as i64),
+            _ => None,
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Bound for u64 {
+    fn from_value(value: &Value) -> Option<u64> {
+        match *value {
+            Value::Int(bound) => Some(u64::try_from(bound).unwrap_or(0)),
+            Value::Uint(bound) => Some(bound),
+            Value::Float(bound) if !bound.is_nan() => Some(bound as u64),
+            _ => None,
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        self as f64
+    }
+}
+
+impl Bound for f64 {
+    fn from_value(value: &Value) -> Option<f64> {
+        match *value {
+            Value::Int(bound) => Some(bound as f64),
+            Value::Uint(bound) => Some(bound as f64),
+            Value::Float(bound) => Some(bound),
+            _ => None,
+        }
+    }
+
+    fn to_f64(self) -> f64 {
+        self
+    }
+}
+
+/// Resolves all bound paths referenced in `desc` against the document.
+pub(crate) fn resolve_all(ui: &Ui, root: Id, desc: &Desc, value: &Value) {
+    let mut paths = Vec::new();
+    collect_paths(desc, &mut paths);
+
+    let resolved = paths
+        .into_iter()
+        .map(|bound_path| {
+            let bound = path::lookup(value, bound_path)
+                .filter(|bound| matches!(bound, Value::Int(_) | Value::Uint(_) | Value::Float(_)))
+                .cloned();
+            (bound_path.to_owned(), bound)
+        })
+        .collect();
+
+    ui.ctx()
+        .data_mut(|d| d.insert_temp(root.with("Bounds"), ResolvedBounds(resolved)));
+}
+
+fn collect_paths<'a>(desc: &'a Desc, paths: &mut Vec<&'a str>) {
+    match desc {
+        Desc::Int { min_path, max_path, .. } | Desc::Float { min_path, max_path, .. } => {
+            paths.extend(min_path.as_deref());
+            paths.extend(max_path.as_deref());
+        }
+        Desc::List { elem_desc: Some(inner), .. } | Desc::Map { value_desc: Some(inner), .. } => {
+            collect_paths(inner, paths)
+        }
+        Desc::Enum { variants } => {
+            for desc in variants.iter().filter_map(|variant| variant.desc.as_ref()) {
+                collect_paths(desc, paths);
+            }
+        }
+        Desc::Struct { fields } => {
+            for field in fields {
+                collect_paths(&field.desc, paths);
+            }
+        }
+        Desc::Tuple { elems } => {
+            for elem in elems {
+                collect_paths(elem, paths);
+            }
+        }
+        Desc::Optional { inner } | Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner } => collect_paths(inner, paths),
+        _ => {}
+    }
+}
+
+/// Returns the value of the bound referenced by `bound_path`, or `fallback` if there is no reference.
+///
+/// If the referenced value is missing or is not a number,
+/// shows a warning and returns `fallback`.
+pub(crate) fn resolve<T: Bound>(
+    ui: &mut Ui,
+    root: Id,
+    bound_path: Option<&str>,
+    fallback: Option<T>,
+) -> Option<T> {
+    let Some(bound_path) = bound_path else {
+        return fallback;
+    };
+
+    let bound = ui
+        .ctx()
+        .data(|d| d.get_temp::<ResolvedBounds>(root.with("Bounds")))
+        .and_then(|resolved| resolved.0.get(bound_path)?.as_ref().and_then(T::from_value));
+
+    match bound {
+        Some(bound) => Some(bound),
+        None => {
+            ui.label(egui::RichText::new("⚠").color(ui.visuals().warn_fg_color))
+                .on_hover_text(format!(
+                    "Bound `{bound_path}` is missing or is not a number, static bound is used"
+                ));
+            fallback
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn path_bounds_saturate() {
+        assert_eq!(i64::from_value(&Value::Uint(u64::MAX)), Some(i64::MAX));
+        assert_eq!(u64::from_value(&Value::Int(-3)), Some(0));
+        assert_eq!(u64::from_value(&Value::Float(-1.5)), Some(0));
+        assert_eq!(i64::from_value(&Value::Float(f64::NAN)), None);
+        assert_eq!(f64::from_value(&Value::Bool(true)), None);
+    }
+
+    #[test]
+    fn static_bound_is_kept() {
+        let ctx = egui::Context::default();
+        let big = i64::MAX - 1;
+        let _ = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                let root = Id::new("root");
+                assert_eq!(resolve(ui, root, None, Some(big)), Some(big));
+
+                let value: Value = [("max".to_owned(), Value::Int(big))].into_iter().collect();
+                let desc = Desc::Struct {
+                    fields: vec![crate::StructField::new(
+                        "max",
+                        Desc::Int {
+                            min: None,
+                            max: None,
+                            min_path: None,
+                            max_path: Some("max".to_owned()),
+                            humanize: None,
+                            stepper: None,
+                            step: None,
+                        },
+                    )],
+                };
+                resolve_all(ui, root, &desc, &value);
+                assert_eq!(resolve(ui, root, Some("max"), Some(0)), Some(big));
+                assert_eq!(resolve::<i64>(ui, root, Some("missing"), Some(7)), Some(7));
+            });
+        });
+    }
+}
@@ -0,0 +1,71 @@
+use std::hash::Hash;
+
+use egui::{Context, Id, Response, Ui};
+
+use crate::{outline, path};
+
+/// Path of the value whose widget had focus most recently.
+#[derive(Clone, Default)]
+struct FocusPath(String);
+
+/// Breadcrumb of the path to the focused value, e.g. `root > items > [2] > name`.
+///
+/// Follows the focus in the `ValueProbe` created with the same `id_source`.
+/// Clicking a segment scrolls the probe to that ancestor.
+/// If the ancestor is inside a collapsed collection, its closest shown ancestor is scrolled to instead.
+pub struct Breadcrumb {
+    id_source: Id,
+}
+
+impl Breadcrumb {
+    pub fn new(id_source: impl Hash) -> Self {
+        Breadcrumb {
+            id_source: Id::new(id_source),
+        }
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let focused = ui
+            .ctx()
+            .data(|d| d.get_temp::<FocusPath>(self.id_source.with("FocusPath")))
+            .unwrap_or_default();
+
+        let mut clicked = None;
+
+        let r = ui
+            .horizontal_wrapped(|ui| {
+                ui.spacing_mut().item_spacing.x = 4.0;
+
+                if ui.link("root").clicked() {
+                    clicked = Some(String::new());
+                }
+
+                if !focused.0.is_empty() {
+                    for (label, path) in path::ancestors(&focused.0) {
+                        ui.weak(">");
+                        if ui.link(label).clicked() {
+                            clicked = Some(path);
+                        }
+                    }
+                }
+            })
+            .response;
+
+        if let Some(path) = clicked {
+            outline::request_scroll(ui.ctx(), self.id_source, path);
+        }
+
+        r
+    }
+}
+
+/// Remembers `path` as the focused one.
+/// Called by probes whose widgets have focus.
+pub(crate) fn set_focus_path(ctx: &Context, root: Id, path: &str) {
+    ctx.data_mut(|d| {
+        let focused = d.get_temp_mut_or_default::<FocusPath>(root.with("FocusPath"));
+        if focused.0 != path {
+            focused.0 = path.to_owned();
+        }
+    });
+}
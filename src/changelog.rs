@@ -0,0 +1,219 @@
+use std::{
+    fmt,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+use egui::{Context, Response, Ui};
+
+use crate::{diff::preview, Change, ChangeKind, Value};
+
+/// Single entry of a `ChangeLog`.
+#[derive(Clone, Debug)]
+pub struct LogEntry {
+    /// When the change was recorded.
+    pub time: SystemTime,
+
+    /// Path of the changed value.
+    pub path: String,
+
+    pub kind: ChangeKind,
+
+    /// Human-readable description, e.g. ``modified `port`: 80 → 8080``.
+    pub description: String,
+}
+
+impl fmt::Display for LogEntry {
+    /// Formats the entry as `HH:MM:SS description` with the time in UTC.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let secs = self.time.duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs()) % 86400;
+        write!(
+            f,
+            "{:02}:{:02}:{:02} {}",
+            secs / 3600,
+            secs / 60 % 60,
+            secs % 60,
+            self.description
+        )
+    }
+}
+
+/// Running log of changes made to a value edited with `ValueProbe`.
+///
+/// Call [`ChangeLog::update`] each frame after the value is shown.
+/// Like with `History`, edits made while dragging are logged once the drag stops.
+#[derive(Clone, Default)]
+pub struct ChangeLog {
+    entries: Vec<LogEntry>,
+    current: Option<Value>,
+    drag_start: Option<Value>,
+}
+
+impl ChangeLog {
+    pub fn new() -> Self {
+        ChangeLog::default()
+    }
+
+    /// Logs changes made to the value since the last call.
+    pub fn update(&mut self, ctx: &Context, value: &Value) {
+        let dragging = ctx.dragged_id().is_some();
+
+        match &mut self.current {
+            None => self.current = Some(value.clone()),
+            Some(current) if current != value => {
+                let previous = std::mem::replace(current, value.clone());
+                if dragging {
+                    self.drag_start.get_or_insert(previous);
+                } else {
+                    self.log(&previous, value);
+                }
+            }
+            Some(_) => {}
+        }
+
+        if !dragging {
+            if let Some(start) = self.drag_start.take() {
+                self.log(&start, value);
+            }
+        }
+    }
+
+    /// Logs every difference between `old` and `new`.
+    pub fn log(&mut self, old: &Value, new: &Value) {
+        let time = SystemTime::now();
+        new.diff(old, &mut |change| {
+            let (kind, description) = match change {
+                Change::Added { path, value } => (
+                    ChangeKind::Added,
+                    format!("added `{path}`: {}", preview(value)),
+                ),
+                Change::Removed { path, value } => (
+                    ChangeKind::Removed,
+                    format!("removed `{path}`: {}", preview(value)),
+                ),
+                Change::Modified { path, old, new } => (
+                    ChangeKind::Modified,
+                    format!("modified `{path}`: {} → {}", preview(old), preview(new)),
+                ),
+            };
+            self.entries.push(LogEntry {
+                time,
+                path: change.path().to_owned(),
+                kind,
+                description,
+            });
+        });
+    }
+
+    /// Returns logged entries, oldest first.
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Returns the log as text, one entry per line.
+    pub fn export(&self) -> String {
+        self.entries.iter().map(|entry| format!("{entry}\n")).collect()
+    }
+
+    /// Shows the log with buttons to clear it and to copy it to the clipboard.
+    pub fn show(&mut self, ui: &mut Ui) -> Response {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.strong(format!("Changes ({})", self.entries.len()));
+                if ui.add_enabled(!self.entries.is_empty(), egui::Button::new("Copy").small()).clicked() {
+                    ui.output_mut(|o| o.copied_text = self.export());
+                }
+                if ui.add_enabled(!self.entries.is_empty(), egui::Button::new("Clear").small()).clicked() {
+                    self.clear();
+                }
+            });
+
+            for entry in self.entries.iter().rev() {
+                ui.label(entry.to_string());
+            }
+        })
+        .response
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::Id;
+    use indexmap::IndexMap;
+
+    use super::*;
+
+    fn map(entries: &[(&str, i64)]) -> Value {
+        Value::Map(
+            entries
+                .iter()
+                .map(|&(key, value)| (key.to_owned(), Value::Int(value)))
+                .collect::<IndexMap<_, _>>(),
+        )
+    }
+
+    fn descriptions(log: &ChangeLog) -> Vec<&str> {
+        log.entries().iter().map(|entry| entry.description.as_str()).collect()
+    }
+
+    #[test]
+    fn update_logs_additions_modifications_and_removals() {
+        let ctx = Context::default();
+        let mut log = ChangeLog::new();
+
+        log.update(&ctx, &map(&[("port", 80)]));
+        assert!(log.entries().is_empty());
+
+        log.update(&ctx, &map(&[("port", 80), ("timeout", 30)]));
+        log.update(&ctx, &map(&[("port", 8080), ("timeout", 30)]));
+        log.update(&ctx, &map(&[("port", 8080)]));
+        log.update(&ctx, &map(&[("port", 8080)]));
+
+        assert_eq!(
+            descriptions(&log),
+            [
+                "added `timeout`: 30",
+                "modified `port`: 80 → 8080",
+                "removed `timeout`: 30",
+            ]
+        );
+        let kinds: Vec<_> = log.entries().iter().map(|entry| entry.kind).collect();
+        assert_eq!(kinds, [ChangeKind::Added, ChangeKind::Modified, ChangeKind::Removed]);
+        assert_eq!(log.entries()[1].path, "port");
+    }
+
+    #[test]
+    fn edits_made_while_dragging_are_logged_once() {
+        let ctx = Context::default();
+        let mut log = ChangeLog::new();
+        log.update(&ctx, &map(&[("port", 80)]));
+
+        ctx.set_dragged_id(Id::new("drag"));
+        for port in [81, 82, 83] {
+            log.update(&ctx, &map(&[("port", port)]));
+        }
+        assert!(log.entries().is_empty());
+
+        ctx.stop_dragging();
+        log.update(&ctx, &map(&[("port", 83)]));
+        assert_eq!(descriptions(&log), ["modified `port`: 80 → 83"]);
+    }
+
+    #[test]
+    fn export_writes_one_line_per_entry() {
+        let mut log = ChangeLog::new();
+        log.log(&map(&[("port", 80)]), &map(&[("port", 81)]));
+        log.log(&map(&[("port", 81)]), &map(&[("port", 82)]));
+
+        let export = log.export();
+        let lines: Vec<_> = export.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[1].ends_with(" modified `port`: 81 → 82"));
+
+        log.clear();
+        assert!(log.export().is_empty());
+    }
+}
@@ -0,0 +1,417 @@
+use std::fmt;
+
+use crate::{clamp, diff::preview, normalize_whitespace, path, pattern, truncate_chars, Desc, Kind, Value};
+
+/// Change made to a value, or input rejected, to make it match its descriptor.
+///
+/// Reported by `Value::coerce_with` and by `ValueProbe` to the hook set with `ValueProbe::with_on_coerce`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CoercionEvent {
+    /// Path of the value, e.g. `servers[0].port`.
+    pub path: String,
+
+    /// The value or the entered text that didn't match, e.g. `42` or `"abc"`.
+    pub attempted: String,
+
+    pub reason: CoercionReason,
+}
+
+/// Why a value was coerced.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CoercionReason {
+    /// The value was converted from kind `from` to kind `to`.
+    Converted { from: Kind, to: Kind },
+
+    /// The number was clamped to the range of the descriptor.
+    Clamped,
+
+    /// The value was replaced with the default of the descriptor.
+    Reset,
+
+    /// The value or the entered text has no sensible conversion and was rejected.
+    Rejected,
+
+    /// The struct field is not in the descriptor and was dropped.
+    Dropped,
+}
+
+impl fmt::Display for CoercionEvent {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = match self.path.as_str() {
+            "" => "(root)",
+            path => path,
+        };
+        match &self.reason {
+            CoercionReason::Converted { from, to } => {
+                write!(f, "`{path}`: converted {from} {} to {to}", self.attempted)
+            }
+            CoercionReason::Clamped => write!(f, "`{path}`: clamped {} to the range", self.attempted),
+            CoercionReason::Reset => write!(f, "`{path}`: reset {} to default", self.attempted),
+            CoercionReason::Rejected => write!(f, "`{path}`: rejected {}", self.attempted),
+            CoercionReason::Dropped => write!(f, "`{path}`: dropped unknown field {}", self.attempted),
+        }
+    }
+}
+
+impl Value {
+    /// Converts the value to match the descriptor.
+    ///
+    /// Numbers are converted into each other and clamped to the descriptor's range,
+    /// scalars are converted to and parsed from strings,
+    /// and collections are converted item by item.
+    /// Returns `None` if there is no sensible conversion.
+    pub fn coerce(&self, desc: &Desc) -> Option<Value> {
+        self.coerce_with(desc, &mut |_| {})
+    }
+
+    /// Converts the value like `Value::coerce`,
+    /// calling `on_event` for every conversion, clamp and rejection on the way.
+    pub fn coerce_with(&self, desc: &Desc, on_event: &mut dyn FnMut(CoercionEvent)) -> Option<Value> {
+        coerce_at(self, desc, "", on_event)
+    }
+}
+
+fn is_negative(value: &Value) -> bool {
+    match *value {
+        Value::Int(value) => value < 0,
+        Value::Float(value) => value < 0.0,
+        _ => false,
+    }
+}
+
+fn coerce_at(value: &Value, desc: &Desc, path: &str, on_event: &mut dyn FnMut(CoercionEvent)) -> Option<Value> {
+    let mut report = |reason| {
+        on_event(CoercionEvent {
+            path: path.to_owned(),
+            attempted: preview(value),
+            reason,
+        })
+    };
+
+    let mut convert = |to: Kind| match value.converted(to) {
+        Ok(converted) => {
+            let from = Kind::of(value);
+            if from != to {
+                report(CoercionReason::Converted { from, to });
+            }
+            Some(converted)
+        }
+        Err(_) => {
+            report(CoercionReason::Rejected);
+            None
+        }
+    };
+
+    match (desc, value) {
+        (Desc::Bool, _) => convert(Kind::Bool),
+        (&Desc::Int { min, max, .. }, _) => match convert(Kind::Int)? {
+            Value::Int(n) if clamp(n, min, max) != n => {
+                report(CoercionReason::Clamped);
+                Some(Value::Int(clamp(n, min, max)))
+            }
+            converted => Some(converted),
+        },
+        (&Desc::Uint { min, max }, _) => match convert(Kind::Uint)? {
+            // Negative numbers saturate to zero on conversion.
+            Value::Uint(n) if is_negative(value) || clamp(n, min, max) != n => {
+                report(CoercionReason::Clamped);
+                Some(Value::Uint(clamp(n, min, max)))
+            }
+            converted => Some(converted),
+        },
+        (&Desc::Float { min, max, .. }, _) => match convert(Kind::Float)? {
+            Value::Float(n) if clamp(n, min, max) != n => {
+                report(CoercionReason::Clamped);
+                Some(Value::Float(clamp(n, min, max)))
+            }
+            converted => Some(converted),
+        },
+        (&Desc::String { ref variants, trim, collapse_whitespace, min_len, max_len, ref pattern, .. }, _) => {
+            let mut text = match convert(Kind::String)? {
+                Value::String(text) => normalize_whitespace(&text, trim, collapse_whitespace),
+                _ => return None,
+            };
+            match variants {
+                Some(variants) if !variants.iter().any(|v| v.name == text) => {
+                    report(CoercionReason::Rejected);
+                    None
+                }
+                Some(_) => Some(Value::String(text)),
+                None if min_len.is_some_and(|min| text.chars().count() < min) => {
+                    report(CoercionReason::Rejected);
+                    None
+                }
+                None if pattern.as_ref().is_some_and(|pattern| pattern::matches(pattern, &text) != Ok(true)) => {
+                    report(CoercionReason::Rejected);
+                    None
+                }
+                None => {
+                    if let Some(max_len) = max_len.filter(|&max| text.chars().count() > max) {
+                        report(CoercionReason::Clamped);
+                        truncate_chars(&mut text, max_len);
+                    }
+                    Some(Value::String(text))
+                }
+            }
+        }
+        (&Desc::Bytes { max_len }, _) => match convert(Kind::Bytes)? {
+            Value::Bytes(mut bytes) => {
+                if let Some(max_len) = max_len.filter(|&max| bytes.len() > max) {
+                    report(CoercionReason::Clamped);
+                    bytes.truncate(max_len);
+                }
+                Some(Value::Bytes(bytes))
+            }
+            _ => None,
+        },
+        (&Desc::Color { alpha }, _) => match convert(Kind::Color)? {
+            Value::Color([r, g, b, a]) if !alpha && a != 255 => {
+                report(CoercionReason::Clamped);
+                Some(Value::Color([r, g, b, 255]))
+            }
+            converted => Some(converted),
+        },
+        (&Desc::Matrix { rows, cols, min, max, .. }, Value::List(elems)) => {
+            if elems.len() != rows * cols {
+                report(CoercionReason::Rejected);
+                return None;
+            }
+            let cell = Desc::Float {
+                min,
+                max,
+                min_path: None,
+                max_path: None,
+                stepper: None,
+                step: None,
+            };
+            elems
+                .iter()
+                .enumerate()
+                .map(|(idx, elem)| match elem {
+                    Value::Int(_) | Value::Float(_) => {
+                        coerce_at(elem, &cell, &path::join(path, &format!("[{idx}]")), on_event)
+                    }
+                    _ => None,
+                })
+                .collect::<Option<_>>()
+                .map(Value::List)
+        }
+        (Desc::List { elem_desc, .. }, Value::List(elems)) => match elem_desc {
+            None => Some(value.clone()),
+            Some(elem_desc) => elems
+                .iter()
+                .enumerate()
+                .map(|(idx, elem)| coerce_at(elem, elem_desc, &path::join(path, &format!("[{idx}]")), on_event))
+                .collect::<Option<_>>()
+                .map(Value::List),
+        },
+        (Desc::Map { value_desc, .. }, Value::Map(values)) => match value_desc {
+            None => Some(value.clone()),
+            Some(value_desc) => values
+                .iter()
+                .map(|(key, value)| Some((key.clone(), coerce_at(value, value_desc, &path::join(path, key), on_event)?)))
+                .collect::<Option<_>>()
+                .map(Value::Map),
+        },
+        (Desc::Enum { variants }, Value::Enum { variant, value }) => {
+            let Some(v) = variants.iter().find(|v| v.name == *variant) else {
+                report(CoercionReason::Rejected);
+                return None;
+            };
+            let path = path::join(path, variant);
+            let value = match (&v.desc, &**value) {
+                (Some(desc), value) => coerce_at(value, desc, &path, on_event)?,
+                (None, Value::Null) => Value::Null,
+                (None, value) => {
+                    on_event(CoercionEvent {
+                        path,
+                        attempted: preview(value),
+                        reason: CoercionReason::Reset,
+                    });
+                    Value::Null
+                }
+            };
+            Some(Value::Enum {
+                variant: variant.clone(),
+                value: Box::new(value),
+            })
+        }
+        (Desc::Struct { fields }, Value::Map(values)) => {
+            let coerced = fields
+                .iter()
+                .map(|field| {
+                    let path = path::join(path, &field.name);
+                    let value = match values.get(&field.name) {
+                        Some(value) => coerce_at(value, &field.desc, &path, on_event)?,
+                        None => field.desc.default_value(),
+                    };
+                    Some((field.name.clone(), value))
+                })
+                .collect::<Option<_>>()?;
+            for (key, value) in values {
+                if !fields.iter().any(|field| field.name == *key) {
+                    on_event(CoercionEvent {
+                        path: path::join(path, key),
+                        attempted: preview(value),
+                        reason: CoercionReason::Dropped,
+                    });
+                }
+            }
+            Some(Value::Map(coerced))
+        }
+        (Desc::Tuple { elems: descs }, Value::List(elems)) => {
+            if elems.len() != descs.len() {
+                report(CoercionReason::Rejected);
+                return None;
+            }
+            descs
+                .iter()
+                .zip(elems)
+                .enumerate()
+                .map(|(idx, (desc, elem))| coerce_at(elem, desc, &path::join(path, &format!("[{idx}]")), on_event))
+                .collect::<Option<_>>()
+                .map(Value::List)
+        }
+        (Desc::Optional { .. }, Value::Null) => Some(Value::Null),
+        (Desc::Optional { inner } | Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner }, value) => coerce_at(value, inner, path, on_event),
+        _ => {
+            report(CoercionReason::Rejected);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StructField;
+
+    fn events(value: &Value, desc: &Desc) -> (Option<Value>, Vec<CoercionEvent>) {
+        let mut events = Vec::new();
+        let coerced = value.coerce_with(desc, &mut |event| events.push(event));
+        (coerced, events)
+    }
+
+    #[test]
+    fn int_to_string_reports_one_conversion() {
+        let desc = Desc::from_value(&Value::from("x"));
+        let (coerced, events) = events(&Value::Int(42), &desc);
+
+        assert_eq!(coerced, Some(Value::from("42")));
+        assert_eq!(
+            events,
+            [CoercionEvent {
+                path: String::new(),
+                attempted: "42".to_owned(),
+                reason: CoercionReason::Converted {
+                    from: Kind::Int,
+                    to: Kind::String,
+                },
+            }]
+        );
+    }
+
+    #[test]
+    fn matching_value_reports_nothing() {
+        let (coerced, events) = events(&Value::Bool(true), &Desc::Bool);
+
+        assert_eq!(coerced, Some(Value::Bool(true)));
+        assert!(events.is_empty());
+    }
+
+    #[test]
+    fn struct_reports_dropped_fields() {
+        let desc = Desc::Struct {
+            fields: vec![StructField::new("name", Desc::from_value(&Value::from("x")))],
+        };
+        let value: Value = [("name".to_owned(), Value::from("a")), ("old".to_owned(), Value::Int(1))]
+            .into_iter()
+            .collect();
+
+        let (coerced, events) = events(&value, &desc);
+
+        let expected: Value = [("name".to_owned(), Value::from("a"))].into_iter().collect();
+        assert_eq!(coerced, Some(expected));
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].path, "old");
+        assert_eq!(events[0].reason, CoercionReason::Dropped);
+        assert_eq!(events[0].to_string(), "`old`: dropped unknown field 1");
+    }
+
+    #[test]
+    fn nested_events_carry_paths() {
+        let desc = Desc::Struct {
+            fields: vec![StructField::new("port", Desc::Uint { min: None, max: Some(100) })],
+        };
+        let value: Value = [("port".to_owned(), Value::Int(8080))].into_iter().collect();
+
+        let (coerced, events) = events(&value, &desc);
+
+        let expected: Value = [("port".to_owned(), Value::Uint(100))].into_iter().collect();
+        assert_eq!(coerced, Some(expected));
+        let reasons: Vec<_> = events.iter().map(|event| (event.path.as_str(), &event.reason)).collect();
+        assert_eq!(
+            reasons,
+            [
+                (
+                    "port",
+                    &CoercionReason::Converted {
+                        from: Kind::Int,
+                        to: Kind::Uint
+                    }
+                ),
+                ("port", &CoercionReason::Clamped),
+            ]
+        );
+    }
+
+    #[test]
+    fn rejected_value_reports_rejection() {
+        let (coerced, events) = events(&Value::from("abc"), &Desc::Bool);
+
+        assert_eq!(coerced, None);
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].reason, CoercionReason::Rejected);
+    }
+
+
+    #[test]
+    fn hex_string_coerces_to_opaque_color() {
+        let (coerced, events) = events(&Value::from("#ff800080"), &Desc::Color { alpha: false });
+
+        assert_eq!(coerced, Some(Value::Color([0xff, 0x80, 0, 255])));
+        let reasons: Vec<_> = events.into_iter().map(|event| event.reason).collect();
+        assert_eq!(
+            reasons,
+            [
+                CoercionReason::Converted {
+                    from: Kind::String,
+                    to: Kind::Color
+                },
+                CoercionReason::Clamped,
+            ]
+        );
+    }
+
+
+    #[test]
+    fn negative_int_coerces_to_zero_uint() {
+        let (coerced, events) = events(&Value::Int(-5), &Desc::Uint { min: None, max: None });
+
+        assert_eq!(coerced, Some(Value::Uint(0)));
+        let reasons: Vec<_> = events.into_iter().map(|event| event.reason).collect();
+        assert_eq!(
+            reasons,
+            [
+                CoercionReason::Converted {
+                    from: Kind::Int,
+                    to: Kind::Uint
+                },
+                CoercionReason::Clamped,
+            ]
+        );
+
+        let coerced = Value::Float(-0.5).coerce(&Desc::Uint { min: Some(3), max: None });
+        assert_eq!(coerced, Some(Value::Uint(3)));
+    }
+}
@@ -0,0 +1,54 @@
+/// Formats the color as `#rrggbb`, or `#rrggbbaa` if it is not opaque.
+pub(crate) fn format(rgba: [u8; 4]) -> String {
+    let [r, g, b, a] = rgba;
+    match a {
+        255 => format!("#{r:02x}{g:02x}{b:02x}"),
+        _ => format!("#{r:02x}{g:02x}{b:02x}{a:02x}"),
+    }
+}
+
+/// Parses a color written as `#rrggbb` or `#rrggbbaa` in either case.
+/// Colors without alpha are opaque.
+pub(crate) fn parse(text: &str) -> Option<[u8; 4]> {
+    let digits = text.trim().strip_prefix('#')?;
+    if !digits.is_ascii() || !matches!(digits.len(), 6 | 8) {
+        return None;
+    }
+
+    let mut rgba = [255; 4];
+    for (idx, channel) in rgba.iter_mut().enumerate().take(digits.len() / 2) {
+        *channel = u8::from_str_radix(&digits[idx * 2..idx * 2 + 2], 16).ok()?;
+    }
+    Some(rgba)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn format_omits_opaque_alpha() {
+        assert_eq!(format([0x12, 0xab, 0xff, 255]), "#12abff");
+        assert_eq!(format([0x12, 0xab, 0xff, 0x80]), "#12abff80");
+    }
+
+    #[test]
+    fn parse_accepts_rgb_and_rgba() {
+        assert_eq!(parse("#12ABff"), Some([0x12, 0xab, 0xff, 255]));
+        assert_eq!(parse(" #12abff80 "), Some([0x12, 0xab, 0xff, 0x80]));
+    }
+
+    #[test]
+    fn parse_rejects_malformed() {
+        for text in ["12abff", "#12abf", "#12abff8", "#12abfg", "#ééé", ""] {
+            assert_eq!(parse(text), None, "{text:?}");
+        }
+    }
+
+    #[test]
+    fn round_trips() {
+        for rgba in [[0, 0, 0, 255], [1, 2, 3, 4], [255; 4]] {
+            assert_eq!(parse(&format(rgba)), Some(rgba));
+        }
+    }
+}
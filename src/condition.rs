@@ -0,0 +1,211 @@
+use egui::{Context, Id, Ui};
+use egui_probe::EguiProbe;
+use hashbrown::HashMap;
+
+use crate::{color, hex, path, Desc, Value};
+
+/// Condition on a scalar field of the document, e.g. `auth == true`.
+#[derive(Clone, Debug, Default, PartialEq, EguiProbe)]
+pub struct Condition {
+    /// Path of the field from the document root, see `ValueProbe::with_visibility`.
+    pub path: String,
+
+    /// Expected value of the field as text, e.g. `true`, `42` or `basic`.
+    pub equals: String,
+}
+
+impl Condition {
+    pub fn new(path: impl Into<String>, equals: impl Into<String>) -> Self {
+        Condition {
+            path: path.into(),
+            equals: equals.into(),
+        }
+    }
+
+    /// Returns `true` if the field at `path` in `root` is a scalar equal to `equals`.
+    ///
+    /// Missing fields, lists and maps never satisfy the condition.
+    pub fn holds(&self, root: &Value) -> bool {
+        path::lookup(root, &self.path).and_then(scalar_text).is_some_and(|text| text == self.equals)
+    }
+}
+
+/// Map key that is required only while a condition holds.
+#[derive(Clone, Debug, Default, PartialEq, EguiProbe)]
+pub struct RequiredIf {
+    /// The conditionally required key.
+    pub key: String,
+
+    /// The condition under which the key is required.
+    pub when: Condition,
+}
+
+impl RequiredIf {
+    pub fn new(key: impl Into<String>, when: Condition) -> Self {
+        RequiredIf { key: key.into(), when }
+    }
+}
+
+fn scalar_text(value: &Value) -> Option<String> {
+    match value {
+        Value::Bool(value) => Some(value.to_string()),
+        Value::Int(value) => Some(value.to_string()),
+        Value::Uint(value) => Some(value.to_string()),
+        Value::Float(value) => Some(value.to_string()),
+        Value::String(value) => Some(value.clone()),
+        Value::Bytes(bytes) => Some(hex::encode(bytes)),
+        Value::Color(rgba) => Some(color::format(*rgba)),
+        Value::List(_) | Value::Map(_) | Value::Enum { .. } | Value::Null => None,
+    }
+}
+
+/// Outcomes of conditions referenced by path, evaluated once per frame by the root probe.
+#[derive(Clone, Default)]
+struct ResolvedConditions(HashMap<String, Option<String>>);
+
+/// Evaluates all condition paths referenced in `desc` against the document.
+pub(crate) fn resolve_all(ui: &Ui, root: Id, desc: &Desc, value: &Value) {
+    let mut paths = Vec::new();
+    collect_paths(desc, &mut paths);
+
+    let resolved = paths
+        .into_iter()
+        .map(|cond_path| {
+            let text = path::lookup(value, cond_path).and_then(scalar_text);
+            (cond_path.to_owned(), text)
+        })
+        .collect();
+
+    ui.ctx()
+        .data_mut(|d| d.insert_temp(root.with("Conditions"), ResolvedConditions(resolved)));
+}
+
+fn collect_paths<'a>(desc: &'a Desc, paths: &mut Vec<&'a str>) {
+    match desc {
+        Desc::Map { value_desc, required_if, .. } => {
+            paths.extend(required_if.iter().map(|req| req.when.path.as_str()));
+            if let Some(inner) = value_desc {
+                collect_paths(inner, paths);
+            }
+        }
+        Desc::List { elem_desc: Some(inner), .. } => collect_paths(inner, paths),
+        Desc::Enum { variants } => {
+            for desc in variants.iter().filter_map(|variant| variant.desc.as_ref()) {
+                collect_paths(desc, paths);
+            }
+        }
+        Desc::Struct { fields } => {
+            for field in fields {
+                collect_paths(&field.desc, paths);
+            }
+        }
+        Desc::Tuple { elems } => {
+            for elem in elems {
+                collect_paths(elem, paths);
+            }
+        }
+        Desc::Optional { inner } | Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner } => collect_paths(inner, paths),
+        _ => {}
+    }
+}
+
+/// Returns `true` if the condition held for the document at the start of the frame.
+pub(crate) fn holds(ctx: &Context, root: Id, cond: &Condition) -> bool {
+    ctx.data(|d| d.get_temp::<ResolvedConditions>(root.with("Conditions")))
+        .and_then(|resolved| resolved.0.get(&cond.path).cloned().flatten())
+        .is_some_and(|text| text == cond.equals)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{tests::shown_texts, ProbeError, StructField, ValueProbe};
+
+    /// `{"auth": <auth>, "creds": {}}` where `creds.user` is required while `auth == true`.
+    fn document(auth: bool) -> (Desc, Value) {
+        let creds = Desc::Map {
+            value_desc: Some(Box::new(Desc::from_value(&Value::from("x")))),
+            tabs: Vec::new(),
+            empty_hint: None,
+            collapsed_by_default: false,
+            max_entries: None,
+            required_keys: Vec::new(),
+            required_if: vec![RequiredIf::new("user", Condition::new("auth", "true"))],
+            strict_keys: false,
+        };
+        let desc = Desc::Struct {
+            fields: vec![StructField::new("auth", Desc::Bool), StructField::new("creds", creds)],
+        };
+        let value = [
+            ("auth".to_owned(), Value::Bool(auth)),
+            ("creds".to_owned(), Value::Map(Default::default())),
+        ]
+        .into_iter()
+        .collect();
+        (desc, value)
+    }
+
+    #[test]
+    fn holds_compares_scalar_text() {
+        let root: Value = [
+            ("auth".to_owned(), Value::Bool(true)),
+            ("port".to_owned(), Value::Int(80)),
+            ("mode".to_owned(), Value::from("basic")),
+            ("key".to_owned(), Value::Bytes(vec![0xab])),
+            ("tags".to_owned(), Value::from(vec![Value::from("basic")])),
+        ]
+        .into_iter()
+        .collect();
+
+        assert!(Condition::new("auth", "true").holds(&root));
+        assert!(Condition::new("port", "80").holds(&root));
+        assert!(Condition::new("mode", "basic").holds(&root));
+        assert!(Condition::new("key", "ab").holds(&root));
+        assert!(!Condition::new("mode", "oauth").holds(&root));
+        assert!(!Condition::new("missing", "").holds(&root));
+        assert!(!Condition::new("tags", "basic").holds(&root));
+        assert!(Condition::new("tags[0]", "basic").holds(&root));
+    }
+
+    #[test]
+    fn paths_are_collected_from_nested_descriptors() {
+        let (desc, _) = document(true);
+        let desc = Desc::List {
+            elem_desc: Some(Box::new(Desc::Optional { inner: Box::new(desc) })),
+            empty_hint: None,
+            collapsed_by_default: false,
+            min_len: None,
+            max_len: None,
+            multi_select: false,
+            unique_by: None,
+        };
+
+        let mut paths = Vec::new();
+        collect_paths(&desc, &mut paths);
+        assert_eq!(paths, ["auth"]);
+    }
+
+    #[test]
+    fn key_is_required_only_while_condition_holds() {
+        let (desc, value) = document(false);
+        assert_eq!(desc.validate(&value), Ok(()));
+
+        let (desc, value) = document(true);
+        let errors = desc.validate(&value).unwrap_err();
+        assert!(
+            matches!(&errors[..], [ProbeError::MissingKeys { path, keys }] if path == "creds" && keys == &["user"]),
+            "{errors:?}"
+        );
+    }
+
+    #[test]
+    fn missing_key_is_offered_while_condition_holds() {
+        for auth in [false, true] {
+            let (desc, mut value) = document(auth);
+            let texts = shown_texts(|ui| {
+                ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+            });
+            assert_eq!(texts.iter().any(|text| text == "Add missing"), auth, "{texts:?}");
+        }
+    }
+}
@@ -0,0 +1,142 @@
+use egui::{Key, KeyboardShortcut, Modifiers};
+
+use crate::NumberFormat;
+
+/// Configuration shared by all nodes of a `ValueProbe`.
+#[derive(Clone, Debug)]
+pub struct ProbeConfig {
+    /// Keyboard shortcuts for editing collections.
+    pub shortcuts: Shortcuts,
+
+    /// Show the description of the selected string or enum variant under the combo box.
+    pub variant_help: bool,
+
+    /// Format used to display and parse numeric fields.
+    pub number_format: NumberFormat,
+
+    /// What happens to typed or pasted numbers outside of the field's range.
+    pub range_policy: RangePolicy,
+
+    /// Ask for confirmation before resetting a value of unexpected kind
+    /// if that would discard its data.
+    pub confirm_kind_switch: bool,
+
+    /// Maximum nesting depth of values whose descriptor is edited in schemaless mode.
+    pub max_depth: usize,
+
+    /// Limit on the document size after which adding items is disabled.
+    pub size_limit: Option<SizeLimit>,
+
+    /// Show a summary of the descriptor when hovering a value.
+    pub schema_tooltips: bool,
+
+    /// When constraint errors of a field are reported.
+    pub validation_timing: ValidationTiming,
+
+    /// Hide controls that add or remove items of lists and maps,
+    /// leaving the values themselves editable.
+    pub structure_locked: bool,
+
+    /// How `ValueProbe::show` lays out nested values.
+    pub layout: LayoutMode,
+
+    /// Show a button next to lists, maps and structs that resets
+    /// the whole section to the defaults of its descriptor after confirmation.
+    pub section_reset: bool,
+}
+
+impl ProbeConfig {
+    pub const DEFAULT: Self = ProbeConfig {
+        shortcuts: Shortcuts::DEFAULT,
+        variant_help: false,
+        number_format: NumberFormat::PLAIN,
+        range_policy: RangePolicy::Clamp,
+        confirm_kind_switch: true,
+        max_depth: 16,
+        size_limit: None,
+        schema_tooltips: false,
+        validation_timing: ValidationTiming::Live,
+        structure_locked: false,
+        layout: LayoutMode::Tree,
+        section_reset: false,
+    };
+}
+
+impl Default for ProbeConfig {
+    #[inline]
+    fn default() -> Self {
+        ProbeConfig::DEFAULT
+    }
+}
+
+/// Handling of numbers entered as text that are outside of the field's range.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum RangePolicy {
+    /// The number is clamped to the range and the entered number is shown next to the field.
+    #[default]
+    Clamp,
+
+    /// The number is not accepted and the entered text is kept until fixed.
+    Reject,
+}
+
+/// When constraint errors of a field are reported.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ValidationTiming {
+    /// Errors are reported while the field is edited.
+    #[default]
+    Live,
+
+    /// Errors of the focused field are held back until it loses focus.
+    OnBlur,
+}
+
+/// Layout of nested values shown with `ValueProbe::show`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum LayoutMode {
+    /// Collapsible tree of `egui_probe::Probe`.
+    #[default]
+    Tree,
+
+    /// Single grid of label and field columns for the whole document,
+    /// with labels of nested values indented by their depth.
+    Columns,
+}
+
+/// Maximum size of a document edited with `ValueProbe`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SizeLimit {
+    /// Maximum number of values, as returned by `Value::node_count`.
+    Nodes(usize),
+
+    /// Maximum approximate size in bytes, as returned by `Value::approx_size`.
+    Bytes(usize),
+}
+
+/// Keyboard shortcuts that act on the focused list or map.
+///
+/// Set a shortcut to `None` to disable it.
+#[derive(Clone, Copy, Debug)]
+pub struct Shortcuts {
+    /// Adds a new element to the collection when it or one of its items has focus.
+    pub add: Option<KeyboardShortcut>,
+
+    /// Removes the focused item from its collection.
+    pub delete: Option<KeyboardShortcut>,
+}
+
+impl Shortcuts {
+    /// Default shortcuts use the command modifier
+    /// so they don't clash with text editing in the focused field.
+    pub const DEFAULT: Self = Shortcuts {
+        add: Some(KeyboardShortcut::new(Modifiers::COMMAND, Key::Enter)),
+        delete: Some(KeyboardShortcut::new(Modifiers::COMMAND, Key::Delete)),
+    };
+}
+
+impl Default for Shortcuts {
+    #[inline]
+    fn default() -> Self {
+        Shortcuts::DEFAULT
+    }
+}
@@ -0,0 +1,365 @@
+use hashbrown::HashMap;
+use indexmap::IndexMap;
+
+use crate::{Desc, Value, Variant};
+
+/// Rust types that can be edited as `Value` with the `Desc` they produce.
+///
+/// Can be derived with the `derive` feature
+/// for structs with named fields and enums with unit variants.
+pub trait DescribeValue: Sized {
+    /// Returns the descriptor of values of this type.
+    fn describe() -> Desc;
+
+    /// Converts to a value matching the descriptor.
+    fn to_value(&self) -> Value;
+
+    /// Converts back from a value.
+    /// Returns `None` if the value doesn't match the descriptor.
+    fn from_value(value: &Value) -> Option<Self>;
+}
+
+impl DescribeValue for bool {
+    fn describe() -> Desc {
+        Desc::Bool
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Bool(*self)
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match *value {
+            Value::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! describe_int {
+    ($($ty:ty)*) => {$(
+        impl DescribeValue for $ty {
+            fn describe() -> Desc {
+                Desc::Int {
+                    min: Some(<$ty>::MIN as i64),
+                    max: Some(<$ty>::MAX as i64),
+                    min_path: None,
+                    max_path: None,
+                    humanize: None,
+                    stepper: None,
+                    step: None,
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                Value::Int(*self as i64)
+            }
+
+            fn from_value(value: &Value) -> Option<Self> {
+                match *value {
+                    Value::Int(value) => value.try_into().ok(),
+                    _ => None,
+                }
+            }
+        }
+    )*};
+}
+
+describe_int!(i8 i16 i32 u8 u16 u32);
+
+impl DescribeValue for i64 {
+    fn describe() -> Desc {
+        Desc::Int {
+            min: None,
+            max: None,
+            min_path: None,
+            max_path: None,
+            humanize: None,
+            stepper: None,
+            step: None,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Int(*self)
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match *value {
+            Value::Int(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl DescribeValue for u64 {
+    fn describe() -> Desc {
+        Desc::Uint { min: None, max: None }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Uint(*self)
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match *value {
+            Value::Uint(value) => Some(value),
+            Value::Int(value) => value.try_into().ok(),
+            _ => None,
+        }
+    }
+}
+
+macro_rules! describe_float {
+    ($($ty:ty)*) => {$(
+        impl DescribeValue for $ty {
+            fn describe() -> Desc {
+                Desc::Float {
+                    min: None,
+                    max: None,
+                    min_path: None,
+                    max_path: None,
+                    stepper: None,
+                    step: None,
+                }
+            }
+
+            fn to_value(&self) -> Value {
+                Value::Float(*self as f64)
+            }
+
+            fn from_value(value: &Value) -> Option<Self> {
+                match *value {
+                    Value::Float(value) => Some(value as $ty),
+                    Value::Int(value) => Some(value as $ty),
+                    Value::Uint(value) => Some(value as $ty),
+                    _ => None,
+                }
+            }
+        }
+    )*};
+}
+
+describe_float!(f32 f64);
+
+impl DescribeValue for String {
+    fn describe() -> Desc {
+        Desc::String {
+            variants: None,
+            trim: false,
+            collapse_whitespace: false,
+            min_len: None,
+            max_len: None,
+            pattern: None,
+            multiline: false,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.clone())
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::String(value) => Some(value.clone()),
+            _ => None,
+        }
+    }
+}
+
+impl<T: DescribeValue> DescribeValue for Vec<T> {
+    fn describe() -> Desc {
+        Desc::List {
+            elem_desc: Some(Box::new(T::describe())),
+            empty_hint: None,
+            collapsed_by_default: false,
+            min_len: None,
+            max_len: None,
+            multi_select: false,
+            unique_by: None,
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        Value::List(self.iter().map(T::to_value).collect())
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::List(elems) => elems.iter().map(T::from_value).collect(),
+            _ => None,
+        }
+    }
+}
+
+impl<T: DescribeValue> DescribeValue for Option<T> {
+    fn describe() -> Desc {
+        Desc::Optional {
+            inner: Box::new(T::describe()),
+        }
+    }
+
+    fn to_value(&self) -> Value {
+        self.as_ref().map_or(Value::Null, T::to_value)
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Null => Some(None),
+            value => T::from_value(value).map(Some),
+        }
+    }
+}
+
+impl<T: DescribeValue> DescribeValue for HashMap<String, T> {
+    fn describe() -> Desc {
+        map_desc(Some(T::describe()))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Map(self.iter().map(|(key, value)| (key.clone(), value.to_value())).collect())
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Map(values) => values
+                .iter()
+                .map(|(key, value)| Some((key.clone(), T::from_value(value)?)))
+                .collect(),
+            _ => None,
+        }
+    }
+}
+
+impl<T: DescribeValue> DescribeValue for IndexMap<String, T> {
+    fn describe() -> Desc {
+        map_desc(Some(T::describe()))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Map(self.iter().map(|(key, value)| (key.clone(), value.to_value())).collect())
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Map(values) => values
+                .iter()
+                .map(|(key, value)| Some((key.clone(), T::from_value(value)?)))
+                .collect(),
+            _ => None,
+        }
+    }
+}
+
+impl<T: DescribeValue> DescribeValue for std::collections::HashMap<String, T> {
+    fn describe() -> Desc {
+        map_desc(Some(T::describe()))
+    }
+
+    fn to_value(&self) -> Value {
+        Value::Map(self.iter().map(|(key, value)| (key.clone(), value.to_value())).collect())
+    }
+
+    fn from_value(value: &Value) -> Option<Self> {
+        match value {
+            Value::Map(values) => values
+                .iter()
+                .map(|(key, value)| Some((key.clone(), T::from_value(value)?)))
+                .collect(),
+            _ => None,
+        }
+    }
+}
+
+fn map_desc(value_desc: Option<Desc>) -> Desc {
+    Desc::Map {
+        value_desc: value_desc.map(Box::new),
+        tabs: Vec::new(),
+        empty_hint: None,
+        collapsed_by_default: false,
+        max_entries: None,
+        required_keys: Vec::new(),
+        required_if: Vec::new(),
+        strict_keys: false,
+    }
+}
+
+/// Helpers used by the code generated with `#[derive(DescribeValue)]`.
+#[doc(hidden)]
+pub mod __private {
+    use super::*;
+
+    /// Describes a struct as a map.
+    ///
+    /// Map values share one descriptor, so fields of different types
+    /// can't be described individually and the map is left without value descriptor.
+    pub fn struct_desc(fields: Vec<(&'static str, Desc)>) -> Desc {
+        let _ = fields;
+        map_desc(None)
+    }
+
+    pub fn struct_value(fields: Vec<(&'static str, Value)>) -> Value {
+        Value::Map(
+            fields
+                .into_iter()
+                .map(|(name, value)| (name.to_owned(), value))
+                .collect(),
+        )
+    }
+
+    pub fn struct_fields(value: &Value) -> Option<&IndexMap<String, Value>> {
+        match value {
+            Value::Map(fields) => Some(fields),
+            _ => None,
+        }
+    }
+
+    pub fn field<T: DescribeValue>(fields: &IndexMap<String, Value>, name: &str) -> Option<T> {
+        T::from_value(fields.get(name)?)
+    }
+
+    pub fn enum_desc(variants: &[&str]) -> Desc {
+        Desc::String {
+            variants: Some(variants.iter().map(|&name| Variant::new(name)).collect()),
+            trim: false,
+            collapse_whitespace: false,
+            min_len: None,
+            max_len: None,
+            pattern: None,
+            multiline: false,
+        }
+    }
+
+    pub fn enum_variant(value: &Value) -> Option<&str> {
+        match value {
+            Value::String(name) => Some(name),
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ints_are_bounded_by_their_type() {
+        assert_eq!(u8::describe().validate(&Value::Int(255)), Ok(()));
+        assert!(u8::describe().validate(&Value::Int(256)).is_err());
+        assert_eq!(u8::from_value(&Value::Int(256)), None);
+        assert_eq!(i8::from_value(&Value::Int(-128)), Some(-128));
+        assert_eq!(u64::from_value(&Value::Int(-1)), None);
+    }
+
+    #[test]
+    fn containers_round_trip() {
+        let list = vec![Some(1.5), None];
+        assert_eq!(Vec::<Option<f64>>::from_value(&list.to_value()), Some(list.clone()));
+        assert_eq!(Vec::<Option<f64>>::describe().validate(&list.to_value()), Ok(()));
+
+        let map: IndexMap<String, bool> = [("a".to_owned(), true)].into_iter().collect();
+        assert_eq!(IndexMap::<String, bool>::from_value(&map.to_value()), Some(map));
+
+        assert_eq!(Vec::<String>::from_value(&Value::List(vec![Value::Bool(true)])), None);
+    }
+}
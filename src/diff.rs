@@ -0,0 +1,281 @@
+use egui::{Color32, Response, RichText, Ui};
+
+use crate::{color, hex, path, Value, ValueProbe};
+
+/// Single difference between two values.
+#[derive(Clone, Copy)]
+pub enum Change<'a> {
+    /// Entry exists only in the current value.
+    Added { path: &'a str, value: &'a Value },
+
+    /// Entry exists only in the baseline value.
+    Removed { path: &'a str, value: &'a Value },
+
+    /// Entry exists in both values but differs.
+    Modified {
+        path: &'a str,
+        old: &'a Value,
+        new: &'a Value,
+    },
+}
+
+impl Change<'_> {
+    pub fn path(&self) -> &str {
+        match *self {
+            Change::Added { path, .. } | Change::Removed { path, .. } | Change::Modified { path, .. } => path,
+        }
+    }
+}
+
+impl Value {
+    /// Calls `f` for every difference between `baseline` and this value.
+    ///
+    /// Collections of the same kind are compared entry by entry,
+    /// list items by index and map entries by key.
+    /// Any other difference is reported as a modification of the whole value.
+    /// Map entries are reported in order of their keys.
+    pub fn diff(&self, baseline: &Value, f: &mut dyn FnMut(Change<'_>)) {
+        diff_at("", baseline, self, f);
+    }
+}
+
+fn diff_at(path: &str, old: &Value, new: &Value, f: &mut dyn FnMut(Change<'_>)) {
+    match (old, new) {
+        (Value::List(old), Value::List(new)) => {
+            for idx in 0..old.len().max(new.len()) {
+                let path = path::join(path, &format!("[{idx}]"));
+                match (old.get(idx), new.get(idx)) {
+                    (Some(old), Some(new)) => diff_at(&path, old, new, f),
+                    (Some(value), None) => f(Change::Removed { path: &path, value }),
+                    (None, Some(value)) => f(Change::Added { path: &path, value }),
+                    (None, None) => unreachable!(),
+                }
+            }
+        }
+        (Value::Map(old), Value::Map(new)) => {
+            let mut keys: Vec<&String> = old.keys().chain(new.keys().filter(|key| !old.contains_key(*key))).collect();
+            keys.sort();
+
+            for key in keys {
+                let path = path::join(path, key);
+                match (old.get(key), new.get(key)) {
+                    (Some(old), Some(new)) => diff_at(&path, old, new, f),
+                    (Some(value), None) => f(Change::Removed { path: &path, value }),
+                    (None, Some(value)) => f(Change::Added { path: &path, value }),
+                    (None, None) => unreachable!(),
+                }
+            }
+        }
+        (Value::Enum { variant, value: old }, Value::Enum { variant: new_variant, value: new }) if variant == new_variant => {
+            diff_at(&path::join(path, variant), old, new, f)
+        }
+        _ if old == new => {}
+        _ => f(Change::Modified { path, old, new }),
+    }
+}
+
+impl ValueProbe<'_> {
+    /// Shows changes of the value relative to `baseline`
+    /// as rows of `path | baseline | current`.
+    ///
+    /// Added, removed and modified entries are colored differently.
+    /// Unchanged top-level entries are listed under a collapsed header.
+    pub fn show_diff(&mut self, ui: &mut Ui, baseline: &Value) -> Response {
+        let added = Color32::from_rgb(0x50, 0xb0, 0x50);
+        let removed = ui.visuals().error_fg_color;
+        let modified = ui.visuals().warn_fg_color;
+
+        let mut changed_roots = Vec::new();
+
+        ui.vertical(|ui| {
+            egui::Grid::new(self.id_source.with("Diff"))
+                .num_columns(3)
+                .striped(true)
+                .show(ui, |ui| {
+                    ui.strong("path");
+                    ui.strong("baseline");
+                    ui.strong("current");
+                    ui.end_row();
+
+                    self.value.diff(baseline, &mut |change| {
+                        changed_roots.push(root_label(change.path()).to_owned());
+
+                        match change {
+                            Change::Added { path, value } => {
+                                ui.label(RichText::new(path).color(added));
+                                ui.weak("—");
+                                ui.label(RichText::new(preview(value)).color(added));
+                            }
+                            Change::Removed { path, value } => {
+                                ui.label(RichText::new(path).color(removed));
+                                ui.label(RichText::new(preview(value)).color(removed).strikethrough());
+                                ui.weak("—");
+                            }
+                            Change::Modified { path, old, new } => {
+                                ui.label(RichText::new(path).color(modified));
+                                ui.label(preview(old));
+                                ui.label(RichText::new(preview(new)).color(modified));
+                            }
+                        }
+                        ui.end_row();
+                    });
+                });
+
+            let unchanged: Vec<(String, &Value)> = match &*self.value {
+                Value::List(elems) => elems
+                    .iter()
+                    .enumerate()
+                    .map(|(idx, elem)| (format!("[{idx}]"), elem))
+                    .collect(),
+                Value::Map(values) => values.iter().map(|(key, value)| (key.clone(), value)).collect(),
+                _ => Vec::new(),
+            };
+
+            let mut unchanged: Vec<_> = unchanged
+                .into_iter()
+                .filter(|(label, _)| !changed_roots.contains(label))
+                .collect();
+            unchanged.sort_by(|a, b| a.0.cmp(&b.0));
+
+            if !unchanged.is_empty() {
+                egui::CollapsingHeader::new(format!("Unchanged ({})", unchanged.len()))
+                    .id_source(self.id_source.with("Unchanged"))
+                    .default_open(false)
+                    .show(ui, |ui| {
+                        for (label, value) in unchanged {
+                            ui.horizontal(|ui| {
+                                ui.label(label);
+                                ui.weak(preview(value));
+                            });
+                        }
+                    });
+            }
+        })
+        .response
+    }
+}
+
+/// Returns the first segment of a path.
+fn root_label(path: &str) -> &str {
+    let end = path
+        .char_indices()
+        .skip(1)
+        .find(|&(_, c)| c == '.' || c == '[')
+        .map_or(path.len(), |(idx, _)| idx);
+    &path[..end]
+}
+
+/// Short single-line representation of a value.
+pub(crate) fn preview(value: &Value) -> String {
+    match value {
+        Value::Bool(value) => value.to_string(),
+        Value::Int(value) => value.to_string(),
+        Value::Uint(value) => value.to_string(),
+        Value::Float(value) => value.to_string(),
+        Value::String(value) => format!("{value:?}"),
+        Value::Bytes(bytes) => format!("0x{}", hex::encode(bytes)),
+        Value::Color(rgba) => color::format(*rgba),
+        Value::List(elems) => format!("list of {}", elems.len()),
+        Value::Map(values) => format!("map of {}", values.len()),
+        Value::Enum { variant, value } => format!("{variant}({})", preview(value)),
+        Value::Null => "none".to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tests::shown_texts;
+
+    /// Returns changes from `baseline` to `current` as `+path`, `-path` and `~path`.
+    fn changes(baseline: &Value, current: &Value) -> Vec<String> {
+        let mut changes = Vec::new();
+        current.diff(baseline, &mut |change| {
+            let sign = match change {
+                Change::Added { .. } => '+',
+                Change::Removed { .. } => '-',
+                Change::Modified { .. } => '~',
+            };
+            changes.push(format!("{sign}{}", change.path()));
+        });
+        changes
+    }
+
+    fn map(entries: Vec<(&str, Value)>) -> Value {
+        entries.into_iter().map(|(key, value)| (key.to_owned(), value)).collect()
+    }
+
+    #[test]
+    fn equal_values_have_no_changes() {
+        let value = map(vec![("a", Value::from(vec![Value::Int(1)])), ("b", Value::from("x"))]);
+        assert!(changes(&value, &value).is_empty());
+    }
+
+    #[test]
+    fn lists_are_compared_by_index() {
+        let baseline = Value::from(vec![Value::Int(1), Value::Int(2)]);
+        let current = Value::from(vec![Value::Int(1), Value::Int(3), Value::Int(4)]);
+        assert_eq!(changes(&baseline, &current), ["~[1]", "+[2]"]);
+        assert_eq!(changes(&current, &baseline), ["~[1]", "-[2]"]);
+    }
+
+    #[test]
+    fn map_entries_are_reported_in_key_order() {
+        let baseline = map(vec![("c", Value::Int(1)), ("a", Value::Int(1))]);
+        let current = map(vec![("b", Value::Int(1)), ("a", Value::Int(2))]);
+        assert_eq!(changes(&baseline, &current), ["~a", "+b", "-c"]);
+    }
+
+    #[test]
+    fn nested_changes_have_full_paths() {
+        let server = |port: i64| map(vec![("port", Value::Int(port))]);
+        let baseline = map(vec![("servers", Value::from(vec![server(80)]))]);
+        let current = map(vec![("servers", Value::from(vec![server(8080)]))]);
+        assert_eq!(changes(&baseline, &current), ["~servers[0].port"]);
+    }
+
+    #[test]
+    fn enums_are_compared_within_the_same_variant() {
+        let variant = |name: &str, value: i64| Value::Enum {
+            variant: name.to_owned(),
+            value: Box::new(Value::Int(value)),
+        };
+        assert_eq!(changes(&variant("a", 1), &variant("a", 2)), ["~a"]);
+        assert_eq!(changes(&variant("a", 1), &variant("b", 1)), ["~"]);
+    }
+
+    #[test]
+    fn kind_change_modifies_whole_value() {
+        let baseline = map(vec![("a", Value::from(vec![Value::Int(1)]))]);
+        let current = map(vec![("a", Value::Int(1))]);
+        assert_eq!(changes(&baseline, &current), ["~a"]);
+    }
+
+    #[test]
+    fn root_label_is_first_segment() {
+        assert_eq!(root_label("servers[0].port"), "servers");
+        assert_eq!(root_label("[0].port"), "[0]");
+        assert_eq!(root_label("name"), "name");
+    }
+
+    #[test]
+    fn preview_is_single_line() {
+        assert_eq!(preview(&Value::from("a\nb")), r#""a\nb""#);
+        assert_eq!(preview(&Value::Bytes(vec![0xab])), "0xab");
+        assert_eq!(preview(&Value::from(vec![Value::Null; 3])), "list of 3");
+        assert_eq!(preview(&Value::Null), "none");
+    }
+
+    #[test]
+    fn diff_view_shows_changes_and_counts_unchanged() {
+        let baseline = map(vec![("a", Value::Int(1)), ("b", Value::Int(2)), ("c", Value::Int(3))]);
+        let mut current = map(vec![("a", Value::Int(1)), ("b", Value::Int(5)), ("c", Value::Int(3))]);
+
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(None, &mut current, "value").show_diff(ui, &baseline);
+        });
+        for text in ["b", "2", "5", "Unchanged (2)"] {
+            assert!(texts.iter().any(|shown| shown == text), "{text} in {texts:?}");
+        }
+    }
+}
@@ -0,0 +1,300 @@
+use indexmap::IndexMap;
+
+use crate::{color, hex, ConvertError, Desc, Kind, Value};
+
+impl Value {
+    /// Flattens the value into environment variables, e.g. `FOO__BAR__0=1`.
+    ///
+    /// Each scalar becomes one variable named by its path below the root,
+    /// with `prefix` in front, segments joined with `separator` and uppercased.
+    /// List items use their index as the segment.
+    /// A scalar root is named by `prefix` alone.
+    /// Empty lists and maps produce no variables.
+    ///
+    /// Scalars are stringified as `Value::convert` to string does:
+    /// `true`/`false` for bools and the shortest round-tripping form for numbers.
+    ///
+    /// Variables are sorted by name.
+    pub fn to_env(&self, prefix: &str, separator: &str) -> Vec<(String, String)> {
+        let mut vars = Vec::new();
+        flatten(self, prefix.to_uppercase(), separator, &mut vars);
+        vars.sort();
+        vars
+    }
+
+    /// Rebuilds a value of `desc` from environment variables produced by `Value::to_env`.
+    ///
+    /// Variables not starting with `prefix` are ignored.
+    /// Values missing from `vars` take their defaults from `desc`,
+    /// list indices missing in between are filled with defaults as well.
+    /// Since names are uppercased, map keys are restored from the keys
+    /// declared in `desc` ignoring case and lowercased otherwise.
+    /// Map keys that contain `separator` can't be told apart from nested keys.
+    ///
+    /// Fails if a scalar doesn't parse as the kind expected by `desc`.
+    pub fn from_env<I, K, V>(desc: &Desc, vars: I, prefix: &str, separator: &str) -> Result<Value, ConvertError>
+    where
+        I: IntoIterator<Item = (K, V)>,
+        K: AsRef<str>,
+        V: Into<String>,
+    {
+        let prefix = prefix.to_uppercase();
+        let entries: Vec<(Vec<String>, String)> = vars
+            .into_iter()
+            .filter_map(|(name, text)| {
+                let rest = name.as_ref().strip_prefix(prefix.as_str())?;
+                let segments = match (rest, prefix.is_empty()) {
+                    ("", _) => Vec::new(),
+                    (rest, true) => split(rest, separator),
+                    (rest, false) => split(rest.strip_prefix(separator)?, separator),
+                };
+                Some((segments, text.into()))
+            })
+            .collect();
+
+        let entries: Vec<Entry> = entries.iter().map(|(segments, text)| (&segments[..], text.as_str())).collect();
+        rebuild(Some(desc), &entries)
+    }
+}
+
+fn split(rest: &str, separator: &str) -> Vec<String> {
+    rest.split(separator).map(str::to_owned).collect()
+}
+
+fn flatten(value: &Value, name: String, separator: &str, vars: &mut Vec<(String, String)>) {
+    let nested = |segment: &str| match name.is_empty() {
+        true => segment.to_uppercase(),
+        false => format!("{name}{separator}{}", segment.to_uppercase()),
+    };
+
+    match value {
+        Value::Bool(value) => vars.push((name, value.to_string())),
+        Value::Int(value) => vars.push((name, value.to_string())),
+        Value::Uint(value) => vars.push((name, value.to_string())),
+        Value::Float(value) => vars.push((name, value.to_string())),
+        Value::String(value) => vars.push((name, value.clone())),
+        Value::Bytes(bytes) => vars.push((name, hex::encode(bytes))),
+        Value::Color(rgba) => vars.push((name, color::format(*rgba))),
+        Value::List(elems) => {
+            for (idx, elem) in elems.iter().enumerate() {
+                flatten(elem, nested(&idx.to_string()), separator, vars);
+            }
+        }
+        Value::Map(values) => {
+            for (key, value) in values {
+                flatten(value, nested(key), separator, vars);
+            }
+        }
+        Value::Enum { variant, value } => flatten(value, nested(variant), separator, vars),
+        Value::Null => {}
+    }
+}
+
+/// Variable split into path segments below the current value and its text.
+type Entry<'a> = (&'a [String], &'a str);
+
+/// Groups entries by their first segment, keeping the rest of the segments.
+fn group<'a>(entries: &[Entry<'a>]) -> Vec<(&'a str, Vec<Entry<'a>>)> {
+    let mut groups: Vec<(&str, Vec<_>)> = Vec::new();
+    for &(segments, text) in entries {
+        let Some((first, rest)) = segments.split_first() else {
+            continue;
+        };
+        match groups.iter_mut().find(|(key, _)| *key == first) {
+            Some((_, group)) => group.push((rest, text)),
+            None => groups.push((first, vec![(rest, text)])),
+        }
+    }
+    groups
+}
+
+fn rebuild(desc: Option<&Desc>, entries: &[Entry]) -> Result<Value, ConvertError> {
+    let scalar = entries.iter().find(|(segments, _)| segments.is_empty()).map(|&(_, text)| text);
+
+    match desc {
+        None => match scalar {
+            Some(text) => Ok(Value::String(text.to_owned())),
+            None => {
+                let values = group(entries)
+                    .into_iter()
+                    .map(|(key, entries)| Ok((key.to_lowercase(), rebuild(None, &entries)?)))
+                    .collect::<Result<_, ConvertError>>()?;
+                Ok(Value::Map(values))
+            }
+        },
+        Some(Desc::List { elem_desc, .. }) => {
+            let mut items: Vec<(usize, Value)> = Vec::new();
+            for (key, entries) in group(entries) {
+                if let Ok(idx) = key.parse() {
+                    items.push((idx, rebuild(elem_desc.as_deref(), &entries)?));
+                }
+            }
+
+            let len = items.iter().map(|(idx, _)| idx + 1).max().unwrap_or(0);
+            let default = elem_desc.as_deref().map_or(Value::String(String::new()), Desc::default_value);
+            let mut elems = vec![default; len];
+            for (idx, item) in items {
+                elems[idx] = item;
+            }
+            Ok(Value::List(elems))
+        }
+        Some(desc @ Desc::Matrix { .. }) => {
+            let mut value = desc.default_value();
+            if let Value::List(cells) = &mut value {
+                for (key, entries) in group(entries) {
+                    let cell = key.parse::<usize>().ok().and_then(|idx| cells.get_mut(idx));
+                    if let Some(cell) = cell {
+                        *cell = rebuild(Some(&Desc::Float { min: None, max: None, min_path: None, max_path: None, stepper: None, step: None }), &entries)?;
+                    }
+                }
+            }
+            Ok(value)
+        }
+        Some(Desc::Map { value_desc, tabs, required_keys, required_if, .. }) => {
+            let declared = required_keys
+                .iter()
+                .chain(required_if.iter().map(|req| &req.key))
+                .chain(tabs.iter().flat_map(|tab| &tab.keys));
+
+            let mut values = IndexMap::new();
+            for (key, entries) in group(entries) {
+                let key = declared
+                    .clone()
+                    .find(|declared| declared.to_uppercase() == key)
+                    .cloned()
+                    .unwrap_or_else(|| key.to_lowercase());
+                values.insert(key, rebuild(value_desc.as_deref(), &entries)?);
+            }
+            Ok(Value::Map(values))
+        }
+        Some(desc @ Desc::Enum { variants }) => {
+            for (key, entries) in group(entries) {
+                if let Some(variant) = variants.iter().find(|variant| variant.name.to_uppercase() == key) {
+                    return Ok(Value::Enum {
+                        variant: variant.name.clone(),
+                        value: Box::new(match &variant.desc {
+                            Some(desc) => rebuild(Some(desc), &entries)?,
+                            None => Value::Null,
+                        }),
+                    });
+                }
+            }
+            Ok(desc.default_value())
+        }
+        Some(desc @ Desc::Tuple { elems }) => {
+            let mut value = desc.default_value();
+            if let Value::List(items) = &mut value {
+                for (key, entries) in group(entries) {
+                    let idx = key.parse::<usize>().ok().filter(|&idx| idx < elems.len());
+                    if let Some(idx) = idx {
+                        items[idx] = rebuild(Some(&elems[idx]), &entries)?;
+                    }
+                }
+            }
+            Ok(value)
+        }
+        Some(Desc::Optional { .. }) if entries.is_empty() => Ok(Value::Null),
+        Some(desc @ Desc::WithDefault { .. }) if entries.is_empty() => Ok(desc.default_value()),
+        Some(Desc::Optional { inner } | Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner }) => {
+            rebuild(Some(inner), entries)
+        }
+        Some(desc @ Desc::Struct { fields }) => {
+            let mut value = desc.default_value();
+            if let Value::Map(values) = &mut value {
+                for (key, entries) in group(entries) {
+                    if let Some(field) = fields.iter().find(|field| field.name.to_uppercase() == key) {
+                        values.insert(field.name.clone(), rebuild(Some(&field.desc), &entries)?);
+                    }
+                }
+            }
+            Ok(value)
+        }
+        Some(desc) => match scalar {
+            Some(text) => Value::String(text.to_owned()).converted(Kind::of(&desc.default_value())),
+            None => Ok(desc.default_value()),
+        },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StructField;
+
+    fn document() -> (Desc, Value) {
+        let server: Value = [
+            ("host".to_owned(), Value::from("localhost")),
+            ("ports".to_owned(), Value::from(vec![Value::Int(80), Value::Int(443)])),
+        ]
+        .into_iter()
+        .collect();
+        let value: Value = [
+            ("server".to_owned(), server),
+            ("debug".to_owned(), Value::Bool(true)),
+            ("ratio".to_owned(), Value::Float(0.25)),
+        ]
+        .into_iter()
+        .collect();
+
+        let server = Desc::Struct {
+            fields: vec![
+                StructField::new("host", Desc::from_value(&Value::from("localhost"))),
+                StructField::new("ports", Desc::from_value(&Value::from(vec![Value::Int(80)]))),
+            ],
+        };
+        let desc = Desc::Struct {
+            fields: vec![
+                StructField::new("server", server),
+                StructField::new("debug", Desc::Bool),
+                StructField::new("ratio", Desc::from_value(&Value::Float(0.25))),
+            ],
+        };
+        (desc, value)
+    }
+
+    #[test]
+    fn flattens_to_prefixed_uppercase_names() {
+        let (_, value) = document();
+        let vars = value.to_env("app", "__");
+        let expected = [
+            ("APP__DEBUG", "true"),
+            ("APP__RATIO", "0.25"),
+            ("APP__SERVER__HOST", "localhost"),
+            ("APP__SERVER__PORTS__0", "80"),
+            ("APP__SERVER__PORTS__1", "443"),
+        ];
+        let expected: Vec<(String, String)> = expected
+            .iter()
+            .map(|&(name, text)| (name.to_owned(), text.to_owned()))
+            .collect();
+        assert_eq!(vars, expected);
+    }
+
+    #[test]
+    fn round_trips_through_env() {
+        let (desc, value) = document();
+        let mut vars = value.to_env("APP", "__");
+        vars.push(("OTHER".to_owned(), "ignored".to_owned()));
+
+        let restored = Value::from_env(&desc, vars, "APP", "__").unwrap();
+        assert_eq!(restored, value);
+    }
+
+    #[test]
+    fn missing_vars_take_defaults() {
+        let (desc, _) = document();
+        let vars = [("APP__SERVER__PORTS__1", "8080")];
+
+        let restored = Value::from_env(&desc, vars, "APP", "__").unwrap();
+        let ports = &restored.as_map().unwrap()["server"].as_map().unwrap()["ports"];
+        assert_eq!(ports, &Value::from(vec![Value::Int(0), Value::Int(8080)]));
+        assert_eq!(restored.as_map().unwrap()["debug"], Value::Bool(false));
+    }
+
+    #[test]
+    fn unparsable_scalar_fails() {
+        let (desc, _) = document();
+        let vars = [("APP__DEBUG", "maybe")];
+        assert!(Value::from_env(&desc, vars, "APP", "__").is_err());
+    }
+}
@@ -0,0 +1,314 @@
+use std::fmt;
+
+use egui::{Context, Id, Response, Ui};
+
+use crate::path;
+
+/// How serious a problem is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+/// Problem with a value that doesn't match its descriptor.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ProbeError {
+    /// The value is of another kind than the descriptor expects.
+    TypeMismatch {
+        path: String,
+        expected: &'static str,
+        found: &'static str,
+    },
+
+    /// The number is outside of the descriptor's bounds.
+    OutOfRange {
+        path: String,
+        value: f64,
+        min: Option<f64>,
+        max: Option<f64>,
+    },
+
+    /// The descriptor's `min` is greater than its `max`.
+    InvalidRange {
+        path: String,
+        min: String,
+        max: String,
+    },
+
+    /// The string is not one of the descriptor's variants.
+    NotInVariants { path: String, value: String },
+
+    /// The list has wrong number of elements.
+    WrongLength {
+        path: String,
+        expected: usize,
+        found: usize,
+    },
+
+    /// The map lacks required keys.
+    MissingKeys { path: String, keys: Vec<String> },
+
+    /// The map has keys that are not allowed.
+    UnexpectedKeys { path: String, keys: Vec<String> },
+
+    /// Text typed into a field doesn't parse and is kept until fixed.
+    InvalidInput { path: String, text: String },
+
+    /// The collection has more items than its descriptor allows.
+    TooLong {
+        path: String,
+        limit: usize,
+        found: usize,
+    },
+
+    /// The string has fewer or more characters than its descriptor allows.
+    TextLength {
+        path: String,
+        min: Option<usize>,
+        max: Option<usize>,
+        found: usize,
+    },
+
+    /// The list has fewer elements than its descriptor requires.
+    TooShort {
+        path: String,
+        limit: usize,
+        found: usize,
+    },
+
+    /// The string doesn't match the descriptor's pattern.
+    PatternMismatch {
+        path: String,
+        value: String,
+        pattern: String,
+    },
+
+    /// The descriptor's pattern is not a valid regular expression.
+    InvalidPattern {
+        path: String,
+        pattern: String,
+        error: String,
+    },
+
+    /// The struct has fields that its descriptor doesn't declare.
+    ExtraFields { path: String, fields: Vec<String> },
+
+    /// The list element has the same value of the unique `field` as another element.
+    NotUnique { path: String, field: String },
+
+    /// Problem reported by the application rather than by the descriptor.
+    Custom {
+        path: String,
+        message: String,
+        severity: Severity,
+    },
+}
+
+impl ProbeError {
+    /// Returns the path of the value with the problem.
+    pub fn path(&self) -> &str {
+        match self {
+            ProbeError::TypeMismatch { path, .. }
+            | ProbeError::OutOfRange { path, .. }
+            | ProbeError::InvalidRange { path, .. }
+            | ProbeError::NotInVariants { path, .. }
+            | ProbeError::WrongLength { path, .. }
+            | ProbeError::MissingKeys { path, .. }
+            | ProbeError::UnexpectedKeys { path, .. }
+            | ProbeError::InvalidInput { path, .. }
+            | ProbeError::TooLong { path, .. }
+            | ProbeError::TooShort { path, .. }
+            | ProbeError::TextLength { path, .. }
+            | ProbeError::PatternMismatch { path, .. }
+            | ProbeError::InvalidPattern { path, .. }
+            | ProbeError::ExtraFields { path, .. }
+            | ProbeError::NotUnique { path, .. }
+            | ProbeError::Custom { path, .. } => path,
+        }
+    }
+
+    /// Returns the path of the value with the problem split into segments,
+    /// e.g. `["items", "[3]", "name"]` for `items[3].name`.
+    pub fn path_segments(&self) -> Vec<String> {
+        match self.path() {
+            "" => Vec::new(),
+            path => path::ancestors(path).into_iter().map(|(label, _)| label).collect(),
+        }
+    }
+
+    /// Returns how serious the problem is.
+    ///
+    /// Problems found by checking the descriptor are errors,
+    /// except extra struct fields, which are kept as they are and only warned about,
+    /// and too short strings, which are warned about while they are typed.
+    pub fn severity(&self) -> Severity {
+        match self {
+            ProbeError::Custom { severity, .. } => *severity,
+            ProbeError::ExtraFields { .. } => Severity::Warning,
+            ProbeError::TextLength { min: Some(min), found, .. } if found < min => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
+}
+
+impl fmt::Display for ProbeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ProbeError::TypeMismatch { expected, found, .. } => {
+                let expected = match *expected {
+                    "bool" => "boolean",
+                    "int" => "integer",
+                    kind => kind,
+                };
+                write!(f, "Expected {expected}, but is {found} instead")
+            }
+            ProbeError::OutOfRange { value, min, max, .. } => {
+                write!(f, "Value {value} is out of range ")?;
+                match (min, max) {
+                    (Some(min), Some(max)) => write!(f, "{min}..={max}"),
+                    (Some(min), None) => write!(f, "{min}.."),
+                    (None, Some(max)) => write!(f, "..={max}"),
+                    (None, None) => write!(f, ".."),
+                }
+            }
+            ProbeError::InvalidRange { min, max, .. } => write!(
+                f,
+                "Invalid range. `min = {min}` must be not greater than `max = {max}`."
+            ),
+            ProbeError::NotInVariants { value, .. } => {
+                write!(f, "{value:?} is not one of the variants")
+            }
+            ProbeError::WrongLength { expected, found, .. } => {
+                write!(f, "Expected {expected} elements, but list has {found} elements")
+            }
+            ProbeError::MissingKeys { keys, .. } => {
+                write!(f, "Missing required keys: {}", keys.join(", "))
+            }
+            ProbeError::UnexpectedKeys { keys, .. } => {
+                write!(f, "Unexpected keys: {}", keys.join(", "))
+            }
+            ProbeError::ExtraFields { fields, .. } => {
+                write!(f, "Unknown fields: {}", fields.join(", "))
+            }
+            ProbeError::InvalidInput { text, .. } => write!(f, "Invalid input `{text}`"),
+            ProbeError::TooLong { limit, found, .. } => {
+                write!(f, "{found} items would exceed limit of {limit}")
+            }
+            ProbeError::TooShort { limit, found, .. } => {
+                write!(f, "{found} elements, at least {limit} required")
+            }
+            ProbeError::TextLength { min: Some(min), found, .. } if found < min => {
+                write!(f, "{found} characters, at least {min} required")
+            }
+            ProbeError::TextLength { max, found, .. } => match max {
+                Some(max) => write!(f, "{found} characters, at most {max} allowed"),
+                None => write!(f, "{found} characters"),
+            },
+            ProbeError::PatternMismatch { value, pattern, .. } => {
+                write!(f, "{value:?} doesn't match pattern `{pattern}`")
+            }
+            ProbeError::InvalidPattern { pattern, error, .. } => {
+                write!(f, "Invalid pattern `{pattern}`: {error}")
+            }
+            ProbeError::NotUnique { field, .. } if field.is_empty() => {
+                write!(f, "Duplicates another element")
+            }
+            ProbeError::NotUnique { field, .. } => {
+                write!(f, "`{field}` duplicates another element")
+            }
+            ProbeError::Custom { message, .. } => f.write_str(message),
+        }
+    }
+}
+
+impl std::error::Error for ProbeError {}
+
+/// Errors shown by a `ValueProbe` in the current frame.
+#[derive(Clone, Default)]
+struct ShownErrors(Vec<ProbeError>);
+
+pub(crate) fn clear(ctx: &Context, root: Id) {
+    ctx.data_mut(|d| d.insert_temp(root.with("Errors"), ShownErrors::default()));
+}
+
+/// Records the error and shows its message.
+pub(crate) fn show(ui: &mut Ui, root: Id, error: ProbeError) -> Response {
+    if deferred(ui.ctx(), root) {
+        return ui.allocate_response(egui::Vec2::ZERO, egui::Sense::hover());
+    }
+    let color = match error.severity() {
+        Severity::Error => ui.visuals().error_fg_color,
+        Severity::Warning => ui.visuals().warn_fg_color,
+        Severity::Info => ui.visuals().hyperlink_color,
+    };
+    let r = ui.label(egui::RichText::new(error.to_string()).strong().color(color));
+    record(ui.ctx(), root, error);
+    r
+}
+
+/// Records the error without showing it.
+pub(crate) fn record(ctx: &Context, root: Id, error: ProbeError) {
+    if deferred(ctx, root) {
+        return;
+    }
+    ctx.data_mut(|d| {
+        d.get_temp_mut_or_default::<ShownErrors>(root.with("Errors"))
+            .0
+            .push(error)
+    });
+}
+
+/// Holds back errors reported under `root` while `defer` is set,
+/// see `ValidationTiming::OnBlur`.
+pub(crate) fn defer(ctx: &Context, root: Id, defer: bool) {
+    ctx.data_mut(|d| d.insert_temp(root.with("Deferred"), defer));
+}
+
+fn deferred(ctx: &Context, root: Id) -> bool {
+    ctx.data(|d| d.get_temp::<bool>(root.with("Deferred"))).unwrap_or(false)
+}
+
+pub(crate) fn shown(ctx: &Context, root: Id) -> Vec<ProbeError> {
+    ctx.data(|d| d.get_temp::<ShownErrors>(root.with("Errors")))
+        .map_or_else(Vec::new, |errors| errors.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn descriptor_problems_are_errors_by_default() {
+        let mismatch = ProbeError::TypeMismatch {
+            path: String::new(),
+            expected: "int",
+            found: "string",
+        };
+        assert_eq!(mismatch.severity(), Severity::Error);
+
+        let extra = ProbeError::ExtraFields {
+            path: String::new(),
+            fields: vec!["old".to_owned()],
+        };
+        assert_eq!(extra.severity(), Severity::Warning);
+
+        let custom = ProbeError::Custom {
+            path: String::new(),
+            message: "unusual".to_owned(),
+            severity: Severity::Info,
+        };
+        assert_eq!(custom.severity(), Severity::Info);
+        assert!(Severity::Info < Severity::Warning && Severity::Warning < Severity::Error);
+    }
+
+    #[test]
+    fn path_is_split_into_segments() {
+        let error = ProbeError::NotUnique {
+            path: "items[3].name".to_owned(),
+            field: "name".to_owned(),
+        };
+        assert_eq!(error.path_segments(), ["items", "[3]", "name"]);
+    }
+}
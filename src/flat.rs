@@ -0,0 +1,205 @@
+use egui::{collapsing_header::CollapsingState, Context, Id, Response, Ui, WidgetText};
+use egui_probe::{EguiProbe, Probe, Style};
+
+use crate::{path, LayoutMode, ValueProbe};
+
+impl ValueProbe<'_> {
+    /// Shows the value labeled with `label` in the layout selected by `ProbeConfig::layout`.
+    pub fn show(&mut self, ui: &mut Ui, label: impl Into<WidgetText>) -> Response {
+        match self.inherited.config.layout {
+            LayoutMode::Tree => Probe::new(label, self).show(ui),
+            LayoutMode::Columns => {
+                let style = Style::default();
+                let root = self.inherited.root;
+                let label = label.into();
+
+                egui::Grid::new(self.id_source.with("Columns"))
+                    .num_columns(2)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        show_row(&Row::Indented { label, depth: 0 }, "", root, self, ui, &style);
+                    })
+                    .response
+            }
+        }
+    }
+
+    /// Shows the value as a flat grid of `path | value` rows
+    /// instead of the nested tree shown by `egui_probe::Probe`.
+    ///
+    /// Collections get a row with their add controls and a collapse toggle,
+    /// followed by rows for each of their items.
+    /// Collections are expanded unless their descriptor sets `collapsed_by_default`,
+    /// which only applies until the toggle is used.
+    pub fn show_flat(&mut self, ui: &mut Ui) -> Response {
+        let style = Style::default();
+        let root = self.inherited.root;
+
+        egui::Grid::new(self.id_source.with("Flat"))
+            .num_columns(2)
+            .striped(true)
+            .show(ui, |ui| {
+                show_row(&Row::Path, "", root, self, ui, &style);
+            })
+            .response
+    }
+}
+
+/// Marks the collection at `path` to start collapsed.
+/// Called by the probe of the collection in the current frame.
+pub(crate) fn mark_collapsed_by_default(ctx: &Context, root: Id, path: &str) {
+    ctx.data_mut(|d| d.insert_temp(root.with(("CollapsedByDefault", path)), true));
+}
+
+/// Label column of a row.
+enum Row {
+    /// The full path of the value, as in `ValueProbe::show_flat`.
+    Path,
+
+    /// The label of the value indented by its depth, as in `LayoutMode::Columns`.
+    Indented { label: WidgetText, depth: usize },
+}
+
+fn show_row(row: &Row, path: &str, root: Id, probe: &mut dyn EguiProbe, ui: &mut Ui, style: &Style) {
+    match row {
+        Row::Path if path.is_empty() => {
+            ui.weak("(root)");
+        }
+        Row::Path => {
+            ui.label(path);
+        }
+        Row::Indented { label, depth } => {
+            ui.horizontal(|ui| {
+                ui.add_space(*depth as f32 * ui.spacing().indent);
+                ui.label(label.clone());
+            });
+        }
+    }
+
+    let mut open = false;
+    ui.horizontal(|ui| {
+        probe.probe(ui, style);
+
+        if probe.has_inner() {
+            let collapsed = ui
+                .ctx()
+                .data_mut(|d| d.remove_temp::<bool>(root.with(("CollapsedByDefault", path))))
+                .unwrap_or(false);
+
+            let id = ui.make_persistent_id(("FlatOpen", path));
+            let mut state = CollapsingState::load_with_default_open(ui.ctx(), id, !collapsed);
+            state.show_toggle_button(ui, egui::collapsing_header::paint_default_icon);
+            open = state.is_open();
+            state.store(ui.ctx());
+        }
+    });
+    ui.end_row();
+
+    if open {
+        probe.iterate_inner(ui, &mut |label, ui, probe| {
+            let nested = match row {
+                Row::Path => Row::Path,
+                Row::Indented { depth, .. } => Row::Indented {
+                    label: label.into(),
+                    depth: depth + 1,
+                },
+            };
+            show_row(&nested, &path::join(path, label), root, probe, ui, style);
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::tests::shown_texts_in;
+    use crate::{Desc, LayoutMode, ProbeConfig, StructField, Value, ValueProbe};
+
+    fn list(collapsed_by_default: bool) -> Desc {
+        match Desc::from_value(&Value::from(vec![Value::from(1)])) {
+            Desc::List { elem_desc, empty_hint, min_len, max_len, multi_select, unique_by, .. } => Desc::List {
+                elem_desc,
+                empty_hint,
+                collapsed_by_default,
+                min_len,
+                max_len,
+                multi_select,
+                unique_by,
+            },
+            desc => desc,
+        }
+    }
+
+    #[test]
+    fn collapsed_by_default_applies_on_first_render() {
+        let mut value = Value::from(vec![Value::from(1)]);
+        let shows_item = |ctx: &egui::Context, desc: &Desc, value: &mut Value| {
+            shown_texts_in(ctx, |ui| {
+                ValueProbe::new(Some(desc), value, "value").show_flat(ui);
+            })
+            .iter()
+            .any(|text| text == "[0]")
+        };
+
+        let ctx = egui::Context::default();
+        assert!(!shows_item(&ctx, &list(true), &mut value));
+        assert!(!shows_item(&ctx, &list(false), &mut value));
+
+        let ctx = egui::Context::default();
+        assert!(shows_item(&ctx, &list(false), &mut value));
+        assert!(shows_item(&ctx, &list(true), &mut value));
+    }
+
+    #[test]
+    fn columns_align_fields_of_siblings_and_nested_values() {
+        let int = || Desc::from_value(&Value::Int(0));
+        let desc = Desc::Struct {
+            fields: vec![
+                StructField::new("a", int()),
+                StructField::new("much_longer_name", int()),
+                StructField::new("nested", Desc::Struct { fields: vec![StructField::new("x", int())] }),
+            ],
+        };
+        let mut value = Value::Map(
+            [
+                ("a".to_owned(), Value::Int(1)),
+                ("much_longer_name".to_owned(), Value::Int(2)),
+                ("nested".to_owned(), Value::Map([("x".to_owned(), Value::Int(3))].into_iter().collect())),
+            ]
+            .into_iter()
+            .collect(),
+        );
+        let config = ProbeConfig {
+            layout: LayoutMode::Columns,
+            ..ProbeConfig::DEFAULT
+        };
+
+        let ctx = egui::Context::default();
+        let mut show = |ctx: &egui::Context| {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ValueProbe::new(Some(&desc), &mut value, "value").with_config(&config).show(ui, "value");
+            });
+        };
+        shown_texts_in(&ctx, |ui| show(ui.ctx()));
+        let output = ctx.run(Default::default(), |ctx| show(ctx));
+
+        fn collect(shape: &egui::Shape, texts: &mut Vec<(String, egui::Pos2)>) {
+            match shape {
+                egui::Shape::Text(text) => texts.push((text.galley.job.text.clone(), text.pos)),
+                egui::Shape::Vec(shapes) => shapes.iter().for_each(|shape| collect(shape, texts)),
+                _ => {}
+            }
+        }
+        let mut texts = Vec::new();
+        for clipped in &output.shapes {
+            collect(&clipped.shape, &mut texts);
+        }
+        let pos = |shown: &str| texts.iter().find(|(text, _)| text == shown).unwrap_or_else(|| panic!("{shown} in {texts:?}")).1;
+
+        // Field column starts at the same x whatever the label, also for nested values.
+        assert_eq!(pos("1").x, pos("2").x);
+        assert_eq!(pos("1").x, pos("3").x);
+        // Labels of nested values are indented.
+        assert_eq!(pos("a").x, pos("nested").x);
+        assert!(pos("x").x > pos("a").x);
+    }
+}
@@ -0,0 +1,142 @@
+use egui::{vec2, Layout, Rect, Response, RichText, Sense, Ui, Vec2};
+use egui_probe::Probe;
+use hashbrown::HashMap;
+
+use crate::{outline, path, Desc, Value, ValueProbe};
+
+const NODE_WIDTH: f32 = 220.0;
+const NODE_SPACING: Vec2 = vec2(60.0, 40.0);
+const NODES_PER_ROW: usize = 3;
+
+/// Positions and sizes of the nodes relative to the canvas.
+#[derive(Clone, Default)]
+struct GraphLayout {
+    offsets: HashMap<String, Vec2>,
+    sizes: HashMap<String, Vec2>,
+}
+
+impl ValueProbe<'_> {
+    /// Shows a map of maps as a node graph.
+    ///
+    /// Each entry of the top-level map is a node with its fields edited in the node body.
+    /// String fields equal to the key of another node are drawn as edges to that node.
+    /// Nodes are dragged by their titles, and clicking a title scrolls the tree view
+    /// of the `ValueProbe` with the same `id_source` to the node.
+    ///
+    /// Values that are not maps are shown with the regular `Probe`.
+    pub fn show_graph(&mut self, ui: &mut Ui) -> Response {
+        let node_desc = match self.desc.map(Desc::unwrapped) {
+            Some(Desc::Map { value_desc, .. }) => value_desc.as_deref(),
+            _ => None,
+        };
+
+        if !matches!(self.value, Value::Map(_)) {
+            return Probe::new("value", self).show(ui);
+        }
+
+        self.prepare_root(ui);
+
+        let Value::Map(nodes) = &mut *self.value else {
+            unreachable!()
+        };
+
+        let keys: Vec<String> = nodes
+            .keys()
+            .filter(|key| self.inherited.is_visible(&path::join(&self.path, key)))
+            .cloned()
+            .collect();
+
+        let mut edges = Vec::new();
+        for from in &keys {
+            let Value::Map(fields) = &nodes[from] else {
+                continue;
+            };
+            for field in fields.values() {
+                if let Value::String(to) = field {
+                    if to != from && keys.contains(to) {
+                        edges.push((from, to.clone()));
+                    }
+                }
+            }
+        }
+
+        let layout_id = ui.make_persistent_id(self.id_source.with("Graph"));
+        let mut layout = ui
+            .ctx()
+            .data(|d| d.get_temp::<GraphLayout>(layout_id))
+            .unwrap_or_default();
+
+        let size = vec2(ui.available_width(), ui.available_height().max(400.0));
+        let (canvas, r) = ui.allocate_exact_size(size, Sense::hover());
+        let clip = canvas.intersect(ui.clip_rect());
+
+        let node_rect = |layout: &GraphLayout, key: &str| {
+            let offset = layout.offsets.get(key)?;
+            let size = layout.sizes.get(key)?;
+            Some(Rect::from_min_size(canvas.min + *offset, *size))
+        };
+
+        let painter = ui.painter_at(clip);
+        let stroke = ui.visuals().widgets.noninteractive.fg_stroke;
+        for (from, to) in &edges {
+            if let (Some(from), Some(to)) = (node_rect(&layout, from), node_rect(&layout, to)) {
+                let origin = from.right_center();
+                let target = to.left_center();
+                painter.arrow(origin, target - origin, stroke);
+            }
+        }
+
+        let mut clicked = None;
+        let id = self.id_source.with("Map");
+        for (idx, key) in keys.iter().enumerate() {
+            let offset = *layout.offsets.entry(key.clone()).or_insert_with(|| {
+                let col = (idx % NODES_PER_ROW) as f32;
+                let row = (idx / NODES_PER_ROW) as f32;
+                NODE_SPACING * 0.5 + vec2(col * (NODE_WIDTH + NODE_SPACING.x), row * 200.0)
+            });
+
+            let max_rect = Rect::from_min_size(canvas.min + offset, vec2(NODE_WIDTH, f32::INFINITY));
+            let mut child = ui.child_ui(max_rect, Layout::top_down(egui::Align::Min));
+            child.set_clip_rect(clip);
+
+            let frame = egui::Frame::window(ui.style()).show(&mut child, |ui| {
+                ui.set_width(NODE_WIDTH - 16.0);
+
+                let title = egui::Label::new(RichText::new(key).strong()).sense(Sense::click_and_drag());
+                let title = ui.add(title).on_hover_cursor(egui::CursorIcon::Grab);
+                if title.clicked() {
+                    clicked = Some(path::join(&self.path, key));
+                }
+                if title.dragged() {
+                    *layout.offsets.get_mut(key).unwrap() += title.drag_delta();
+                }
+                ui.separator();
+
+                let Some(value) = nodes.get_mut(key) else {
+                    return;
+                };
+                let mut probe = ValueProbe::nested(
+                    node_desc,
+                    value,
+                    id.with(key),
+                    path::join(&self.path, key),
+                    self.depth + 1,
+                    self.inherited.reborrow(),
+                );
+                probe.show(ui, key);
+            });
+
+            layout.sizes.insert(key.clone(), frame.response.rect.size());
+        }
+
+        layout.offsets.retain(|key, _| keys.contains(key));
+        layout.sizes.retain(|key, _| keys.contains(key));
+        ui.ctx().data_mut(|d| d.insert_temp(layout_id, layout));
+
+        if let Some(path) = clicked {
+            outline::request_scroll(ui.ctx(), self.inherited.root, path);
+        }
+
+        r
+    }
+}
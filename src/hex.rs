@@ -0,0 +1,136 @@
+use std::fmt::{self, Write as _};
+
+use egui::{Id, Response, TextEdit, TextStyle, Ui};
+
+/// Error parsing the hex text of `Value::Bytes`.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) enum HexError {
+    /// Character at byte offset `at` of the text is not a hex digit.
+    InvalidChar { at: usize, ch: char },
+
+    /// The text has an odd number of hex digits.
+    OddLength,
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            HexError::InvalidChar { at, ch } => write!(f, "{ch:?} at {at} is not a hex digit"),
+            HexError::OddLength => f.write_str("odd number of hex digits"),
+        }
+    }
+}
+
+/// Formats the bytes as lowercase hex digits, two per byte.
+pub(crate) fn encode(bytes: &[u8]) -> String {
+    let mut text = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        let _ = write!(text, "{byte:02x}");
+    }
+    text
+}
+
+/// Parses hex digits in either case, two per byte.
+/// Whitespace between the digits is ignored, e.g. `"de ad be ef"`.
+pub(crate) fn decode(text: &str) -> Result<Vec<u8>, HexError> {
+    let mut bytes = Vec::with_capacity(text.len() / 2);
+    let mut high = None;
+
+    for (at, ch) in text.char_indices() {
+        if ch.is_whitespace() {
+            continue;
+        }
+        let Some(digit) = ch.to_digit(16) else {
+            return Err(HexError::InvalidChar { at, ch });
+        };
+        match high.take() {
+            None => high = Some(digit as u8),
+            Some(high) => bytes.push(high << 4 | digit as u8),
+        }
+    }
+
+    match high {
+        None => Ok(bytes),
+        Some(_) => Err(HexError::OddLength),
+    }
+}
+
+/// Hex text being edited, kept while the field has focus or doesn't parse.
+#[derive(Clone)]
+struct HexText(String);
+
+/// Shows the bytes as an editable hex string.
+///
+/// Typed characters that are not hex digits or whitespace are rejected,
+/// and input longer than `max_len` bytes is truncated.
+/// Returns the text if it doesn't parse, e.g. has an odd number of digits.
+pub(crate) fn probe_hex(ui: &mut Ui, id: Id, bytes: &mut Vec<u8>, max_len: Option<usize>) -> (Response, Option<String>) {
+    let mut text = ui
+        .data(|d| d.get_temp::<HexText>(id))
+        .map_or_else(|| encode(bytes), |text| text.0);
+    let before = text.clone();
+
+    let r = ui.add(TextEdit::singleline(&mut text).font(TextStyle::Monospace).hint_text("hex"));
+    if r.changed() {
+        match decode(&text) {
+            Err(HexError::InvalidChar { .. }) => text = before,
+            Err(HexError::OddLength) => {}
+            Ok(mut decoded) => {
+                if let Some(max_len) = max_len.filter(|&max_len| decoded.len() > max_len) {
+                    decoded.truncate(max_len);
+                    text = encode(&decoded);
+                }
+                *bytes = decoded;
+            }
+        }
+    }
+
+    let invalid = decode(&text).is_err();
+    if r.has_focus() || invalid {
+        ui.data_mut(|d| d.insert_temp(id, HexText(text.clone())));
+    } else {
+        ui.data_mut(|d| d.remove::<HexText>(id));
+    }
+
+    (r, invalid.then_some(text))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn encode_is_lowercase_pairs() {
+        assert_eq!(encode(&[]), "");
+        assert_eq!(encode(&[0x00, 0x0f, 0xab, 0xff]), "000fabff");
+    }
+
+    #[test]
+    fn decode_accepts_case_and_whitespace() {
+        assert_eq!(decode("DEadBeef"), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(decode(" de ad\tbe ef "), Ok(vec![0xde, 0xad, 0xbe, 0xef]));
+        assert_eq!(decode(""), Ok(Vec::new()));
+    }
+
+    #[test]
+    fn decode_reports_invalid_char() {
+        assert_eq!(decode("abxz"), Err(HexError::InvalidChar { at: 2, ch: 'x' }));
+        assert_eq!(decode("ab é"), Err(HexError::InvalidChar { at: 3, ch: 'é' }));
+        assert_eq!(
+            HexError::InvalidChar { at: 2, ch: 'x' }.to_string(),
+            "'x' at 2 is not a hex digit"
+        );
+    }
+
+    #[test]
+    fn decode_reports_odd_length() {
+        assert_eq!(decode("abc"), Err(HexError::OddLength));
+        assert_eq!(decode("a b c"), Err(HexError::OddLength));
+    }
+
+    #[test]
+    fn round_trips() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(decode(&encode(&bytes)), Ok(bytes));
+    }
+}
@@ -0,0 +1,161 @@
+use egui::Context;
+
+use crate::Value;
+
+/// Undo history of a value edited with `ValueProbe`.
+///
+/// Call [`History::update`] each frame after the value is shown.
+/// Discrete edits, like typing or toggling, become undo entries immediately.
+/// Edits made while dragging, like moving a slider,
+/// are coalesced into a single entry committed when the drag stops.
+#[derive(Clone, Default)]
+pub struct History {
+    undo: Vec<Value>,
+    redo: Vec<Value>,
+    current: Option<Value>,
+    drag_start: Option<Value>,
+    limit: Option<usize>,
+}
+
+impl History {
+    pub fn new() -> Self {
+        History::default()
+    }
+
+    /// Keep at most `limit` undo entries, dropping the oldest ones.
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Records changes made to the value since the last call.
+    pub fn update(&mut self, ctx: &Context, value: &Value) {
+        let dragging = ctx.dragged_id().is_some();
+
+        match &mut self.current {
+            None => self.current = Some(value.clone()),
+            Some(current) if current != value => {
+                let previous = std::mem::replace(current, value.clone());
+                if dragging {
+                    self.drag_start.get_or_insert(previous);
+                } else {
+                    self.commit(previous);
+                }
+            }
+            Some(_) => {}
+        }
+
+        if !dragging {
+            if let Some(start) = self.drag_start.take() {
+                self.commit(start);
+            }
+        }
+    }
+
+    /// Returns `true` if there are changes to undo.
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty() || self.drag_start.is_some()
+    }
+
+    /// Returns `true` if there are undone changes to redo.
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
+    }
+
+    /// Reverts the last change.
+    /// Returns `false` if there is nothing to undo.
+    pub fn undo(&mut self, value: &mut Value) -> bool {
+        if let Some(start) = self.drag_start.take() {
+            self.commit(start);
+        }
+
+        match self.undo.pop() {
+            Some(previous) => {
+                self.redo.push(std::mem::replace(value, previous));
+                self.current = Some(value.clone());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Re-applies the last undone change.
+    /// Returns `false` if there is nothing to redo.
+    pub fn redo(&mut self, value: &mut Value) -> bool {
+        match self.redo.pop() {
+            Some(next) => {
+                self.undo.push(std::mem::replace(value, next));
+                self.current = Some(value.clone());
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn commit(&mut self, previous: Value) {
+        self.undo.push(previous);
+        self.redo.clear();
+
+        if let Some(limit) = self.limit {
+            if self.undo.len() > limit {
+                self.undo.drain(..self.undo.len() - limit);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Runs a frame in which the value is changed to `value`, optionally while dragging.
+    fn frame(ctx: &Context, history: &mut History, value: &Value, dragging: bool) {
+        let _ = ctx.run(Default::default(), |ctx| {
+            if dragging {
+                ctx.set_dragged_id(egui::Id::new("slider"));
+            } else {
+                ctx.stop_dragging();
+            }
+            history.update(ctx, value);
+        });
+    }
+
+    #[test]
+    fn drag_is_one_undo_entry() {
+        let ctx = Context::default();
+        let mut history = History::new();
+        let mut value = Value::Int(0);
+        frame(&ctx, &mut history, &value, false);
+
+        for n in 1..=10 {
+            value = Value::Int(n);
+            frame(&ctx, &mut history, &value, true);
+        }
+        frame(&ctx, &mut history, &value, false);
+
+        assert!(history.undo(&mut value));
+        assert_eq!(value, Value::Int(0));
+        assert!(!history.undo(&mut value));
+        assert!(history.redo(&mut value));
+        assert_eq!(value, Value::Int(10));
+    }
+
+    #[test]
+    fn discrete_edits_are_separate_entries() {
+        let ctx = Context::default();
+        let mut history = History::new().with_limit(2);
+        let mut value = Value::Int(0);
+        frame(&ctx, &mut history, &value, false);
+
+        for n in 1..=3 {
+            value = Value::Int(n);
+            frame(&ctx, &mut history, &value, false);
+        }
+
+        assert!(history.undo(&mut value));
+        assert_eq!(value, Value::Int(2));
+        assert!(history.undo(&mut value));
+        assert_eq!(value, Value::Int(1));
+        assert!(!history.undo(&mut value));
+    }
+}
@@ -0,0 +1,274 @@
+use std::fmt;
+
+use crate::Value;
+
+/// Text format of a document, see `Value::import`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Format {
+    Json,
+    Toml,
+    Yaml,
+}
+
+impl Format {
+    /// Returns the name of the format, e.g. `"json"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Format::Json => "json",
+            Format::Toml => "toml",
+            Format::Yaml => "yaml",
+        }
+    }
+
+    /// Returns `true` if the crate is built with the feature parsing this format.
+    pub fn is_enabled(&self) -> bool {
+        match self {
+            Format::Json => cfg!(feature = "json"),
+            Format::Toml => cfg!(feature = "toml"),
+            Format::Yaml => cfg!(feature = "yaml"),
+        }
+    }
+
+    /// Guesses formats of the text, most likely first.
+    ///
+    /// - Text starting with `{` is JSON.
+    /// - Text starting with a `[section]` line is TOML, any other `[` starts JSON.
+    /// - Text starting with `---` is YAML.
+    /// - Otherwise the first line decides: `key = value` is TOML and `key: value` is YAML.
+    ///
+    /// Comment lines starting with `#` are skipped.
+    pub fn detect(text: &str) -> [Format; 3] {
+        let first = text
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty() && !line.starts_with('#'))
+            .unwrap_or("");
+
+        let is_section = first.starts_with('[')
+            && first.ends_with(']')
+            && first[1..first.len() - 1]
+                .trim_matches(|c| c == '[' || c == ']')
+                .chars()
+                .all(|c| c.is_alphanumeric() || "_-. \"'".contains(c));
+
+        let assignment = first.find('=');
+        let colon = first.find(':');
+
+        if first.starts_with('{') {
+            [Format::Json, Format::Yaml, Format::Toml]
+        } else if is_section {
+            [Format::Toml, Format::Json, Format::Yaml]
+        } else if first.starts_with('[') {
+            [Format::Json, Format::Yaml, Format::Toml]
+        } else if first.starts_with("---") {
+            [Format::Yaml, Format::Json, Format::Toml]
+        } else if assignment.is_some_and(|eq| colon.is_none_or(|colon| eq < colon)) {
+            [Format::Toml, Format::Yaml, Format::Json]
+        } else if colon.is_some() {
+            [Format::Yaml, Format::Toml, Format::Json]
+        } else {
+            [Format::Json, Format::Yaml, Format::Toml]
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned by `Value::import`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ImportError {
+    /// Formats tried in order, with the reason each of them failed.
+    pub tried: Vec<(Format, String)>,
+}
+
+impl fmt::Display for ImportError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.tried.is_empty() {
+            return f.write_str("No document format is enabled");
+        }
+
+        f.write_str("Unrecognized document format.")?;
+        for (format, reason) in &self.tried {
+            write!(f, " Not {format}: {reason}.")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ImportError {}
+
+impl Value {
+    /// Parses a document of unknown format, returning the format it was parsed as.
+    ///
+    /// Formats are tried in the order guessed by `Format::detect`,
+    /// skipping formats whose features are not enabled.
+    ///
+    /// `null` values are imported as `Value::Null`.
+    /// TOML dates are imported as strings.
+    pub fn import(text: &str) -> Result<(Value, Format), ImportError> {
+        let mut tried = Vec::new();
+        for format in Format::detect(text) {
+            if !format.is_enabled() {
+                continue;
+            }
+            match parse(text, format) {
+                Ok(value) => return Ok((value, format)),
+                Err(reason) => tried.push((format, reason)),
+            }
+        }
+        Err(ImportError { tried })
+    }
+}
+
+#[cfg_attr(not(any(feature = "json", feature = "toml", feature = "yaml")), allow(unused_variables))]
+fn parse(text: &str, format: Format) -> Result<Value, String> {
+    match format {
+        #[cfg(feature = "json")]
+        Format::Json => {
+            let value: serde_json::Value = serde_json::from_str(text).map_err(|err| err.to_string())?;
+            Ok(Value::from(value))
+        }
+        #[cfg(feature = "toml")]
+        Format::Toml => {
+            let table = toml::from_str(text).map_err(|err: toml::de::Error| err.message().to_owned())?;
+            Ok(from_toml(toml::Value::Table(table)))
+        }
+        #[cfg(feature = "yaml")]
+        Format::Yaml => {
+            let value = serde_yaml::from_str(text).map_err(|err| err.to_string())?;
+            from_yaml(value)
+        }
+        #[allow(unreachable_patterns)]
+        _ => Err("format is not enabled".to_owned()),
+    }
+}
+
+#[cfg(feature = "toml")]
+fn from_toml(value: toml::Value) -> Value {
+    use toml::Value as Toml;
+
+    match value {
+        Toml::Boolean(value) => Value::Bool(value),
+        Toml::Integer(value) => Value::Int(value),
+        Toml::Float(value) => Value::Float(value),
+        Toml::String(value) => Value::String(value),
+        Toml::Datetime(value) => Value::String(value.to_string()),
+        Toml::Array(elems) => Value::List(elems.into_iter().map(from_toml).collect()),
+        Toml::Table(values) => Value::Map(
+            values
+                .into_iter()
+                .map(|(key, value)| (key, from_toml(value)))
+                .collect(),
+        ),
+    }
+}
+
+#[cfg(feature = "yaml")]
+fn from_yaml(value: serde_yaml::Value) -> Result<Value, String> {
+    use serde_yaml::Value as Yaml;
+
+    let value = match value {
+        Yaml::Null => Value::Null,
+        Yaml::Bool(value) => Value::Bool(value),
+        Yaml::Number(n) => match n.as_i64() {
+            Some(n) => Value::Int(n),
+            None => Value::Float(n.as_f64().unwrap_or(f64::NAN)),
+        },
+        Yaml::String(value) => Value::String(value),
+        Yaml::Sequence(elems) => Value::List(elems.into_iter().map(from_yaml).collect::<Result<_, _>>()?),
+        Yaml::Mapping(values) => Value::Map(
+            values
+                .into_iter()
+                .map(|(key, value)| {
+                    let key = match from_yaml(key)? {
+                        Value::String(key) => key,
+                        Value::Bool(key) => key.to_string(),
+                        Value::Int(key) => key.to_string(),
+                        Value::Uint(key) => key.to_string(),
+                        Value::Float(key) => key.to_string(),
+                        Value::Bytes(_) | Value::Color(_) | Value::List(_) | Value::Map(_) | Value::Enum { .. } | Value::Null => {
+                            return Err("keys must be scalars".to_owned())
+                        }
+                    };
+                    Ok((key, from_yaml(value)?))
+                })
+                .collect::<Result<_, String>>()?,
+        ),
+        Yaml::Tagged(tagged) => from_yaml(tagged.value)?,
+    };
+    Ok(value)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_format_by_content() {
+        assert_eq!(Format::detect(r#"{"a": 1}"#)[0], Format::Json);
+        assert_eq!(Format::detect("[1, 2]")[0], Format::Json);
+        assert_eq!(Format::detect("[server]\nport = 80")[0], Format::Toml);
+        assert_eq!(Format::detect("[[servers]]\nport = 80")[0], Format::Toml);
+        assert_eq!(Format::detect("# comment\nport = 80")[0], Format::Toml);
+        assert_eq!(Format::detect("url = \"http://x\"")[0], Format::Toml);
+        assert_eq!(Format::detect("---\na: 1")[0], Format::Yaml);
+        assert_eq!(Format::detect("port: 80")[0], Format::Yaml);
+        assert_eq!(Format::detect("")[0], Format::Json);
+    }
+
+    #[test]
+    fn error_lists_tried_formats() {
+        let error = ImportError {
+            tried: vec![(Format::Json, "expected value".to_owned()), (Format::Yaml, "bad indent".to_owned())],
+        };
+        assert_eq!(
+            error.to_string(),
+            "Unrecognized document format. Not json: expected value. Not yaml: bad indent."
+        );
+        assert_eq!(ImportError { tried: Vec::new() }.to_string(), "No document format is enabled");
+    }
+
+    #[cfg(feature = "json")]
+    #[test]
+    fn imports_json() {
+        let (value, format) = Value::import(r#"{"a": [1, null]}"#).unwrap();
+        assert_eq!(format, Format::Json);
+        let expected: Value = [("a".to_owned(), Value::from(vec![Value::Int(1), Value::Null]))].into_iter().collect();
+        assert_eq!(value, expected);
+    }
+
+    #[cfg(feature = "toml")]
+    #[test]
+    fn imports_toml() {
+        let (value, format) = Value::import("[server]\nport = 80").unwrap();
+        assert_eq!(format, Format::Toml);
+        let server: Value = [("port".to_owned(), Value::Int(80))].into_iter().collect();
+        assert_eq!(value, [("server".to_owned(), server)].into_iter().collect());
+    }
+
+    #[cfg(feature = "yaml")]
+    #[test]
+    fn imports_yaml() {
+        let (value, format) = Value::import("name: x\nlist:\n  - true").unwrap();
+        assert_eq!(format, Format::Yaml);
+        let expected: Value = [
+            ("name".to_owned(), Value::from("x")),
+            ("list".to_owned(), Value::from(vec![Value::Bool(true)])),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(value, expected);
+    }
+
+    #[cfg(all(feature = "json", feature = "toml", feature = "yaml"))]
+    #[test]
+    fn unparsable_text_reports_every_format() {
+        let error = Value::import("{ not: [valid").unwrap_err();
+        let tried: Vec<Format> = error.tried.iter().map(|(format, _)| *format).collect();
+        assert_eq!(tried, [Format::Json, Format::Yaml, Format::Toml]);
+    }
+}
@@ -0,0 +1,170 @@
+use serde_json::{Map, Number, Value as Json};
+
+use crate::{color, Value};
+
+impl Value {
+    /// Converts a JSON value.
+    ///
+    /// Numbers become `Value::Int` if they fit `i64` and `Value::Float` otherwise,
+    /// `null` becomes `Value::Null` and objects become maps with keys in document order
+    /// if `serde_json` keeps it.
+    pub fn from_json(json: &Json) -> Value {
+        match json {
+            Json::Null => Value::Null,
+            Json::Bool(value) => Value::Bool(*value),
+            Json::Number(n) => match n.as_i64() {
+                Some(n) => Value::Int(n),
+                None => Value::Float(n.as_f64().unwrap_or(f64::NAN)),
+            },
+            Json::String(value) => Value::String(value.clone()),
+            Json::Array(elems) => Value::List(elems.iter().map(Value::from_json).collect()),
+            Json::Object(values) => Value::Map(
+                values
+                    .iter()
+                    .map(|(key, value)| (key.clone(), Value::from_json(value)))
+                    .collect(),
+            ),
+        }
+    }
+
+    /// Converts the value to JSON like it is serialized, so some values don't come back the same:
+    /// - non-finite floats become `null`, which is read back as `Value::Null`,
+    /// - uints above `i64::MAX` are read back as floats, and smaller ones as ints,
+    /// - bytes become arrays of numbers and are read back as lists,
+    /// - colors become `#rrggbb` or `#rrggbbaa` strings,
+    /// - enums become objects with the variant name as the single key,
+    /// - map entries are sorted by key unless `serde_json` keeps insertion order.
+    pub fn to_json(&self) -> Json {
+        match self {
+            Value::Bool(value) => Json::Bool(*value),
+            Value::Int(value) => Json::Number((*value).into()),
+            Value::Uint(value) => Json::Number((*value).into()),
+            Value::Float(value) => Number::from_f64(*value).map_or(Json::Null, Json::Number),
+            Value::String(value) => Json::String(value.clone()),
+            Value::Bytes(bytes) => Json::Array(bytes.iter().map(|&byte| Json::from(byte)).collect()),
+            Value::Color(rgba) => Json::String(color::format(*rgba)),
+            Value::List(elems) => Json::Array(elems.iter().map(Value::to_json).collect()),
+            Value::Map(values) => Json::Object(
+                values
+                    .iter()
+                    .map(|(key, value)| (key.clone(), value.to_json()))
+                    .collect(),
+            ),
+            Value::Enum { variant, value } => {
+                let mut object = Map::new();
+                object.insert(variant.clone(), value.to_json());
+                Json::Object(object)
+            }
+            Value::Null => Json::Null,
+        }
+    }
+}
+
+/// Converts like `Value::from_json`.
+impl From<Json> for Value {
+    #[inline]
+    fn from(json: Json) -> Self {
+        Value::from_json(&json)
+    }
+}
+
+/// Converts like `Value::to_json`.
+impl From<Value> for Json {
+    #[inline]
+    fn from(value: Value) -> Self {
+        value.to_json()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+
+    use super::*;
+    use crate::{
+        tests::{shown_texts, type_into_text},
+        Desc, ValueProbe,
+    };
+
+    #[test]
+    fn nested_object_round_trips() {
+        let json = json!({
+            "name": "server",
+            "ports": [80, 443],
+            "tls": { "enabled": true, "ratio": 0.5 },
+            "comment": null,
+        });
+
+        let value = Value::from(json.clone());
+
+        assert_eq!(value.as_map().unwrap()["ports"], Value::from(vec![Value::Int(80), Value::Int(443)]));
+        assert_eq!(value.as_map().unwrap()["comment"], Value::Null);
+        assert_eq!(Json::from(value), json);
+    }
+
+    #[test]
+    fn numbers_become_ints_when_they_fit() {
+        assert_eq!(Value::from(json!(-3)), Value::Int(-3));
+        assert_eq!(Value::from(json!(1.5)), Value::Float(1.5));
+        assert_eq!(Value::from(json!(u64::MAX)), Value::Float(u64::MAX as f64));
+    }
+
+    #[test]
+    fn lossy_values_are_documented() {
+        assert_eq!(Value::Float(f64::NAN).to_json(), Json::Null);
+        assert_eq!(Value::Uint(7).to_json(), json!(7));
+        assert_eq!(Value::from(Value::Uint(7).to_json()), Value::Int(7));
+        assert_eq!(Value::Bytes(vec![1, 2]).to_json(), json!([1, 2]));
+        assert_eq!(Value::Color([255, 0, 0, 255]).to_json(), json!("#ff0000"));
+
+        let variant = Value::Enum {
+            variant: "on".to_owned(),
+            value: Box::new(Value::Int(1)),
+        };
+        assert_eq!(variant.to_json(), json!({ "on": 1 }));
+    }
+
+    #[test]
+    fn loaded_json_is_shown_and_edited() {
+        let json: Json = serde_json::from_str(r#"{ "title": "hello", "author": "me" }"#).unwrap();
+        let mut value = Value::from(json);
+        let desc = Desc::infer(&value);
+
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+
+        assert!(texts.iter().any(|text| text == "title"));
+        assert!(texts.iter().any(|text| text == "hello"));
+        assert!(texts.iter().any(|text| text == "me"));
+        assert_eq!(desc.validate(&value), Ok(()));
+
+        type_into_text(&egui::Context::default(), "hello", 0, "bye", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert_eq!(value.to_json(), json!({ "title": "bye", "author": "me" }));
+    }
+
+
+    #[test]
+    fn bridges_round_trip_nested_arrays_and_objects() {
+        let json = json!([
+            { "id": 1, "tags": ["a", "b"] },
+            { "id": 2, "tags": [], "parent": { "id": 1 } },
+            [[true], [false, null]],
+        ]);
+
+        let value = Value::from_json(&json);
+        let elems = value.as_list().unwrap();
+        assert_eq!(elems[0].as_map().unwrap()["tags"], Value::from(vec!["a".into(), "b".into()]));
+        assert_eq!(elems[1].as_map().unwrap()["parent"].as_map().unwrap()["id"], Value::Int(1));
+        assert_eq!(elems[2].as_list().unwrap()[1], Value::from(vec![Value::Bool(false), Value::Null]));
+        assert_eq!(value.to_json(), json);
+    }
+
+    #[test]
+    fn null_becomes_null_value() {
+        assert_eq!(Value::from_json(&Json::Null), Value::Null);
+        assert_eq!(Value::Null.to_json(), Json::Null);
+    }
+}
@@ -0,0 +1,281 @@
+use std::fmt;
+
+use crate::{color, hex, Value};
+
+/// Kind of a `Value`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Kind {
+    Bool,
+    Int,
+    Uint,
+    Float,
+    String,
+    Bytes,
+    Color,
+    List,
+    Map,
+    Enum,
+    Null,
+}
+
+impl Kind {
+    /// All kinds in declaration order.
+    pub const ALL: [Kind; 11] = [
+        Kind::Bool,
+        Kind::Int,
+        Kind::Uint,
+        Kind::Float,
+        Kind::String,
+        Kind::Bytes,
+        Kind::Color,
+        Kind::List,
+        Kind::Map,
+        Kind::Enum,
+        Kind::Null,
+    ];
+
+    /// Returns the kind of the value.
+    pub fn of(value: &Value) -> Kind {
+        match value {
+            Value::Bool(_) => Kind::Bool,
+            Value::Int(_) => Kind::Int,
+            Value::Uint(_) => Kind::Uint,
+            Value::Float(_) => Kind::Float,
+            Value::String(_) => Kind::String,
+            Value::Bytes(_) => Kind::Bytes,
+            Value::Color(_) => Kind::Color,
+            Value::List(_) => Kind::List,
+            Value::Map(_) => Kind::Map,
+            Value::Enum { .. } => Kind::Enum,
+            Value::Null => Kind::Null,
+        }
+    }
+
+    /// Returns the name of the kind, e.g. `"int"`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Kind::Bool => "bool",
+            Kind::Int => "int",
+            Kind::Uint => "uint",
+            Kind::Float => "float",
+            Kind::String => "string",
+            Kind::Bytes => "bytes",
+            Kind::Color => "color",
+            Kind::List => "list",
+            Kind::Map => "map",
+            Kind::Enum => "enum",
+            Kind::Null => "null",
+        }
+    }
+}
+
+impl fmt::Display for Kind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// Error returned by `Value::convert`.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ConvertError {
+    /// Values of kind `from` are never converted to kind `to`.
+    Unsupported { from: Kind, to: Kind },
+
+    /// The string doesn't parse as a value of kind `to`.
+    Unparsable { text: String, to: Kind },
+}
+
+impl fmt::Display for ConvertError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ConvertError::Unsupported { from, to } => write!(f, "Can't convert {from} to {to}"),
+            ConvertError::Unparsable { text, to } => write!(f, "{text:?} is not a valid {to}"),
+        }
+    }
+}
+
+impl std::error::Error for ConvertError {}
+
+impl Value {
+    /// Returns `true` if values of kind `from` can be converted to kind `to`.
+    ///
+    /// The rules are:
+    /// - every kind converts to itself,
+    /// - `int`, `uint` and `float` convert into each other, floats are truncated
+    ///   and numbers saturate at the bounds of the target, e.g. negative numbers become `0u`,
+    /// - `bool`, `int`, `uint` and `float` convert to `string`,
+    /// - `string` converts to `bool`, `int`, `uint` and `float` if it parses,
+    /// - `string` and `bytes` convert into each other as hex digits, e.g. `"dead"`,
+    /// - `int` converts to `bytes` in big-endian order without leading zero bytes,
+    /// - `string` and `color` convert into each other as `#rrggbb` or `#rrggbbaa`,
+    /// - `list`, `map`, `enum` and `null` convert only to themselves.
+    pub fn can_convert(from: Kind, to: Kind) -> bool {
+        use Kind::*;
+
+        matches!(
+            (from, to),
+            (Int | Uint, Float)
+                | (Float | Uint, Int)
+                | (Int | Float, Uint)
+                | (Bool | Int | Uint | Float | Bytes | Color, String)
+                | (String, Bool | Int | Uint | Float | Bytes | Color)
+                | (Int, Bytes)
+        ) || from == to
+    }
+
+    /// Converts the value to kind `to` following the rules of `Value::can_convert`.
+    ///
+    /// The value is left unchanged on error.
+    pub fn convert(&mut self, to: Kind) -> Result<(), ConvertError> {
+        *self = self.converted(to)?;
+        Ok(())
+    }
+
+    /// Returns the value converted to kind `to`, see `Value::convert`.
+    pub(crate) fn converted(&self, to: Kind) -> Result<Value, ConvertError> {
+        let from = Kind::of(self);
+        if !Value::can_convert(from, to) {
+            return Err(ConvertError::Unsupported { from, to });
+        }
+
+        let unparsable = |text: &str| ConvertError::Unparsable {
+            text: text.to_owned(),
+            to,
+        };
+
+        let value = match (self, to) {
+            (Value::Int(value), Kind::Float) => Value::Float(*value as f64),
+            (Value::Float(value), Kind::Int) => Value::Int(*value as i64),
+            (Value::Uint(value), Kind::Int) => Value::Int(i64::try_from(*value).unwrap_or(i64::MAX)),
+            (Value::Uint(value), Kind::Float) => Value::Float(*value as f64),
+            (Value::Int(value), Kind::Uint) => Value::Uint(u64::try_from(*value).unwrap_or(0)),
+            (Value::Float(value), Kind::Uint) => Value::Uint(*value as u64),
+            (Value::Uint(value), Kind::String) => Value::String(value.to_string()),
+            (Value::Bool(value), Kind::String) => Value::String(value.to_string()),
+            (Value::Int(value), Kind::String) => Value::String(value.to_string()),
+            (Value::Float(value), Kind::String) => Value::String(value.to_string()),
+            (Value::Bytes(bytes), Kind::String) => Value::String(hex::encode(bytes)),
+            (Value::Color(rgba), Kind::String) => Value::String(color::format(*rgba)),
+            (Value::Int(value), Kind::Bytes) => {
+                let bytes = value.to_be_bytes();
+                let zeros = bytes.iter().take(7).take_while(|&&byte| byte == 0).count();
+                Value::Bytes(bytes[zeros..].to_vec())
+            }
+            (Value::String(text), Kind::Bool) => {
+                Value::Bool(text.trim().parse().map_err(|_| unparsable(text))?)
+            }
+            (Value::String(text), Kind::Int) => {
+                Value::Int(text.trim().parse().map_err(|_| unparsable(text))?)
+            }
+            (Value::String(text), Kind::Uint) => {
+                Value::Uint(text.trim().parse().map_err(|_| unparsable(text))?)
+            }
+            (Value::String(text), Kind::Float) => {
+                Value::Float(text.trim().parse().map_err(|_| unparsable(text))?)
+            }
+            (Value::String(text), Kind::Bytes) => Value::Bytes(hex::decode(text).map_err(|_| unparsable(text))?),
+            (Value::String(text), Kind::Color) => Value::Color(color::parse(text).ok_or_else(|| unparsable(text))?),
+            (value, _) => value.clone(),
+        };
+
+        Ok(value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bytes_convert_to_and_from_hex_strings() {
+        let bytes = Value::Bytes(vec![0xde, 0xad]);
+        assert_eq!(bytes.converted(Kind::String), Ok(Value::from("dead")));
+        assert_eq!(Value::from("DE AD").converted(Kind::Bytes), Ok(bytes));
+        assert!(matches!(
+            Value::from("xyz").converted(Kind::Bytes),
+            Err(ConvertError::Unparsable { to: Kind::Bytes, .. })
+        ));
+    }
+
+    #[test]
+    fn int_converts_to_big_endian_bytes() {
+        assert_eq!(Value::Int(0x1234).converted(Kind::Bytes), Ok(Value::Bytes(vec![0x12, 0x34])));
+        assert_eq!(Value::Int(0).converted(Kind::Bytes), Ok(Value::Bytes(vec![0])));
+        assert_eq!(Value::Int(-1).converted(Kind::Bytes), Ok(Value::Bytes(vec![0xff; 8])));
+        assert!(!Value::can_convert(Kind::Bytes, Kind::Int));
+    }
+
+
+    #[test]
+    fn numbers_truncate_and_saturate() {
+        assert_eq!(Value::Float(2.9).converted(Kind::Int), Ok(Value::Int(2)));
+        assert_eq!(Value::Float(-2.9).converted(Kind::Uint), Ok(Value::Uint(0)));
+        assert_eq!(Value::Int(-5).converted(Kind::Uint), Ok(Value::Uint(0)));
+        assert_eq!(Value::Uint(u64::MAX).converted(Kind::Int), Ok(Value::Int(i64::MAX)));
+        assert_eq!(Value::Float(1e30).converted(Kind::Int), Ok(Value::Int(i64::MAX)));
+        assert_eq!(Value::Uint(3).converted(Kind::Float), Ok(Value::Float(3.0)));
+    }
+
+    #[test]
+    fn strings_parse_trimmed() {
+        assert_eq!(Value::from(" 42 ").converted(Kind::Int), Ok(Value::Int(42)));
+        assert_eq!(Value::from("true").converted(Kind::Bool), Ok(Value::Bool(true)));
+        assert_eq!(Value::from("0.5").converted(Kind::Float), Ok(Value::Float(0.5)));
+        assert_eq!(Value::from("#ff000080").converted(Kind::Color), Ok(Value::Color([255, 0, 0, 128])));
+        assert_eq!(Value::Color([255, 0, 0, 255]).converted(Kind::String), Ok(Value::from("#ff0000")));
+        assert_eq!(Value::Bool(false).converted(Kind::String), Ok(Value::from("false")));
+    }
+
+    #[test]
+    fn failed_conversion_leaves_value_unchanged() {
+        let mut value = Value::from("-1");
+        assert_eq!(
+            value.convert(Kind::Uint),
+            Err(ConvertError::Unparsable {
+                text: "-1".to_owned(),
+                to: Kind::Uint,
+            })
+        );
+        assert_eq!(value, Value::from("-1"));
+
+        let mut value = Value::Bool(true);
+        assert_eq!(
+            value.convert(Kind::Int),
+            Err(ConvertError::Unsupported {
+                from: Kind::Bool,
+                to: Kind::Int,
+            })
+        );
+        assert_eq!(value, Value::Bool(true));
+    }
+
+    #[test]
+    fn collections_convert_only_to_themselves() {
+        for from in [Kind::List, Kind::Map, Kind::Enum, Kind::Null] {
+            for to in Kind::ALL {
+                assert_eq!(Value::can_convert(from, to), from == to, "{from} to {to}");
+            }
+        }
+        for kind in Kind::ALL {
+            assert!(Value::can_convert(kind, kind));
+        }
+
+        let list = Value::from(vec![Value::Int(1)]);
+        assert_eq!(list.converted(Kind::List), Ok(list.clone()));
+    }
+
+    #[test]
+    fn kind_of_value_has_its_name() {
+        assert_eq!(Kind::of(&Value::Uint(1)), Kind::Uint);
+        assert_eq!(Kind::of(&Value::Null).to_string(), "null");
+        assert_eq!(Value::Color([0; 4]).kind(), "color");
+        assert_eq!(
+            ConvertError::Unsupported {
+                from: Kind::Map,
+                to: Kind::Int,
+            }
+            .to_string(),
+            "Can't convert map to int"
+        );
+    }
+}
@@ -1,38 +1,268 @@
-use std::{fmt::Display, hash::Hash};
+use std::{
+    fmt::{self, Display},
+    hash::Hash,
+};
 
-use egui::{Id, Response, Ui};
+use egui::{Color32, Id, KeyboardShortcut, Rect, Response, Ui, WidgetText};
 use egui_probe::{EguiProbe, Style};
 use hashbrown::HashMap;
+use indexmap::IndexMap;
+
+mod apply;
+#[cfg(feature = "binary")]
+mod binary;
+mod bind;
+mod bounds;
+mod breadcrumb;
+mod changelog;
+mod coercion;
+mod color;
+mod condition;
+mod config;
+mod describe;
+mod diff;
+mod env;
+mod error;
+mod flat;
+#[cfg(feature = "graph")]
+mod graph;
+mod hex;
+mod history;
+mod import;
+#[cfg(feature = "json")]
+mod json;
+mod kind;
+mod number;
+mod outline;
+mod overrides;
+mod path;
+mod pattern;
+mod preview;
+mod scroll;
+#[cfg(feature = "serde")]
+mod serialize;
+mod tabs;
+mod validate;
+
+pub use self::{
+    apply::{ApplyReport, ChangeKind},
+    bind::Bindings,
+    breadcrumb::Breadcrumb,
+    changelog::{ChangeLog, LogEntry},
+    coercion::{CoercionEvent, CoercionReason},
+    condition::{Condition, RequiredIf},
+    config::{LayoutMode, ProbeConfig, RangePolicy, Shortcuts, SizeLimit, ValidationTiming},
+    describe::DescribeValue,
+    diff::Change,
+    error::{ProbeError, Severity},
+    history::History,
+    import::{Format, ImportError},
+    kind::{ConvertError, Kind},
+    number::{HumanizeKind, NumberFormat},
+    outline::Outline,
+    overrides::DescOverrides,
+    path::PathSeg,
+    preview::DefaultPreview,
+    scroll::ScrollSync,
+};
+
+#[doc(hidden)]
+pub use self::describe::__private;
+
+#[cfg(feature = "binary")]
+pub use self::binary::DecodeError;
+
+#[cfg(feature = "derive")]
+pub use egui_any_derive::DescribeValue;
 
 /// Top-level descriptio of a value.
-#[derive(Clone, Debug, Default, EguiProbe)]
+#[derive(Clone, Debug, Default, PartialEq, EguiProbe)]
 pub enum Desc {
     /// A boolean value.
     #[default]
     Bool,
 
     /// An integer value.
-    Int { min: Option<i64>, max: Option<i64> },
+    Int {
+        min: Option<i64>,
+        max: Option<i64>,
+
+        // Paths to numeric fields of the document that override `min` and `max`.
+        min_path: Option<String>,
+        max_path: Option<String>,
+
+        // Units to show the integer in, e.g. `1 MiB` for `1048576` bytes.
+        humanize: Option<HumanizeKind>,
+
+        // Step of the −/+ buttons shown instead of the drag value, if set.
+        stepper: Option<i64>,
+
+        // Change of the drag value per pixel, if set.
+        // Edits are snapped to multiples of it if it is at least one.
+        step: Option<f64>,
+    },
+
+    /// An unsigned integer value.
+    Uint {
+        min: Option<u64>,
+        max: Option<u64>,
+    },
 
     /// A floating-point value.
-    Float { min: Option<f64>, max: Option<f64> },
+    Float {
+        min: Option<f64>,
+        max: Option<f64>,
+
+        // Paths to numeric fields of the document that override `min` and `max`.
+        min_path: Option<String>,
+        max_path: Option<String>,
+
+        // Step of the −/+ buttons shown instead of the drag value, if set.
+        stepper: Option<f64>,
+
+        // Change of the drag value per pixel, if set.
+        step: Option<f64>,
+    },
 
     /// A string value.
     String {
-        variants: Option<Vec<String>>,
+        variants: Option<Vec<Variant>>,
+
+        // Whether leading and trailing whitespace is removed when editing is finished.
+        trim: bool,
+
+        // Whether runs of whitespace inside the string are replaced with a single space
+        // when editing is finished.
+        collapse_whitespace: bool,
+
+        // Minimum number of characters of a free-form string.
+        // The default value is still empty, so a required string is flagged until filled in.
+        min_len: Option<usize>,
+
+        // Maximum number of characters of a free-form string.
+        // Longer input is truncated while editing.
+        max_len: Option<usize>,
+
+        // Regular expression the whole free-form string must match.
+        // Checked only with the `regex` feature.
+        pattern: Option<String>,
+
+        // Whether a free-form string is edited in a multi-line text field.
+        multiline: bool,
+    },
+
+    /// A binary blob, edited as a hex string.
+    Bytes {
+        // Maximum number of bytes.
+        // Longer input is truncated while editing.
+        max_len: Option<usize>,
+    },
+
+    /// A color, edited with a color picker.
+    Color {
+        // Whether the alpha channel is edited.
+        // Otherwise the color is kept opaque.
+        alpha: bool,
     },
 
     /// A list of values.
     List {
         // The description of the values.
         elem_desc: Option<Box<Desc>>,
+
+        // Text shown while the list is empty.
+        empty_hint: Option<String>,
+
+        // Whether the list starts collapsed in views that expand collections,
+        // e.g. `ValueProbe::show_flat`.
+        collapsed_by_default: bool,
+
+        // Minimum number of elements.
+        // Elements can't be deleted below it, and shorter lists and the default value
+        // are filled with default elements.
+        min_len: Option<usize>,
+
+        // Maximum number of elements.
+        max_len: Option<usize>,
+
+        // Whether items get checkboxes to delete or duplicate several of them at once.
+        multi_select: bool,
+
+        // Path within each element of a field that must differ between elements,
+        // empty for the elements themselves. New elements repeating it are not added.
+        unique_by: Option<String>,
     },
 
     /// A map of key-value pairs.
     Map {
         // The description of the values.
         value_desc: Option<Box<Desc>>,
+
+        // Groups of keys shown as tabs by `ValueProbe::show_tabs`.
+        tabs: Vec<Tab>,
+
+        // Text shown while the map is empty.
+        empty_hint: Option<String>,
+
+        // Whether the map starts collapsed in views that expand collections,
+        // e.g. `ValueProbe::show_flat`.
+        collapsed_by_default: bool,
+
+        // Maximum number of entries.
+        max_entries: Option<usize>,
+
+        // Keys that must be present in the map.
+        required_keys: Vec<String>,
+
+        // Keys that must be present in the map while their conditions hold.
+        required_if: Vec<RequiredIf>,
+
+        // Whether keys other than `required_keys` and `required_if` keys are reported as errors.
+        strict_keys: bool,
+    },
+
+    /// A matrix of floating-point values stored row by row in a list.
+    Matrix {
+        rows: usize,
+        cols: usize,
+        min: Option<f64>,
+        max: Option<f64>,
+
+        // Whether the default value is the identity matrix rather than the zero matrix.
+        identity: bool,
+    },
+
+    /// One of several named variants, each with its own descriptor.
+    Enum { variants: Vec<EnumVariant> },
+
+    /// A record with a fixed set of named fields, each with its own descriptor,
+    /// stored as a map and shown in the order of `fields`.
+    Struct { fields: Vec<StructField> },
+
+    /// A fixed number of positional values, each with its own descriptor, stored as a list.
+    Tuple { elems: Vec<Desc> },
+
+    /// A value described by `inner` that may be absent, stored as `Value::Null` then.
+    Optional { inner: Box<Desc> },
+
+    /// A value described by `inner` with help text shown when hovering its widget.
+    Documented { doc: String, inner: Box<Desc> },
+
+    /// A value described by `inner` that defaults to `default` instead of the default of `inner`.
+    ///
+    /// Use `Desc::with_default` to check that `default` fits `inner`.
+    /// A `default` of another kind is ignored.
+    WithDefault {
+        #[egui_probe(skip)]
+        default: Box<Value>,
+        inner: Box<Desc>,
     },
+
+    /// A value described by `inner` that is hidden unless "Show advanced" is checked
+    /// on the struct, tuple, list or map holding it.
+    ///
+    /// Hidden values are kept as they are.
+    Advanced { inner: Box<Desc> },
 }
 
 impl Desc {
@@ -40,562 +270,5941 @@ impl Desc {
         match *self {
             Desc::Bool => Value::Bool(false),
             Desc::Int { min, .. } => Value::Int(min.unwrap_or(0)),
+            Desc::Uint { min, .. } => Value::Uint(min.unwrap_or(0)),
             Desc::Float { min, .. } => Value::Float(min.unwrap_or(0.0)),
-            Desc::String { ref variants } => {
-                variants.as_ref().and_then(|v| v.first()).map_or_else(
-                    || Value::String(String::new()),
-                    |s| Value::String(s.clone()),   
-                )
+            Desc::String { ref variants, .. } => {
+                Value::String(variants.as_ref().and_then(|v| v.first()).map_or_else(String::new, |s| s.name.clone()))
             }
-            Desc::List { .. } => Value::List(Vec::new()),
-            Desc::Map { .. } => Value::Map(HashMap::new()),
+            Desc::Bytes { .. } => Value::Bytes(Vec::new()),
+            Desc::Color { .. } => Value::Color([255; 4]),
+            Desc::List { ref elem_desc, min_len, .. } => {
+                let elem = elem_desc.as_deref().map_or_else(|| Desc::default().default_value(), Desc::default_value);
+                Value::List(vec![elem; min_len.unwrap_or(0)])
+            }
+            Desc::Map { .. } => Value::Map(IndexMap::new()),
+            Desc::Matrix { rows, cols, identity, .. } => Value::List(
+                (0..rows * cols)
+                    .map(|idx| Value::Float(matrix_default(idx / cols, idx % cols, identity)))
+                    .collect(),
+            ),
+            Desc::Enum { ref variants } => match variants.first() {
+                Some(first) => first.default_value(),
+                None => Value::Enum {
+                    variant: String::new(),
+                    value: Box::new(Value::Null),
+                },
+            },
+            Desc::Struct { ref fields } => Value::Map(
+                fields
+                    .iter()
+                    .map(|field| (field.name.clone(), field.desc.default_value()))
+                    .collect(),
+            ),
+            Desc::Tuple { ref elems } => Value::List(elems.iter().map(Desc::default_value).collect()),
+            Desc::Optional { .. } => Value::Null,
+            Desc::Documented { ref inner, .. } | Desc::Advanced { ref inner } => inner.default_value(),
+            Desc::WithDefault { ref default, ref inner } => match inner.default_fits(default) {
+                true => (**default).clone(),
+                false => inner.default_value(),
+            },
         }
     }
 }
 
-impl Desc {
-    pub fn kind(&self) -> &str {
-        match self {
-            Desc::Bool => "bool",
-            Desc::Int { .. } => "int",
-            Desc::Float { .. } => "float",
-            Desc::String { .. } => "string",
-            Desc::List { .. } => "list",
-            Desc::Map { .. } => "map",
+/// Returns the descriptor inferred for all of the items, if they agree on one.
+fn common_desc<'a>(items: impl Iterator<Item = &'a Value>) -> Option<Desc> {
+    let mut nullable = false;
+    let mut common: Option<Desc> = None;
+    for item in items {
+        if let Value::Null = item {
+            nullable = true;
+            continue;
+        }
+        let desc = Desc::from_value(item);
+        common = match common {
+            None => Some(desc),
+            Some(common) if common == desc => Some(common),
+            // Strings and colors differ only in flags inferred from the text or alpha, which are united.
+            Some(mut common @ (Desc::String { .. } | Desc::Color { .. })) if common.kind() == desc.kind() => {
+                match (&mut common, &desc) {
+                    (Desc::String { multiline, .. }, Desc::String { multiline: other, .. }) => *multiline |= *other,
+                    (Desc::Color { alpha }, Desc::Color { alpha: other }) => *alpha |= *other,
+                    _ => {}
+                }
+                Some(common)
+            }
+            Some(_) => return None,
+        };
+    }
+
+    match (common, nullable) {
+        (Some(common), true) => Some(Desc::Optional { inner: Box::new(common) }),
+        (common, _) => common,
+    }
+}
+
+/// Returns the descriptor inferred for all of the items, see `Desc::infer`.
+///
+/// Items that are collections are described together by pooling their own items.
+fn pooled_desc(items: Vec<&Value>) -> Option<Desc> {
+    let nullable = items.iter().any(|item| item.is_null());
+    let present: Vec<&Value> = items.iter().copied().filter(|item| !item.is_null()).collect();
+    let first = present.first()?;
+    if present.iter().any(|item| Kind::of(item) != Kind::of(first)) {
+        return None;
+    }
+
+    let desc = match first {
+        Value::List(_) => {
+            let mut desc = Desc::from_value(&Value::List(Vec::new()));
+            if let Desc::List { elem_desc, .. } = &mut desc {
+                let elems = present.iter().filter_map(|item| item.as_list()).flatten().collect();
+                *elem_desc = pooled_desc(elems).map(Box::new);
+            }
+            desc
+        }
+        Value::Map(_) => {
+            let mut desc = Desc::from_value(&Value::Map(IndexMap::new()));
+            if let Desc::Map { value_desc, .. } = &mut desc {
+                let values = present.iter().filter_map(|item| item.as_map()).flat_map(IndexMap::values).collect();
+                *value_desc = pooled_desc(values).map(Box::new);
+            }
+            desc
+        }
+        Value::Enum { .. } => {
+            let mut payloads: IndexMap<&str, Vec<&Value>> = IndexMap::new();
+            for item in &present {
+                if let Value::Enum { variant, value } = item {
+                    payloads.entry(variant.as_str()).or_default().push(value);
+                }
+            }
+            let variants = payloads
+                .into_iter()
+                .map(|(name, payloads)| match payloads.iter().all(|payload| payload.is_null()) {
+                    true => Some(EnumVariant::unit(name)),
+                    false => Some(EnumVariant::new(name, pooled_desc(payloads)?)),
+                })
+                .collect::<Option<_>>()?;
+            Desc::Enum { variants }
         }
+        _ => return common_desc(items.into_iter()),
+    };
+
+    match nullable {
+        true => Some(Desc::Optional { inner: Box::new(desc) }),
+        false => Some(desc),
     }
 }
 
-/// Top-level value.
-#[derive(Clone)]
-pub enum Value {
-    Bool(bool),
-    Int(i64),
-    Float(f64),
-    String(String),
-    List(Vec<Value>),
-    Map(HashMap<String, Value>),
+fn matrix_default(row: usize, col: usize, identity: bool) -> f64 {
+    if identity && row == col {
+        1.0
+    } else {
+        0.0
+    }
 }
 
-impl Value {
-    pub fn kind(&self) -> &str {
-        match self {
-            Value::Bool(_) => "bool",
-            Value::Int(_) => "int",
-            Value::Float(_) => "float",
-            Value::String(_) => "string",
-            Value::List(_) => "list",
-            Value::Map(_) => "map",
+/// One of the allowed values of a string.
+#[derive(Clone, Debug, Default, PartialEq, EguiProbe)]
+pub struct Variant {
+    /// The string value of the variant.
+    pub name: String,
+
+    /// Optional explanation of what the variant means.
+    #[egui_probe(multiline)]
+    pub description: Option<String>,
+
+    /// Optional icon shown before the name, e.g. an emoji.
+    /// Only affects display, the stored value is always the name.
+    pub icon: Option<String>,
+}
+
+impl Variant {
+    pub fn new(name: impl Into<String>) -> Self {
+        Variant {
+            name: name.into(),
+            description: None,
+            icon: None,
         }
     }
 
-    fn has_inner(&self) -> bool {
-        match self {
-            Value::List(elems) => !elems.is_empty(),
-            Value::Map(values) => !values.is_empty(),
-            _ => false,
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Returns the name prefixed with the icon, if any.
+    fn label(&self) -> String {
+        match &self.icon {
+            Some(icon) => format!("{icon} {}", self.name),
+            None => self.name.clone(),
         }
-    
     }
 }
 
-pub struct ValueProbe<'a> {
-    desc: Option<&'a Desc>,
-    mydesc: Desc,
-    myid: Id,
-    value: &'a mut Value,
-    id_source: Id,
+/// Named variant of `Desc::Enum`.
+#[derive(Clone, Debug, Default, PartialEq, EguiProbe)]
+pub struct EnumVariant {
+    pub name: String,
+
+    /// Descriptor of the payload held by the variant.
+    /// Variants without payload hold `Value::Null`.
+    pub desc: Option<Desc>,
+
+    /// Optional explanation of what the variant means.
+    #[egui_probe(multiline)]
+    pub description: Option<String>,
+
+    /// Optional icon shown before the name, e.g. an emoji.
+    /// Only affects display, the stored variant is always the name.
+    pub icon: Option<String>,
 }
 
-impl<'a> ValueProbe<'a> {
-    pub fn new(desc: Option<&'a Desc>, value: &'a mut Value, id_source: impl Hash) -> Self {
-        ValueProbe {
-            desc,
-            mydesc: Desc::Bool,
-            myid: Id::NULL,
-            value,
-            id_source: Id::new(id_source),
+impl EnumVariant {
+    pub fn new(name: impl Into<String>, desc: Desc) -> Self {
+        EnumVariant {
+            name: name.into(),
+            desc: Some(desc),
+            description: None,
+            icon: None,
+        }
+    }
+
+    /// Returns a variant without payload.
+    pub fn unit(name: impl Into<String>) -> Self {
+        EnumVariant {
+            name: name.into(),
+            desc: None,
+            description: None,
+            icon: None,
+        }
+    }
+
+    pub fn with_description(mut self, description: impl Into<String>) -> Self {
+        self.description = Some(description.into());
+        self
+    }
+
+    pub fn with_icon(mut self, icon: impl Into<String>) -> Self {
+        self.icon = Some(icon.into());
+        self
+    }
+
+    /// Returns the name prefixed with the icon, if any.
+    fn label(&self) -> String {
+        match &self.icon {
+            Some(icon) => format!("{icon} {}", self.name),
+            None => self.name.clone(),
+        }
+    }
+
+    /// Returns the default payload of the variant.
+    pub fn default_payload(&self) -> Value {
+        self.desc.as_ref().map_or(Value::Null, Desc::default_value)
+    }
+
+    /// Returns the variant holding its default payload.
+    pub fn default_value(&self) -> Value {
+        Value::Enum {
+            variant: self.name.clone(),
+            value: Box::new(self.default_payload()),
         }
     }
 }
 
-impl EguiProbe for ValueProbe<'_> {
-    fn probe(&mut self, ui: &mut Ui, style: &Style) -> Response {
-        match self.desc {
-            None => {
-                let id = ui.make_persistent_id(self.id_source);
-                self.mydesc = ui
-                    .ctx()
-                    .data(|d| d.get_temp::<Desc>(id))
-                    .unwrap_or_default();
-                let r = self.mydesc.probe(ui, style);
-                ui.ctx()
-                    .data_mut(|d| d.insert_temp(id, self.mydesc.clone()));
-                r
-            }
-            Some(Desc::Bool) => match self.value {
-                Value::Bool(value) => value.probe(ui, style),
-                _ => {
-                    ui.horizontal(|ui| {
-                        ui.strong(format!(
-                            "Expected boolean, but is {} instead",
-                            self.value.kind()
-                        ));
-                        if ui.small_button("Reset to false").clicked() {
-                            *self.value = Value::Bool(false);
-                        }
-                        ui.strong("?");
-                    })
-                    .response
-                }
-            },
-            Some(&Desc::Int { min, max }) => {
-                let reset_to = match (min, max) {
-                    (None, None) => 0,
-                    (Some(min), None) => min.max(0),
-                    (None, Some(max)) => max.min(0),
-                    (Some(min), Some(max)) if min <= max => 0i64.clamp(min, max),
-                    (Some(min), Some(max)) => {
-                        return invalid_range(ui, min, max);
-                    }
-                };
+/// Named field of `Desc::Struct`.
+#[derive(Clone, Debug, Default, PartialEq, EguiProbe)]
+pub struct StructField {
+    pub name: String,
 
-                match self.value {
-                    Value::Int(value) => match (min, max) {
-                        (None, None) => value.probe(ui, style),
-                        (Some(min), None) => {
-                            egui_probe::customize::probe_range(min.., value).probe(ui, style)
-                        }
-                        (None, Some(max)) => {
-                            egui_probe::customize::probe_range(..=max, value).probe(ui, style)
-                        }
-                        (Some(min), Some(max)) => {
-                            egui_probe::customize::probe_range(min..=max, value).probe(ui, style)
-                        }
-                    },
-                    Value::Float(value) => {
-                        let f = *value as i64;
-                        let x = match (min, max) {
-                            (None, None) => f,
-                            (Some(min), None) => min.max(f),
-                            (None, Some(max)) => max.min(f),
-                            (Some(min), Some(max)) => f.clamp(min, max),
-                        };
+    /// Descriptor of the field's value.
+    pub desc: Desc,
+}
 
-                        ui.horizontal(|ui| {
-                            ui.strong(format!(
-                                "Expected integer, but is {} instead",
-                                self.value.kind()
-                            ));
+impl StructField {
+    pub fn new(name: impl Into<String>, desc: Desc) -> Self {
+        StructField { name: name.into(), desc }
+    }
+}
 
-                            if ui.small_button(format!("Convert to {x}")).clicked() {
-                                *self.value = Value::Int(x);
-                            }
+impl From<String> for Variant {
+    #[inline]
+    fn from(name: String) -> Self {
+        Variant::new(name)
+    }
+}
 
-                            ui.strong("?");
-                        })
-                        .response
-                    }
-                    _ => {
-                        ui.horizontal(|ui| {
-                            ui.strong(format!(
-                                "Expected integer, but is {} instead",
-                                self.value.kind()
-                            ));
-                            if ui.small_button(format!("Reset to {reset_to}")).clicked() {
-                                *self.value = Value::Int(reset_to);
-                            }
-                            ui.strong("?");
-                        })
-                        .response
-                    }
-                }
-            }
-            Some(&Desc::Float { min, max }) => {
-                let reset_to = match (min, max) {
-                    (None, None) => 0.0,
-                    (Some(min), None) => min.max(0.0),
-                    (None, Some(max)) => max.min(0.0),
-                    (Some(min), Some(max)) if min <= max => 0f64.clamp(min, max),
-                    (Some(min), Some(max)) => {
-                        return invalid_range(ui, min, max);
-                    }
-                };
+impl From<&str> for Variant {
+    #[inline]
+    fn from(name: &str) -> Self {
+        Variant::new(name)
+    }
+}
 
-                match self.value {
-                    Value::Float(value) => match (min, max) {
-                        (None, None) => value.probe(ui, style),
-                        (Some(min), None) => {
-                            egui_probe::customize::probe_range(min.., value).probe(ui, style)
-                        }
-                        (None, Some(max)) => {
-                            egui_probe::customize::probe_range(..=max, value).probe(ui, style)
-                        }
-                        (Some(min), Some(max)) => {
-                            egui_probe::customize::probe_range(min..=max, value).probe(ui, style)
-                        }
-                    },
-                    Value::Int(value) => {
-                        let f = *value as f64;
-                        let x = match (min, max) {
-                            (None, None) => f,
-                            (Some(min), None) => min.max(f),
-                            (None, Some(max)) => max.min(f),
-                            (Some(min), Some(max)) => f.clamp(min, max),
-                        };
+impl Desc {
+    /// Infers the most specific descriptor of the value, without constraints.
+    ///
+    /// Lists and maps get a shared item descriptor if all their items agree on it,
+    /// where `Null` items make it optional, and no item descriptor otherwise.
+    /// Enums are described with the single variant they hold,
+    /// and `Null` as an optional bool.
+    ///
+    /// See `Desc::infer` for item descriptors that cover more data.
+    pub fn from_value(value: &Value) -> Desc {
+        match value {
+            Value::Bool(_) => Desc::Bool,
+            Value::Int(_) => Desc::Int {
+                min: None,
+                max: None,
+                min_path: None,
+                max_path: None,
+                humanize: None,
+                stepper: None,
+                step: None,
+            },
+            Value::Uint(_) => Desc::Uint { min: None, max: None },
+            Value::Float(_) => Desc::Float {
+                min: None,
+                max: None,
+                min_path: None,
+                max_path: None,
+                stepper: None,
+                step: None,
+            },
+            Value::String(text) => Desc::String {
+                variants: None,
+                trim: false,
+                collapse_whitespace: false,
+                min_len: None,
+                max_len: None,
+                pattern: None,
+                multiline: text.contains('\n'),
+            },
+            Value::Bytes(_) => Desc::Bytes { max_len: None },
+            Value::Color([.., a]) => Desc::Color { alpha: *a != 255 },
+            Value::List(elems) => Desc::List {
+                elem_desc: common_desc(elems.iter()).map(Box::new),
+                empty_hint: None,
+                collapsed_by_default: false,
+                min_len: None,
+                max_len: None,
+                multi_select: false,
+                unique_by: None,
+            },
+            Value::Map(values) => Desc::Map {
+                value_desc: common_desc(values.values()).map(Box::new),
+                tabs: Vec::new(),
+                empty_hint: None,
+                collapsed_by_default: false,
+                max_entries: None,
+                required_keys: Vec::new(),
+                required_if: Vec::new(),
+                strict_keys: false,
+            },
+            Value::Enum { variant, value } => Desc::Enum {
+                variants: vec![match **value {
+                    Value::Null => EnumVariant::unit(variant.clone()),
+                    ref value => EnumVariant::new(variant.clone(), Desc::from_value(value)),
+                }],
+            },
+            Value::Null => Desc::Optional {
+                inner: Box::new(Desc::Bool),
+            },
+        }
+    }
 
-                        ui.horizontal(|ui| {
-                            ui.strong(format!(
-                                "Expected integer, but is {} instead",
-                                self.value.kind()
-                            ));
+    /// Infers a descriptor of the value like `Desc::from_value`,
+    /// but describes items of lists and maps that are collections themselves together.
+    ///
+    /// Nested lists and maps get an item descriptor inferred from the items of all of them,
+    /// so an empty list among them doesn't prevent it,
+    /// and enums get every variant found among the items.
+    /// Items that still disagree, e.g. an int and a string, get no item descriptor.
+    pub fn infer(value: &Value) -> Desc {
+        pooled_desc(vec![value]).unwrap_or_else(|| Desc::from_value(value))
+    }
 
-                            if ui.small_button(format!("Convert to {x:0.1}")).clicked() {
-                                *self.value = Value::Float(x);
-                            }
+    /// Returns the number of value levels described by this descriptor.
+    ///
+    /// Scalars have depth of 1.
+    /// Collections add one level to the depth of their items,
+    /// and items without a descriptor count as one level.
+    pub fn depth(&self) -> usize {
+        match self {
+            Desc::List { elem_desc: inner, .. } | Desc::Map { value_desc: inner, .. } => {
+                1 + inner.as_ref().map_or(1, |inner| inner.depth())
+            }
+            Desc::Matrix { .. } => 2,
+            Desc::Enum { variants } => 1 + variants.iter().map(|v| v.desc.as_ref().map_or(0, Desc::depth)).max().unwrap_or(0),
+            Desc::Struct { fields } => 1 + fields.iter().map(|f| f.desc.depth()).max().unwrap_or(0),
+            Desc::Tuple { elems } => 1 + elems.iter().map(Desc::depth).max().unwrap_or(0),
+            Desc::Optional { inner } | Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner } => inner.depth(),
+            _ => 1,
+        }
+    }
 
-                            ui.strong("?");
-                        })
-                        .response
-                    }
-                    _ => {
-                        ui.horizontal(|ui| {
-                            ui.strong(format!(
-                                "Expected integer, but is {} instead",
-                                self.value.kind()
-                            ));
-                            if ui.small_button(format!("Reset to {reset_to}")).clicked() {
-                                *self.value = Value::Float(reset_to);
-                            }
-                            ui.strong("?");
-                        })
-                        .response
-                    }
+    /// Cuts the descriptor so that its depth does not exceed `max_depth`.
+    ///
+    /// Collections that don't fit are replaced with `Desc::Bool`.
+    /// Returns `true` if the descriptor was changed.
+    fn truncate_depth(&mut self, max_depth: usize) -> bool {
+        match self {
+            Desc::List { .. } | Desc::Map { .. } | Desc::Enum { .. } | Desc::Struct { .. } | Desc::Tuple { .. }
+                if max_depth < 2 =>
+            {
+                *self = Desc::Bool;
+                true
+            }
+            Desc::List { elem_desc: Some(inner), .. } | Desc::Map { value_desc: Some(inner), .. } => {
+                inner.truncate_depth(max_depth - 1)
+            }
+            Desc::Enum { variants } => {
+                let mut changed = false;
+                for desc in variants.iter_mut().filter_map(|variant| variant.desc.as_mut()) {
+                    changed |= desc.truncate_depth(max_depth - 1);
                 }
+                changed
             }
-            Some(&Desc::String { ref variants }) => match self.value {
+            Desc::Struct { fields } => {
+                let mut changed = false;
+                for field in fields {
+                    changed |= field.desc.truncate_depth(max_depth - 1);
+                }
+                changed
+            }
+            Desc::Tuple { elems } => {
+                let mut changed = false;
+                for elem in elems {
+                    changed |= elem.truncate_depth(max_depth - 1);
+                }
+                changed
+            }
+            Desc::Optional { inner } | Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner } => {
+                inner.truncate_depth(max_depth)
+            }
+            _ => false,
+        }
+    }
+
+    /// Returns this descriptor refined by `patch`.
+    ///
+    /// If both are of the same kind, options set in `patch` replace the ones of this descriptor,
+    /// unset options are kept, and nested descriptors are merged recursively.
+    /// Flags have no unset state, so they are always taken from `patch`.
+    /// Help text of `Desc::Documented`, the default of `Desc::WithDefault` and the `Desc::Advanced` mark
+    /// are kept on either side, with the ones of `patch` preferred.
+    /// Otherwise `patch` replaces this descriptor.
+    pub fn merged(&self, patch: &Desc) -> Desc {
+        fn nested(base: &Option<Box<Desc>>, patch: &Option<Box<Desc>>) -> Option<Box<Desc>> {
+            match (base, patch) {
+                (Some(base), Some(patch)) => Some(Box::new(base.merged(patch))),
+                _ => patch.clone().or_else(|| base.clone()),
+            }
+        }
+
+        fn list<T: Clone>(base: &[T], patch: &[T]) -> Vec<T> {
+            if patch.is_empty() { base.to_vec() } else { patch.to_vec() }
+        }
+
+        match (self, patch) {
+            (
+                Desc::Int { min, max, min_path, max_path, humanize, stepper, step },
+                Desc::Int {
+                    min: pmin,
+                    max: pmax,
+                    min_path: pmin_path,
+                    max_path: pmax_path,
+                    humanize: phumanize,
+                    stepper: pstepper,
+                    step: pstep,
+                },
+            ) => Desc::Int {
+                min: pmin.or(*min),
+                max: pmax.or(*max),
+                min_path: pmin_path.clone().or_else(|| min_path.clone()),
+                max_path: pmax_path.clone().or_else(|| max_path.clone()),
+                humanize: phumanize.or(*humanize),
+                stepper: pstepper.or(*stepper),
+                step: pstep.or(*step),
+            },
+            (Desc::Uint { min, max }, Desc::Uint { min: pmin, max: pmax }) => Desc::Uint {
+                min: pmin.or(*min),
+                max: pmax.or(*max),
+            },
+            (
+                Desc::Float { min, max, min_path, max_path, stepper, step },
+                Desc::Float {
+                    min: pmin,
+                    max: pmax,
+                    min_path: pmin_path,
+                    max_path: pmax_path,
+                    stepper: pstepper,
+                    step: pstep,
+                },
+            ) => Desc::Float {
+                min: pmin.or(*min),
+                max: pmax.or(*max),
+                min_path: pmin_path.clone().or_else(|| min_path.clone()),
+                max_path: pmax_path.clone().or_else(|| max_path.clone()),
+                stepper: pstepper.or(*stepper),
+                step: pstep.or(*step),
+            },
+            (
+                Desc::String { variants, min_len, max_len, pattern, .. },
+                Desc::String {
+                    variants: pvariants,
+                    trim: ptrim,
+                    collapse_whitespace: pcollapse,
+                    min_len: pmin_len,
+                    max_len: pmax_len,
+                    pattern: ppattern,
+                    multiline: pmultiline,
+                },
+            ) => Desc::String {
+                variants: pvariants.clone().or_else(|| variants.clone()),
+                trim: *ptrim,
+                collapse_whitespace: *pcollapse,
+                min_len: pmin_len.or(*min_len),
+                max_len: pmax_len.or(*max_len),
+                pattern: ppattern.clone().or_else(|| pattern.clone()),
+                multiline: *pmultiline,
+            },
+            (Desc::Bytes { max_len }, Desc::Bytes { max_len: pmax_len }) => Desc::Bytes {
+                max_len: pmax_len.or(*max_len),
+            },
+            (Desc::Color { .. }, Desc::Color { alpha }) => Desc::Color { alpha: *alpha },
+            (
+                Desc::List { elem_desc, empty_hint, min_len, max_len, unique_by, .. },
+                Desc::List {
+                    elem_desc: pelem,
+                    empty_hint: phint,
+                    collapsed_by_default: pcollapsed,
+                    min_len: pmin,
+                    max_len: pmax,
+                    multi_select: pmulti,
+                    unique_by: punique,
+                },
+            ) => Desc::List {
+                elem_desc: nested(elem_desc, pelem),
+                empty_hint: phint.clone().or_else(|| empty_hint.clone()),
+                collapsed_by_default: *pcollapsed,
+                min_len: pmin.or(*min_len),
+                max_len: pmax.or(*max_len),
+                multi_select: *pmulti,
+                unique_by: punique.clone().or_else(|| unique_by.clone()),
+            },
+            (
+                Desc::Map {
+                    value_desc,
+                    tabs,
+                    empty_hint,
+                    max_entries,
+                    required_keys,
+                    required_if,
+                    ..
+                },
+                Desc::Map {
+                    value_desc: pvalue,
+                    tabs: ptabs,
+                    empty_hint: phint,
+                    collapsed_by_default: pcollapsed,
+                    max_entries: pmax,
+                    required_keys: prequired,
+                    required_if: prequired_if,
+                    strict_keys: pstrict,
+                },
+            ) => Desc::Map {
+                value_desc: nested(value_desc, pvalue),
+                tabs: list(tabs, ptabs),
+                empty_hint: phint.clone().or_else(|| empty_hint.clone()),
+                collapsed_by_default: *pcollapsed,
+                max_entries: pmax.or(*max_entries),
+                required_keys: list(required_keys, prequired),
+                required_if: list(required_if, prequired_if),
+                strict_keys: *pstrict,
+            },
+            (Desc::Enum { variants }, Desc::Enum { variants: pvariants }) => Desc::Enum {
+                variants: list(variants, pvariants),
+            },
+            (Desc::Struct { fields }, Desc::Struct { fields: pfields }) => Desc::Struct {
+                fields: list(fields, pfields),
+            },
+            (Desc::Tuple { elems }, Desc::Tuple { elems: pelems }) => Desc::Tuple {
+                elems: list(elems, pelems),
+            },
+            (Desc::Optional { inner }, Desc::Optional { inner: pinner }) => Desc::Optional {
+                inner: Box::new(inner.merged(pinner)),
+            },
+            (Desc::Documented { inner, .. }, Desc::Documented { doc, inner: pinner }) => Desc::Documented {
+                doc: doc.clone(),
+                inner: Box::new(inner.merged(pinner)),
+            },
+            (Desc::Documented { doc, inner }, patch) => Desc::Documented {
+                doc: doc.clone(),
+                inner: Box::new(inner.merged(patch)),
+            },
+            (base, Desc::Documented { doc, inner: pinner }) => Desc::Documented {
+                doc: doc.clone(),
+                inner: Box::new(base.merged(pinner)),
+            },
+            (Desc::WithDefault { inner, .. }, Desc::WithDefault { default, inner: pinner }) => Desc::WithDefault {
+                default: default.clone(),
+                inner: Box::new(inner.merged(pinner)),
+            },
+            (Desc::WithDefault { default, inner }, patch) => Desc::WithDefault {
+                default: default.clone(),
+                inner: Box::new(inner.merged(patch)),
+            },
+            (base, Desc::WithDefault { default, inner: pinner }) => Desc::WithDefault {
+                default: default.clone(),
+                inner: Box::new(base.merged(pinner)),
+            },
+            (Desc::Advanced { inner }, Desc::Advanced { inner: pinner }) => Desc::Advanced {
+                inner: Box::new(inner.merged(pinner)),
+            },
+            (Desc::Advanced { inner }, patch) => Desc::Advanced {
+                inner: Box::new(inner.merged(patch)),
+            },
+            (base, Desc::Advanced { inner: pinner }) => Desc::Advanced {
+                inner: Box::new(base.merged(pinner)),
+            },
+            _ => patch.clone(),
+        }
+    }
+
+    /// Returns human-readable summary of the descriptor,
+    /// e.g. `int, 0..=100` or `list of (string, one of a, b)`.
+    pub fn summary(&self) -> String {
+        fn bound<T: Display>(value: &Option<T>, path: &Option<String>) -> Option<String> {
+            match path {
+                Some(path) => Some(format!("`{path}`")),
+                None => value.as_ref().map(T::to_string),
+            }
+        }
+
+        fn range<T: Display>(
+            kind: &str,
+            min: &Option<T>,
+            max: &Option<T>,
+            min_path: &Option<String>,
+            max_path: &Option<String>,
+        ) -> String {
+            match (bound(min, min_path), bound(max, max_path)) {
+                (None, None) => kind.to_owned(),
+                (Some(min), None) => format!("{kind}, {min}.."),
+                (None, Some(max)) => format!("{kind}, ..={max}"),
+                (Some(min), Some(max)) => format!("{kind}, {min}..={max}"),
+            }
+        }
+
+        fn nested(desc: &Option<Box<Desc>>) -> String {
+            match desc {
+                None => "any".to_owned(),
+                Some(desc) => match **desc {
+                    Desc::Bool => desc.summary(),
+                    _ => format!("({})", desc.summary()),
+                },
+            }
+        }
+
+        match self {
+            Desc::Bool => "bool".to_owned(),
+            Desc::Int { min, max, min_path, max_path, humanize, .. } => {
+                let range = range("int", min, max, min_path, max_path);
+                match humanize {
+                    None => range,
+                    Some(HumanizeKind::Bytes) => format!("{range}, bytes"),
+                    Some(HumanizeKind::Millis) => format!("{range}, milliseconds"),
+                    Some(HumanizeKind::Seconds) => format!("{range}, seconds"),
+                }
+            }
+            Desc::Uint { min, max } => range("uint", min, max, &None, &None),
+            Desc::Float { min, max, min_path, max_path, .. } => range("float", min, max, min_path, max_path),
+            Desc::String { variants: None, min_len, max_len, .. } => match (min_len, max_len) {
+                (None, None) => "string".to_owned(),
+                _ => format!("{} chars", range("string", min_len, max_len, &None, &None)),
+            },
+            Desc::String { variants: Some(variants), .. } => {
+                let names: Vec<&str> = variants.iter().map(|v| v.name.as_str()).collect();
+                format!("string, one of {}", names.join(", "))
+            }
+            Desc::Bytes { max_len: None } => "bytes".to_owned(),
+            Desc::Bytes { max_len: Some(max) } => format!("bytes, at most {max}"),
+            Desc::Color { alpha: false } => "color".to_owned(),
+            Desc::Color { alpha: true } => "color with alpha".to_owned(),
+            Desc::List { elem_desc, .. } => format!("list of {}", nested(elem_desc)),
+            Desc::Map { value_desc, .. } => format!("map of {}", nested(value_desc)),
+            Desc::Matrix { rows, cols, min, max, .. } => range(&format!("matrix {rows}x{cols}"), min, max, &None, &None),
+            Desc::Enum { variants } => {
+                let variants: Vec<String> = variants
+                    .iter()
+                    .map(|v| match &v.desc {
+                        Some(desc) => format!("{} ({})", v.name, desc.summary()),
+                        None => v.name.clone(),
+                    })
+                    .collect();
+                format!("one of {}", variants.join(", "))
+            }
+            Desc::Struct { fields } => {
+                let fields: Vec<String> = fields
+                    .iter()
+                    .map(|f| format!("{} ({})", f.name, f.desc.summary()))
+                    .collect();
+                format!("struct of {}", fields.join(", "))
+            }
+            Desc::Tuple { elems } => {
+                let elems: Vec<String> = elems.iter().map(Desc::summary).collect();
+                format!("tuple of ({})", elems.join(", "))
+            }
+            Desc::Optional { inner } => format!("optional {}", inner.summary()),
+            Desc::Documented { inner, .. } => inner.summary(),
+            Desc::WithDefault { default, inner } => format!("{}, default {}", inner.summary(), diff::preview(default)),
+            Desc::Advanced { inner } => format!("advanced {}", inner.summary()),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            Desc::Bool => "bool",
+            Desc::Int { .. } => "int",
+            Desc::Uint { .. } => "uint",
+            Desc::Float { .. } => "float",
+            Desc::String { .. } => "string",
+            Desc::Bytes { .. } => "bytes",
+            Desc::Color { .. } => "color",
+            Desc::List { .. } => "list",
+            Desc::Map { .. } => "map",
+            Desc::Matrix { .. } => "matrix",
+            Desc::Enum { .. } => "enum",
+            Desc::Struct { .. } => "struct",
+            Desc::Tuple { .. } => "tuple",
+            Desc::Optional { .. } => "optional",
+            Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner } => inner.kind(),
+        }
+    }
+
+    /// Returns the descriptor under the `Desc::Documented`, `Desc::WithDefault` and `Desc::Advanced` wrappers.
+    pub(crate) fn unwrapped(&self) -> &Desc {
+        match self {
+            Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner } => inner.unwrapped(),
+            desc => desc,
+        }
+    }
+
+    /// Wraps the descriptor so that `default` is its default value.
+    ///
+    /// Fails if `default` is of another kind than the values of this descriptor.
+    /// `Value::Null` is accepted only for `Desc::Optional`.
+    pub fn with_default(self, default: Value) -> Result<Desc, ConvertError> {
+        if !self.default_fits(&default) {
+            return Err(ConvertError::Unsupported {
+                from: Kind::of(&default),
+                to: Kind::of(&self.default_value()),
+            });
+        }
+        Ok(Desc::WithDefault {
+            default: Box::new(default),
+            inner: Box::new(self),
+        })
+    }
+
+    /// Returns `true` if `default` is of the kind of the values of this descriptor.
+    fn default_fits(&self, default: &Value) -> bool {
+        match self {
+            Desc::Optional { inner } => default.is_null() || inner.default_fits(default),
+            Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner } => {
+                inner.default_fits(default)
+            }
+            desc => Kind::of(&desc.default_value()) == Kind::of(default),
+        }
+    }
+
+    /// Wraps the descriptor into `Desc::Advanced`,
+    /// hiding its values unless "Show advanced" is checked.
+    pub fn advanced(self) -> Desc {
+        Desc::Advanced { inner: Box::new(self) }
+    }
+
+    /// Returns `true` if the descriptor is `Desc::Advanced`,
+    /// possibly under `Desc::Documented` or `Desc::WithDefault`.
+    pub fn is_advanced(&self) -> bool {
+        match self {
+            Desc::Advanced { .. } => true,
+            Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } => inner.is_advanced(),
+            _ => false,
+        }
+    }
+
+    /// Returns `true` if values held by this struct, tuple, list or map may be advanced,
+    /// that is whether it needs the "Show advanced" toggle.
+    fn holds_advanced(&self) -> bool {
+        match self {
+            Desc::Struct { fields } => fields.iter().any(|field| field.desc.is_advanced()),
+            Desc::Tuple { elems } => elems.iter().any(Desc::is_advanced),
+            Desc::List { elem_desc: Some(inner), .. } | Desc::Map { value_desc: Some(inner), .. } => inner.is_advanced(),
+            Desc::Optional { inner }
+            | Desc::Documented { inner, .. }
+            | Desc::WithDefault { inner, .. }
+            | Desc::Advanced { inner } => inner.holds_advanced(),
+            _ => false,
+        }
+    }
+}
+
+/// Named group of map keys shown as a tab.
+#[derive(Clone, Debug, Default, PartialEq, EguiProbe)]
+pub struct Tab {
+    /// The title of the tab.
+    pub name: String,
+
+    /// Keys of the map entries shown in the tab.
+    pub keys: Vec<String>,
+}
+
+impl Tab {
+    pub fn new(name: impl Into<String>, keys: impl IntoIterator<Item = impl Into<String>>) -> Self {
+        Tab {
+            name: name.into(),
+            keys: keys.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+/// Top-level value.
+///
+/// Values are equal if they are of the same kind and their contents are equal.
+/// Maps are compared entry by entry regardless of order,
+/// floats follow IEEE semantics, so `NaN` is not equal to itself
+/// and `Int(1)` is not equal to `Float(1.0)`.
+#[derive(Clone, Default, PartialEq)]
+pub enum Value {
+    Bool(bool),
+    Int(i64),
+    Uint(u64),
+    Float(f64),
+    String(String),
+    Bytes(Vec<u8>),
+
+    /// Unmultiplied sRGB color with alpha.
+    Color([u8; 4]),
+
+    List(Vec<Value>),
+
+    /// Map entries keep the order they were inserted in and are shown in that order.
+    Map(IndexMap<String, Value>),
+
+    /// Selected variant of `Desc::Enum` and its value.
+    Enum { variant: String, value: Box<Value> },
+
+    /// Absent value of `Desc::Optional`.
+    #[default]
+    Null,
+}
+
+/// Formats values like a derived `Debug`, e.g. `Map({"a": Bool(true)})`,
+/// but with map entries sorted by key, so equal maps with different order print the same.
+impl fmt::Debug for Value {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Value::Bool(value) => f.debug_tuple("Bool").field(value).finish(),
+            Value::Int(value) => f.debug_tuple("Int").field(value).finish(),
+            Value::Uint(value) => f.debug_tuple("Uint").field(value).finish(),
+            Value::Float(value) => f.debug_tuple("Float").field(value).finish(),
+            Value::String(value) => f.debug_tuple("String").field(value).finish(),
+            Value::Bytes(bytes) => f.debug_tuple("Bytes").field(bytes).finish(),
+            Value::Color(rgba) => f.debug_tuple("Color").field(rgba).finish(),
+            Value::List(elems) => f.debug_tuple("List").field(elems).finish(),
+            Value::Map(values) => f.debug_tuple("Map").field(&SortedMap(values)).finish(),
+            Value::Enum { variant, value } => f
+                .debug_struct("Enum")
+                .field("variant", variant)
+                .field("value", value)
+                .finish(),
+            Value::Null => f.write_str("Null"),
+        }
+    }
+}
+
+/// Formats map entries in key order.
+struct SortedMap<'a>(&'a IndexMap<String, Value>);
+
+impl fmt::Debug for SortedMap<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut entries: Vec<_> = self.0.iter().collect();
+        entries.sort_by(|a, b| a.0.cmp(b.0));
+        f.debug_map().entries(entries).finish()
+    }
+}
+
+impl From<bool> for Value {
+    #[inline]
+    fn from(value: bool) -> Self {
+        Value::Bool(value)
+    }
+}
+
+impl From<i64> for Value {
+    #[inline]
+    fn from(value: i64) -> Self {
+        Value::Int(value)
+    }
+}
+
+/// Makes integer literals work, e.g. `Value::from(42)`.
+impl From<i32> for Value {
+    #[inline]
+    fn from(value: i32) -> Self {
+        Value::Int(value.into())
+    }
+}
+
+impl From<u64> for Value {
+    #[inline]
+    fn from(value: u64) -> Self {
+        Value::Uint(value)
+    }
+}
+
+impl From<f64> for Value {
+    #[inline]
+    fn from(value: f64) -> Self {
+        Value::Float(value)
+    }
+}
+
+impl From<String> for Value {
+    #[inline]
+    fn from(value: String) -> Self {
+        Value::String(value)
+    }
+}
+
+impl From<&str> for Value {
+    #[inline]
+    fn from(value: &str) -> Self {
+        Value::from(value.to_owned())
+    }
+}
+
+impl From<Vec<Value>> for Value {
+    #[inline]
+    fn from(elems: Vec<Value>) -> Self {
+        Value::List(elems)
+    }
+}
+
+/// Collects entries into a `Value::Map`, keeping their order.
+/// Later entries replace earlier ones with the same key.
+impl FromIterator<(String, Value)> for Value {
+    fn from_iter<I: IntoIterator<Item = (String, Value)>>(iter: I) -> Self {
+        Value::Map(iter.into_iter().collect())
+    }
+}
+
+impl Value {
+    pub fn kind(&self) -> &'static str {
+        Kind::of(self).name()
+    }
+
+    /// Returns the boolean if the value is `Value::Bool`.
+    pub fn as_bool(&self) -> Option<bool> {
+        match *self {
+            Value::Bool(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer if the value is `Value::Int`.
+    pub fn as_int(&self) -> Option<i64> {
+        match *self {
+            Value::Int(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the integer if the value is `Value::Uint`.
+    pub fn as_uint(&self) -> Option<u64> {
+        match *self {
+            Value::Uint(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the number if the value is `Value::Float`.
+    ///
+    /// Integers are not converted, see `Value::convert` for that.
+    pub fn as_float(&self) -> Option<f64> {
+        match *self {
+            Value::Float(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if the value is `Value::String`.
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the string if the value is `Value::String`.
+    pub fn as_string_mut(&mut self) -> Option<&mut String> {
+        match self {
+            Value::String(value) => Some(value),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes if the value is `Value::Bytes`.
+    pub fn as_bytes(&self) -> Option<&[u8]> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns the bytes if the value is `Value::Bytes`.
+    pub fn as_bytes_mut(&mut self) -> Option<&mut Vec<u8>> {
+        match self {
+            Value::Bytes(bytes) => Some(bytes),
+            _ => None,
+        }
+    }
+
+    /// Returns the color if the value is `Value::Color`.
+    pub fn as_color(&self) -> Option<[u8; 4]> {
+        match *self {
+            Value::Color(rgba) => Some(rgba),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements if the value is `Value::List`.
+    pub fn as_list(&self) -> Option<&[Value]> {
+        match self {
+            Value::List(elems) => Some(elems),
+            _ => None,
+        }
+    }
+
+    /// Returns the elements if the value is `Value::List`.
+    pub fn as_list_mut(&mut self) -> Option<&mut Vec<Value>> {
+        match self {
+            Value::List(elems) => Some(elems),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries if the value is `Value::Map`.
+    pub fn as_map(&self) -> Option<&IndexMap<String, Value>> {
+        match self {
+            Value::Map(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the entries if the value is `Value::Map`.
+    pub fn as_map_mut(&mut self) -> Option<&mut IndexMap<String, Value>> {
+        match self {
+            Value::Map(values) => Some(values),
+            _ => None,
+        }
+    }
+
+    /// Returns the variant name and its value if the value is `Value::Enum`.
+    pub fn as_enum(&self) -> Option<(&str, &Value)> {
+        match self {
+            Value::Enum { variant, value } => Some((variant, value)),
+            _ => None,
+        }
+    }
+
+    /// Returns `true` if the value is `Value::Null`.
+    pub fn is_null(&self) -> bool {
+        matches!(self, Value::Null)
+    }
+
+    /// Returns the nested value at `path`.
+    ///
+    /// Keys select map entries or the value of the enum variant with that name,
+    /// and indices select list elements.
+    /// Returns `None` if a step is missing or doesn't fit the kind of the value it is applied to.
+    pub fn get_path(&self, path: &[PathSeg]) -> Option<&Value> {
+        path.iter().try_fold(self, |value, segment| match (segment, value) {
+            (PathSeg::Key(key), Value::Map(values)) => values.get(key),
+            (PathSeg::Key(key), Value::Enum { variant, value }) => (variant == key).then_some(&**value),
+            (PathSeg::Index(idx), Value::List(elems)) => elems.get(*idx),
+            _ => None,
+        })
+    }
+
+    /// Returns the nested value at `path` for modification, see `Value::get_path`.
+    pub fn get_path_mut(&mut self, path: &[PathSeg]) -> Option<&mut Value> {
+        path.iter().try_fold(self, |value, segment| match (segment, value) {
+            (PathSeg::Key(key), Value::Map(values)) => values.get_mut(key),
+            (PathSeg::Key(key), Value::Enum { variant, value }) => (variant == key).then_some(&mut **value),
+            (PathSeg::Index(idx), Value::List(elems)) => elems.get_mut(*idx),
+            _ => None,
+        })
+    }
+
+    /// Returns the number of values in this document, including itself.
+    pub fn node_count(&self) -> usize {
+        match self {
+            Value::List(elems) => 1 + elems.iter().map(Value::node_count).sum::<usize>(),
+            Value::Map(values) => 1 + values.values().map(Value::node_count).sum::<usize>(),
+            Value::Enum { value, .. } => 1 + value.node_count(),
+            _ => 1,
+        }
+    }
+
+    /// Returns approximate size of the document data in bytes.
+    ///
+    /// Counts one byte for booleans, four bytes for colors, eight bytes for numbers
+    /// and the length of strings, byte blobs and map keys.
+    /// Container overhead is not counted.
+    pub fn approx_size(&self) -> usize {
+        match self {
+            Value::Bool(_) => 1,
+            Value::Int(_) | Value::Uint(_) | Value::Float(_) => 8,
+            Value::String(value) => value.len(),
+            Value::Bytes(bytes) => bytes.len(),
+            Value::Color(_) => 4,
+            Value::List(elems) => elems.iter().map(Value::approx_size).sum(),
+            Value::Map(values) => values
+                .iter()
+                .map(|(key, value)| key.len() + value.approx_size())
+                .sum(),
+            Value::Enum { variant, value } => variant.len() + value.approx_size(),
+            Value::Null => 0,
+        }
+    }
+
+    /// Counts items of a list or values of a map by their kind.
+    ///
+    /// Keys are the strings returned by `Value::kind`.
+    /// Returns an empty map for scalar values.
+    pub fn type_histogram(&self) -> HashMap<&'static str, usize> {
+        let mut histogram = HashMap::new();
+        let mut count = |value: &Value| *histogram.entry(value.kind()).or_insert(0) += 1;
+
+        match self {
+            Value::List(elems) => elems.iter().for_each(&mut count),
+            Value::Map(values) => values.values().for_each(&mut count),
+            _ => {}
+        }
+
+        histogram
+    }
+
+    /// Removes nested values for which `keep` returns `false`.
+    ///
+    /// `keep` receives the path of each nested value and the value itself.
+    /// Containers are visited depth-first: their items are pruned first
+    /// and only then the container itself is passed to `keep`,
+    /// so a predicate may drop containers that became empty.
+    /// List indices in paths refer to positions before pruning.
+    /// The value itself is never removed, only its items.
+    pub fn retain_matching(&mut self, keep: &dyn Fn(&str, &Value) -> bool) {
+        self.retain_matching_at("", keep);
+    }
+
+    fn retain_matching_at(&mut self, path: &str, keep: &dyn Fn(&str, &Value) -> bool) {
+        match self {
+            Value::List(elems) => {
+                let mut idx = 0;
+                elems.retain_mut(|elem| {
+                    let path = path::join(path, &format!("[{idx}]"));
+                    idx += 1;
+                    elem.retain_matching_at(&path, keep);
+                    keep(&path, elem)
+                });
+            }
+            Value::Map(values) => {
+                values.retain(|key, value| {
+                    let path = path::join(path, key);
+                    value.retain_matching_at(&path, keep);
+                    keep(&path, value)
+                });
+            }
+            _ => {}
+        }
+    }
+
+    /// Describes the data that would be lost if the value was reset
+    /// to a value of another kind.
+    ///
+    /// Returns `None` if the value holds nothing worth keeping,
+    /// like `false`, zero or an empty string or collection.
+    fn data_lost_on_reset(&self) -> Option<String> {
+        match self {
+            Value::Bool(false) | Value::Int(0) | Value::Uint(0) => None,
+            Value::Float(value) if *value == 0.0 => None,
+            Value::String(value) if value.is_empty() => None,
+            Value::Bytes(bytes) if bytes.is_empty() => None,
+            Value::List(elems) if elems.is_empty() => None,
+            Value::Map(values) if values.is_empty() => None,
+            Value::Null => None,
+            Value::Enum { variant, .. } => Some(format!("variant `{variant}`")),
+            Value::Bool(value) => Some(format!("bool `{value}`")),
+            Value::Int(value) => Some(format!("int `{value}`")),
+            Value::Uint(value) => Some(format!("uint `{value}`")),
+            Value::Float(value) => Some(format!("float `{value}`")),
+            Value::String(value) => Some(format!("string {value:?}")),
+            Value::Bytes(bytes) => Some(format!("{} bytes", bytes.len())),
+            Value::Color(rgba) => Some(format!("color `{}`", color::format(*rgba))),
+            Value::List(elems) => Some(format!("list with {} elements", elems.len())),
+            Value::Map(values) => Some(format!("map with {} entries", values.len())),
+        }
+    }
+
+    fn has_inner(&self) -> bool {
+        match self {
+            Value::List(elems) => !elems.is_empty(),
+            Value::Map(values) => !values.is_empty(),
+            _ => false,
+        }
+    
+    }
+}
+
+pub struct ValueProbe<'a> {
+    desc: Option<&'a Desc>,
+    mydesc: Desc,
+    myid: Id,
+    value: &'a mut Value,
+    id_source: Id,
+    path: String,
+    depth: usize,
+    inherited: Inherited<'a>,
+}
+
+/// Hook called for items added by the user, see `ValueProbe::with_on_add`.
+type OnAdd<'a> = &'a dyn Fn(&str, &mut Value);
+
+/// Background color of values by path, see `ValueProbe::with_style_for`.
+type StyleFor<'a> = &'a dyn Fn(&str) -> Option<Color32>;
+
+/// Hook called for coerced values, see `ValueProbe::with_on_coerce`.
+type OnCoerce<'a> = &'a mut dyn FnMut(CoercionEvent);
+
+/// State passed down from a probe to probes of nested values.
+///
+/// Not `Copy` because of the `on_coerce` hook, use `Inherited::reborrow` to pass it down.
+struct Inherited<'a> {
+    root: Id,
+    config: &'a ProbeConfig,
+    visible: Option<&'a dyn Fn(&str) -> bool>,
+    on_add: Option<OnAdd<'a>>,
+    style_for: Option<StyleFor<'a>>,
+    on_coerce: Option<OnCoerce<'a>>,
+    overrides: Option<&'a DescOverrides>,
+    size_limit_reached: bool,
+    read_only: bool,
+}
+
+impl Inherited<'_> {
+    /// Returns a copy of the state borrowing the `on_coerce` hook for a shorter lifetime.
+    fn reborrow(&mut self) -> Inherited<'_> {
+        Inherited {
+            root: self.root,
+            config: self.config,
+            visible: self.visible,
+            on_add: self.on_add,
+            style_for: self.style_for,
+            on_coerce: match &mut self.on_coerce {
+                Some(on_coerce) => Some(&mut **on_coerce),
+                None => None,
+            },
+            overrides: self.overrides,
+            size_limit_reached: self.size_limit_reached,
+            read_only: self.read_only,
+        }
+    }
+
+    fn can_add(&self) -> bool {
+        !self.size_limit_reached
+    }
+
+    /// Returns `true` if items of lists and maps can't be added, removed, moved or renamed.
+    fn structure_locked(&self) -> bool {
+        self.config.structure_locked || self.read_only
+    }
+
+    fn is_visible(&self, path: &str) -> bool {
+        self.visible.is_none_or(|visible| visible(path))
+    }
+
+    /// Reports a coercion of the value at `path` to the `on_coerce` hook.
+    fn coerced(&mut self, path: &str, attempted: String, reason: CoercionReason) {
+        if let Some(on_coerce) = &mut self.on_coerce {
+            on_coerce(CoercionEvent {
+                path: path.to_owned(),
+                attempted,
+                reason,
+            });
+        }
+    }
+
+    /// Creates an item added by the user to the collection at `path`.
+    fn new_item(&self, path: &str, desc: &Desc) -> Value {
+        self.copied_item(path, &desc.default_value())
+    }
+
+    /// Creates a copy of `item` added by the user to the collection at `path`.
+    fn copied_item(&self, path: &str, item: &Value) -> Value {
+        let mut value = item.clone();
+        if let Some(on_add) = self.on_add {
+            on_add(path, &mut value);
+        }
+        value
+    }
+}
+
+impl<'a> ValueProbe<'a> {
+    pub fn new(desc: Option<&'a Desc>, value: &'a mut Value, id_source: impl Hash) -> Self {
+        let id_source = Id::new(id_source);
+        ValueProbe {
+            desc,
+            mydesc: Desc::Bool,
+            myid: Id::NULL,
+            value,
+            id_source,
+            path: String::new(),
+            depth: 0,
+            inherited: Inherited {
+                root: id_source,
+                config: &ProbeConfig::DEFAULT,
+                visible: None,
+                on_add: None,
+                style_for: None,
+                on_coerce: None,
+                overrides: None,
+                size_limit_reached: false,
+                read_only: false,
+            },
+        }
+    }
+
+    fn nested(
+        desc: Option<&'a Desc>,
+        value: &'a mut Value,
+        id_source: Id,
+        path: String,
+        depth: usize,
+        inherited: Inherited<'a>,
+    ) -> Self {
+        let desc = inherited.overrides.and_then(|o| o.get(&path)).or(desc);
+
+        ValueProbe {
+            desc,
+            mydesc: Desc::Bool,
+            myid: Id::NULL,
+            value,
+            id_source,
+            path,
+            depth,
+            inherited,
+        }
+    }
+
+    /// Returns errors shown by this probe and its nested values in the current frame.
+    ///
+    /// Call after the probe is shown.
+    pub fn errors(&self, ctx: &egui::Context) -> Vec<ProbeError> {
+        error::shown(ctx, self.inherited.root)
+    }
+
+    /// Use the given configuration for this probe and all nested values.
+    pub fn with_config(mut self, config: &'a ProbeConfig) -> Self {
+        self.inherited.config = config;
+        self
+    }
+
+    /// Hide nested values for which `visible` returns `false`.
+    ///
+    /// The predicate receives the path of the value,
+    /// e.g. `servers[0].address`.
+    /// Hidden values and their subtrees are not shown but stay in the value untouched.
+    pub fn with_visibility(mut self, visible: &'a dyn Fn(&str) -> bool) -> Self {
+        self.inherited.visible = Some(visible);
+        self
+    }
+
+    /// Show values at the paths of `overrides` with the overriding descriptors.
+    ///
+    /// Use `DescOverrides::set` to add overrides, which also coerces the affected values.
+    pub fn with_overrides(mut self, overrides: &'a DescOverrides) -> Self {
+        if let Some(desc) = overrides.get(&self.path) {
+            self.desc = Some(desc);
+        }
+        self.inherited.overrides = Some(overrides);
+        self
+    }
+
+    /// Tint the background of values for which `style_for` returns a color.
+    ///
+    /// The hook receives the path of the value, e.g. `servers[0].address`,
+    /// and can be used to highlight recent changes or search matches.
+    pub fn with_style_for(mut self, style_for: &'a dyn Fn(&str) -> Option<Color32>) -> Self {
+        self.inherited.style_for = Some(style_for);
+        self
+    }
+
+    /// Call `on_coerce` whenever a value is converted, clamped or reset to match its descriptor,
+    /// or entered text is rejected.
+    ///
+    /// The hook can be used to collect how often values don't match the schema.
+    pub fn with_on_coerce(mut self, on_coerce: &'a mut dyn FnMut(CoercionEvent)) -> Self {
+        self.inherited.on_coerce = Some(on_coerce);
+        self
+    }
+
+    /// Call `on_add` for every item the user adds to a list or a map,
+    /// before the item is shown.
+    ///
+    /// The hook receives the path of the collection and the new item
+    /// created from the item descriptor, and may modify the item, e.g. assign a unique id.
+    pub fn with_on_add(mut self, on_add: &'a dyn Fn(&str, &mut Value)) -> Self {
+        self.inherited.on_add = Some(on_add);
+        self
+    }
+
+    /// Show this value and all nested values without letting the user change them.
+    ///
+    /// Widgets are disabled, and buttons that add, remove, move, rename, reset or fix values are hidden.
+    /// Missing struct fields and list elements are not filled in, and empty hints are not shown.
+    pub fn read_only(mut self, yes: bool) -> Self {
+        self.inherited.read_only = yes;
+        self
+    }
+}
+
+impl ValueProbe<'_> {
+    /// Shows the widget of this value alone, without nested values,
+    /// so that it can be placed in a custom layout.
+    ///
+    /// Scalars get the same widget and constraints as in the tree.
+    /// Without a descriptor the widget is chosen by the kind of the value.
+    /// Lists and maps have no widget of their own and are shown as a placeholder.
+    pub fn probe_leaf(&mut self, ui: &mut Ui, style: &Style) -> Response {
+        ui.add_enabled_ui(!self.inherited.read_only, |ui| self.leaf_value(ui, style)).inner
+    }
+
+    fn leaf_value(&mut self, ui: &mut Ui, style: &Style) -> Response {
+        let desc = match self.desc.map(Desc::unwrapped) {
+            Some(Desc::List { .. } | Desc::Map { .. } | Desc::Enum { .. } | Desc::Struct { .. } | Desc::Tuple { .. }) => None,
+            Some(_) => return self.probe_value(ui, style),
+            None => match &*self.value {
+                Value::List(_) | Value::Map(_) | Value::Enum { .. } | Value::Null => None,
+                value => Some(Desc::from_value(value)),
+            },
+        };
+
+        match desc {
+            None => ui.weak(format!("<{}>", self.value.kind())),
+            Some(desc) => ValueProbe::nested(
+                Some(&desc),
+                self.value,
+                self.id_source,
+                self.path.clone(),
+                self.depth,
+                self.inherited.reborrow(),
+            )
+            .probe_value(ui, style),
+        }
+    }
+
+    /// Prepares per-frame state that depends on the whole document.
+    /// Called before the root value is shown.
+    fn prepare_root(&mut self, ui: &mut Ui) {
+        error::clear(ui.ctx(), self.inherited.root);
+        outline::start_scroll(ui, self.inherited.root);
+        if let Some(desc) = self.desc {
+            bounds::resolve_all(ui, self.inherited.root, desc, self.value);
+            condition::resolve_all(ui, self.inherited.root, desc, self.value);
+        }
+
+        self.inherited.size_limit_reached = match self.inherited.config.size_limit {
+            None => false,
+            Some(SizeLimit::Nodes(max)) => self.value.node_count() >= max,
+            Some(SizeLimit::Bytes(max)) => self.value.approx_size() >= max,
+        };
+    }
+
+    /// Returns the probe of the present value of `Desc::Optional`,
+    /// shown in place of this probe.
+    fn optional_inner<'b>(&'b mut self, inner: &'b Desc) -> ValueProbe<'b> {
+        let id_source = self.id_source.with("Some");
+        self.wrapped_inner(inner, id_source)
+    }
+
+    /// Returns the probe of the value described by `inner` of a wrapping descriptor,
+    /// shown in place of this probe.
+    fn wrapped_inner<'b>(&'b mut self, inner: &'b Desc, id_source: Id) -> ValueProbe<'b> {
+        ValueProbe {
+            desc: Some(inner),
+            mydesc: std::mem::take(&mut self.mydesc),
+            myid: self.myid,
+            value: self.value,
+            id_source,
+            path: self.path.clone(),
+            depth: self.depth,
+            inherited: self.inherited.reborrow(),
+        }
+    }
+
+    fn probe_value(&mut self, ui: &mut Ui, style: &Style) -> Response {
+        match self.desc {
+            None => {
+                let id = ui.make_persistent_id(self.id_source);
+                self.mydesc = ui
+                    .ctx()
+                    .data(|d| d.get_temp::<Desc>(id))
+                    .unwrap_or_default();
+                let r = ui.horizontal(|ui| {
+                    self.mydesc.probe(ui, style);
+                    limit_desc_depth(ui, &mut self.mydesc, self.depth, self.inherited.config.max_depth);
+                }).response;
+                ui.ctx()
+                    .data_mut(|d| d.insert_temp(id, self.mydesc.clone()));
+                r
+            }
+            Some(Desc::Bool) => match self.value {
+                Value::Bool(value) => value.probe(ui, style),
+                _ => {
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                            path: self.path.clone(),
+                            expected: "bool",
+                            found: self.value.kind(),
+                        });
+                        if reset_button(ui, &mut self.inherited, self.id_source, &self.path, "Reset to false", self.value) {
+                            *self.value = Value::Bool(false);
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+            },
+            Some(Desc::Int { min, max, min_path, max_path, humanize, stepper, step }) => {
+                let root = self.inherited.root;
+                let min = bounds::resolve(ui, root, min_path.as_deref(), *min);
+                let max = bounds::resolve(ui, root, max_path.as_deref(), *max);
+
+                let reset_to = match (min, max) {
+                    (None, None) => 0,
+                    (Some(min), None) => min.max(0),
+                    (None, Some(max)) => max.min(0),
+                    (Some(min), Some(max)) if min <= max => 0i64.clamp(min, max),
+                    (Some(min), Some(max)) => {
+                        return invalid_range(ui, self.inherited.root, &self.path, min, max);
+                    }
+                };
+
+                match self.value {
+                    Value::Int(value) => {
+                        // Bounds taken from other fields follow their edits, so the value is reclamped right away.
+                        let bound_moved = (min_path.is_some() && min.is_some_and(|min| *value < min))
+                            || (max_path.is_some() && max.is_some_and(|max| *value > max));
+                        if bound_moved && !self.inherited.read_only {
+                            self.inherited.coerced(&self.path, value.to_string(), CoercionReason::Clamped);
+                            *value = clamp(*value, min, max);
+                        }
+
+                        let clamped = clamp(*value, min, max);
+                        if clamped != *value {
+                            error::record(ui.ctx(), self.inherited.root, ProbeError::OutOfRange {
+                                path: self.path.clone(),
+                                value: *value as f64,
+                                min: min.map(|min| min as f64),
+                                max: max.map(|max| max as f64),
+                            });
+                        }
+                        ui.horizontal(|ui| {
+                            let r = match (stepper, humanize) {
+                                (Some(step), Some(kind)) => number::probe_stepper(ui, value, *step, min, max, |n| kind.format(n)),
+                                (Some(step), None) => number::probe_stepper(ui, value, *step, min, max, |n| self.inherited.config.number_format.format(n as f64, 0..=0)),
+                                (None, Some(kind)) => number::probe_humanized(ui, self.id_source, value, min, max, *step, *kind, self.inherited.config),
+                                (None, None) => number::probe_number(ui, self.id_source, value, min, max, *step, self.inherited.config),
+                            };
+                            if let Some(step) = step.filter(|&step| step >= 1.0) {
+                                if r.changed() {
+                                    *value = clamp(snap_int(*value, step), min, max);
+                                }
+                            }
+                            report_raw_text(ui.ctx(), self.inherited.root, self.id_source, &self.path);
+                            report_entered_text(ui.ctx(), &mut self.inherited, self.id_source, &self.path);
+
+                            if clamped != *value && !self.inherited.read_only && ui.small_button(format!("Clamp to {clamped}")).clicked() {
+                                self.inherited.coerced(&self.path, value.to_string(), CoercionReason::Clamped);
+                                *value = clamped;
+                            }
+                            r
+                        })
+                        .inner
+                    }
+                    Value::Float(_) | Value::Uint(_) => {
+                        let f = match self.value.converted(Kind::Int) {
+                            Ok(Value::Int(f)) => f,
+                            _ => reset_to,
+                        };
+                        let x = match (min, max) {
+                            (None, None) => f,
+                            (Some(min), None) => min.max(f),
+                            (None, Some(max)) => max.min(f),
+                            (Some(min), Some(max)) => f.clamp(min, max),
+                        };
+
+                        ui.horizontal(|ui| {
+                            error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                                path: self.path.clone(),
+                                expected: "int",
+                                found: self.value.kind(),
+                            });
+
+                            if !self.inherited.read_only && ui.small_button(format!("Convert to {x}")).clicked() {
+                                let reason = CoercionReason::Converted { from: Kind::of(self.value), to: Kind::Int };
+                                self.inherited.coerced(&self.path, diff::preview(self.value), reason);
+                                *self.value = Value::Int(x);
+                            }
+
+                            ui.strong("?");
+                        })
+                        .response
+                    }
+                    _ => {
+                        ui.horizontal(|ui| {
+                            error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                                path: self.path.clone(),
+                                expected: "int",
+                                found: self.value.kind(),
+                            });
+                            if reset_button(ui, &mut self.inherited, self.id_source, &self.path, format!("Reset to {reset_to}"), self.value) {
+                                *self.value = Value::Int(reset_to);
+                            }
+                            ui.strong("?");
+                        })
+                        .response
+                    }
+                }
+            }
+            Some(&Desc::Uint { min, max }) => {
+                let reset_to = match (min, max) {
+                    (Some(min), Some(max)) if min > max => {
+                        return invalid_range(ui, self.inherited.root, &self.path, min, max);
+                    }
+                    (min, _) => min.unwrap_or(0),
+                };
+
+                match self.value {
+                    Value::Uint(value) => {
+                        let clamped = clamp(*value, min, max);
+                        if clamped != *value {
+                            error::record(ui.ctx(), self.inherited.root, ProbeError::OutOfRange {
+                                path: self.path.clone(),
+                                value: *value as f64,
+                                min: min.map(|min| min as f64),
+                                max: max.map(|max| max as f64),
+                            });
+                        }
+                        ui.horizontal(|ui| {
+                            let r = number::probe_number(ui, self.id_source, value, min, max, None, self.inherited.config);
+                            report_raw_text(ui.ctx(), self.inherited.root, self.id_source, &self.path);
+                            report_entered_text(ui.ctx(), &mut self.inherited, self.id_source, &self.path);
+
+                            if clamped != *value && !self.inherited.read_only && ui.small_button(format!("Clamp to {clamped}")).clicked() {
+                                self.inherited.coerced(&self.path, value.to_string(), CoercionReason::Clamped);
+                                *value = clamped;
+                            }
+                            r
+                        })
+                        .inner
+                    }
+                    Value::Int(_) | Value::Float(_) => {
+                        let negative = match *self.value {
+                            Value::Int(value) => value < 0,
+                            Value::Float(value) => value < 0.0,
+                            _ => false,
+                        };
+                        let x = match self.value.converted(Kind::Uint) {
+                            Ok(Value::Uint(x)) => clamp(x, min, max),
+                            _ => reset_to,
+                        };
+
+                        ui.horizontal(|ui| {
+                            error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                                path: self.path.clone(),
+                                expected: "uint",
+                                found: self.value.kind(),
+                            });
+
+                            let (text, reason) = match negative {
+                                true => (format!("Clamp to {x}"), CoercionReason::Clamped),
+                                false => (
+                                    format!("Convert to {x}"),
+                                    CoercionReason::Converted { from: Kind::of(self.value), to: Kind::Uint },
+                                ),
+                            };
+                            if !self.inherited.read_only && ui.small_button(text).clicked() {
+                                self.inherited.coerced(&self.path, diff::preview(self.value), reason);
+                                *self.value = Value::Uint(x);
+                            }
+
+                            ui.strong("?");
+                        })
+                        .response
+                    }
+                    _ => {
+                        ui.horizontal(|ui| {
+                            error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                                path: self.path.clone(),
+                                expected: "uint",
+                                found: self.value.kind(),
+                            });
+                            if reset_button(ui, &mut self.inherited, self.id_source, &self.path, format!("Reset to {reset_to}"), self.value) {
+                                *self.value = Value::Uint(reset_to);
+                            }
+                            ui.strong("?");
+                        })
+                        .response
+                    }
+                }
+            }
+            Some(Desc::Float { min, max, min_path, max_path, stepper, step }) => {
+                let root = self.inherited.root;
+                let min = bounds::resolve(ui, root, min_path.as_deref(), *min);
+                let max = bounds::resolve(ui, root, max_path.as_deref(), *max);
+
+                let reset_to = match (min, max) {
+                    (None, None) => 0.0,
+                    (Some(min), None) => min.max(0.0),
+                    (None, Some(max)) => max.min(0.0),
+                    (Some(min), Some(max)) if min <= max => 0f64.clamp(min, max),
+                    (Some(min), Some(max)) => {
+                        return invalid_range(ui, self.inherited.root, &self.path, min, max);
+                    }
+                };
+
+                match self.value {
+                    Value::Float(value) => {
+                        // Bounds taken from other fields follow their edits, so the value is reclamped right away.
+                        let bound_moved = (min_path.is_some() && min.is_some_and(|min| *value < min))
+                            || (max_path.is_some() && max.is_some_and(|max| *value > max));
+                        if bound_moved && !self.inherited.read_only {
+                            self.inherited.coerced(&self.path, value.to_string(), CoercionReason::Clamped);
+                            *value = clamp(*value, min, max);
+                        }
+
+                        let clamped = clamp(*value, min, max);
+                        if clamped != *value {
+                            error::record(ui.ctx(), self.inherited.root, ProbeError::OutOfRange {
+                                path: self.path.clone(),
+                                value: *value,
+                                min,
+                                max,
+                            });
+                        }
+                        ui.horizontal(|ui| {
+                            let r = match stepper {
+                                Some(step) => number::probe_stepper(ui, value, *step, min, max, |n| self.inherited.config.number_format.format(n, 0..=6)),
+                                None => number::probe_number(ui, self.id_source, value, min, max, *step, self.inherited.config),
+                            };
+                            report_raw_text(ui.ctx(), self.inherited.root, self.id_source, &self.path);
+                            report_entered_text(ui.ctx(), &mut self.inherited, self.id_source, &self.path);
+
+                            if clamped != *value && !self.inherited.read_only && ui.small_button(format!("Clamp to {clamped}")).clicked() {
+                                self.inherited.coerced(&self.path, value.to_string(), CoercionReason::Clamped);
+                                *value = clamped;
+                            }
+                            r
+                        })
+                        .inner
+                    }
+                    Value::Int(_) | Value::Uint(_) => {
+                        let f = match self.value.converted(Kind::Float) {
+                            Ok(Value::Float(f)) => f,
+                            _ => reset_to,
+                        };
+                        let x = match (min, max) {
+                            (None, None) => f,
+                            (Some(min), None) => min.max(f),
+                            (None, Some(max)) => max.min(f),
+                            (Some(min), Some(max)) => f.clamp(min, max),
+                        };
+
+                        ui.horizontal(|ui| {
+                            error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                                path: self.path.clone(),
+                                expected: "float",
+                                found: self.value.kind(),
+                            });
+
+                            if !self.inherited.read_only && ui.small_button(format!("Convert to {x:0.1}")).clicked() {
+                                let reason = CoercionReason::Converted { from: Kind::of(self.value), to: Kind::Float };
+                                self.inherited.coerced(&self.path, diff::preview(self.value), reason);
+                                *self.value = Value::Float(x);
+                            }
+
+                            ui.strong("?");
+                        })
+                        .response
+                    }
+                    _ => {
+                        ui.horizontal(|ui| {
+                            error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                                path: self.path.clone(),
+                                expected: "float",
+                                found: self.value.kind(),
+                            });
+                            if reset_button(ui, &mut self.inherited, self.id_source, &self.path, format!("Reset to {reset_to}"), self.value) {
+                                *self.value = Value::Float(reset_to);
+                            }
+                            ui.strong("?");
+                        })
+                        .response
+                    }
+                }
+            }
+            Some(&Desc::String { ref variants, trim, collapse_whitespace, min_len, max_len, ref pattern, multiline }) => match self.value {
                 Value::String(value) => {
                     match variants {
-                        None => value.probe(ui, style),
+                        None => ui.horizontal(|ui| {
+                            let matched = pattern
+                                .as_ref()
+                                .map(|pattern| pattern::matches_cached(ui.ctx(), self.id_source, pattern, value));
+
+                            let r = ui.scope(|ui| {
+                                if let Some(Ok(false)) = matched {
+                                    ui.visuals_mut().override_text_color = Some(ui.visuals().error_fg_color);
+                                }
+                                match multiline {
+                                    true => ui.text_edit_multiline(value),
+                                    false => value.probe(ui, style),
+                                }
+                            }).inner;
+                            if r.changed() {
+                                if let Some(max_len) = max_len {
+                                    truncate_chars(value, max_len);
+                                }
+                            }
+                            if r.lost_focus() && (trim || collapse_whitespace) {
+                                let normalized = normalize_whitespace(value, trim, collapse_whitespace);
+                                if normalized != *value {
+                                    *value = normalized;
+                                }
+                            }
+
+                            let len = value.chars().count();
+                            if min_len.is_some_and(|min| len < min) || max_len.is_some_and(|max| len > max) {
+                                error::show(ui, self.inherited.root, ProbeError::TextLength {
+                                    path: self.path.clone(),
+                                    min: min_len,
+                                    max: max_len,
+                                    found: len,
+                                });
+                            }
+                            match (pattern, matched) {
+                                (Some(pattern), Some(Ok(false))) => {
+                                    error::show(ui, self.inherited.root, ProbeError::PatternMismatch {
+                                        path: self.path.clone(),
+                                        value: value.clone(),
+                                        pattern: pattern.clone(),
+                                    });
+                                }
+                                (Some(pattern), Some(Err(error))) => {
+                                    error::show(ui, self.inherited.root, ProbeError::InvalidPattern {
+                                        path: self.path.clone(),
+                                        pattern: pattern.clone(),
+                                        error,
+                                    });
+                                }
+                                _ => {}
+                            }
+                            r
+                        })
+                        .inner,
                         Some(variants) => {
-                            let cbox = egui::ComboBox::from_id_source(self.id_source).selected_text(&**value);
+                            let selected = variants
+                                .iter()
+                                .find(|v| v.name == *value)
+                                .map_or_else(|| value.clone(), Variant::label);
+                            let cbox = egui::ComboBox::from_id_source(self.id_source).selected_text(selected);
                             
-                            cbox.show_ui(ui, |ui| {
-                                for variant in variants.iter() {
-                                    if ui.selectable_label(value == variant, variant).clicked() {
-                                        *value = variant.clone();
+                            ui.vertical(|ui| {
+                                let r = cbox.show_ui(ui, |ui| {
+                                    for variant in variants.iter() {
+                                        let mut r = ui.selectable_label(*value == variant.name, variant.label());
+                                        if let Some(description) = &variant.description {
+                                            r = r.on_hover_text(description);
+                                        }
+                                        if r.clicked() {
+                                            *value = variant.name.clone();
+                                        }
+                                    }
+                                }).response;
+
+                                if !variants.iter().any(|v| v.name == *value) {
+                                    error::record(ui.ctx(), self.inherited.root, ProbeError::NotInVariants {
+                                        path: self.path.clone(),
+                                        value: value.clone(),
+                                    });
+                                }
+
+                                if self.inherited.config.variant_help {
+                                    let description = variants
+                                        .iter()
+                                        .find(|v| v.name == *value)
+                                        .and_then(|v| v.description.as_deref());
+
+                                    if let Some(description) = description {
+                                        ui.weak(description);
+                                    }
+                                }
+                                r
+                            }).inner
+                        }
+                    }
+
+                }
+                Value::Bool(_) | Value::Int(_) | Value::Uint(_) | Value::Float(_) | Value::Bytes(_) | Value::Color(_)
+                    if variants.is_none() =>
+                {
+                    let (r, s) = convert_to_string(ui, &self.inherited, &self.path, self.value);
+                    if let Some(s) = s {
+                        let reason = CoercionReason::Converted { from: Kind::of(self.value), to: Kind::String };
+                        self.inherited.coerced(&self.path, diff::preview(self.value), reason);
+                        *self.value = s;
+                    }
+                    r
+                }
+                _  if variants.is_none() => {
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                            path: self.path.clone(),
+                            expected: "string",
+                            found: self.value.kind(),
+                        });
+                        if reset_button(ui, &mut self.inherited, self.id_source, &self.path, "Reset to empty string", self.value) {
+                            *self.value = Value::String(String::new());
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+                _  => {
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                            path: self.path.clone(),
+                            expected: "string",
+                            found: self.value.kind(),
+                        });
+                        if reset_button(ui, &mut self.inherited, self.id_source, &self.path, "Reset to default value", self.value) {
+                            *self.value = Value::String( variants.as_ref().unwrap().first().map_or(String::new(), |s| s.name.clone()) );
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+            },
+            Some(&Desc::Bytes { max_len }) => match self.value {
+                Value::Bytes(bytes) => ui
+                    .horizontal(|ui| {
+                        let (r, invalid) = hex::probe_hex(ui, self.id_source.with("Hex"), bytes, max_len);
+                        ui.weak(format!("{} bytes", bytes.len()));
+
+                        if let Some(text) = invalid {
+                            error::show(ui, self.inherited.root, ProbeError::InvalidInput {
+                                path: self.path.clone(),
+                                text,
+                            });
+                        }
+                        if let Some(limit) = max_len.filter(|&limit| bytes.len() > limit) {
+                            error::show(ui, self.inherited.root, ProbeError::TooLong {
+                                path: self.path.clone(),
+                                limit,
+                                found: bytes.len(),
+                            });
+                        }
+                        r
+                    })
+                    .inner,
+                _ => {
+                    let converted = match self.value {
+                        Value::String(_) | Value::Int(_) => self.value.converted(Kind::Bytes).ok(),
+                        _ => None,
+                    };
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                            path: self.path.clone(),
+                            expected: "bytes",
+                            found: self.value.kind(),
+                        });
+                        if let Some(converted) = converted {
+                            let text = match self.value {
+                                Value::String(_) => "Convert to bytes from hex",
+                                _ => "Convert to bytes",
+                            };
+                            if !self.inherited.read_only && ui.small_button(text).clicked() {
+                                let reason = CoercionReason::Converted { from: Kind::of(self.value), to: Kind::Bytes };
+                                self.inherited.coerced(&self.path, diff::preview(self.value), reason);
+                                *self.value = converted;
+                            }
+                        } else if reset_button(ui, &mut self.inherited, self.id_source, &self.path, "Reset to empty bytes", self.value) {
+                            *self.value = Value::Bytes(Vec::new());
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+            },
+            Some(&Desc::Color { alpha }) => match self.value {
+                Value::Color(rgba) => {
+                    let r = if alpha {
+                        ui.color_edit_button_srgba_unmultiplied(rgba)
+                    } else {
+                        let [r, g, b, _] = rgba;
+                        let mut rgb = [*r, *g, *b];
+                        let r = ui.color_edit_button_srgb(&mut rgb);
+                        if r.changed() {
+                            *rgba = [rgb[0], rgb[1], rgb[2], 255];
+                        }
+                        r
+                    };
+                    r.on_hover_text(color::format(*rgba))
+                }
+                _ => {
+                    let parsed = match self.value {
+                        Value::String(_) => self.value.converted(Kind::Color).ok(),
+                        _ => None,
+                    };
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                            path: self.path.clone(),
+                            expected: "color",
+                            found: self.value.kind(),
+                        });
+                        if let Some(Value::Color(mut rgba)) = parsed {
+                            if !alpha {
+                                rgba[3] = 255;
+                            }
+                            if !self.inherited.read_only && ui.small_button("Parse color").clicked() {
+                                let reason = CoercionReason::Converted { from: Kind::String, to: Kind::Color };
+                                self.inherited.coerced(&self.path, diff::preview(self.value), reason);
+                                *self.value = Value::Color(rgba);
+                            }
+                        } else if reset_button(ui, &mut self.inherited, self.id_source, &self.path, "Reset to white", self.value) {
+                            *self.value = Value::Color([255; 4]);
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+            },
+            Some(&Desc::Matrix { rows, cols, min, max, identity }) => match self.value {
+                Value::List(elems)
+                    if elems.len() == rows * cols && elems.iter().all(|elem| matches!(elem, Value::Float(_))) =>
+                {
+                    if cols == 0 {
+                        return ui.weak("empty matrix");
+                    }
+
+                    let format = &self.inherited.config.number_format;
+                    egui::Grid::new(self.id_source.with("Matrix")).show(ui, |ui| {
+                        for row in elems.chunks_mut(cols) {
+                            for elem in row {
+                                if let Value::Float(value) = elem {
+                                    ui.add(number::drag_number(value, min, max, format));
+                                }
+                            }
+                            ui.end_row();
+                        }
+                    }).response
+                }
+                Value::List(elems) => {
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::WrongLength {
+                            path: self.path.clone(),
+                            expected: rows * cols,
+                            found: elems.len(),
+                        });
+                        if !self.inherited.read_only && ui.small_button("Fix").on_hover_text("Convert elements to floats and pad or truncate to the matrix size").clicked() {
+                            elems.resize_with(rows * cols, || Value::Float(0.0));
+                            for (idx, elem) in elems.iter_mut().enumerate() {
+                                let path = path::join(&self.path, &format!("[{idx}]"));
+                                let value = match *elem {
+                                    Value::Int(value) => {
+                                        self.inherited.coerced(&path, value.to_string(), CoercionReason::Converted {
+                                            from: Kind::Int,
+                                            to: Kind::Float,
+                                        });
+                                        value as f64
+                                    }
+                                    Value::Float(value) => value,
+                                    _ => {
+                                        self.inherited.coerced(&path, diff::preview(elem), CoercionReason::Reset);
+                                        matrix_default(idx / cols, idx % cols, identity)
+                                    }
+                                };
+                                if clamp(value, min, max) != value {
+                                    self.inherited.coerced(&path, value.to_string(), CoercionReason::Clamped);
+                                }
+                                *elem = Value::Float(clamp(value, min, max));
+                            }
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+                _ => {
+                    let desc = self.desc.unwrap();
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                            path: self.path.clone(),
+                            expected: "matrix",
+                            found: self.value.kind(),
+                        });
+                        let text = if identity { "Reset to identity matrix" } else { "Reset to zero matrix" };
+                        if reset_button(ui, &mut self.inherited, self.id_source, &self.path, text, self.value) {
+                            *self.value = desc.default_value();
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+            },
+            Some(Desc::Enum { variants }) => match self.value {
+                Value::Enum { variant, value } if variants.iter().any(|v| v.name == *variant) => {
+                    let selected = variants
+                        .iter()
+                        .find(|v| v.name == *variant)
+                        .map_or_else(|| variant.clone(), EnumVariant::label);
+                    ui.vertical(|ui| {
+                        let r = egui::ComboBox::from_id_source(self.id_source)
+                            .selected_text(selected)
+                            .show_ui(ui, |ui| {
+                                for v in variants {
+                                    let mut r = ui.selectable_label(*variant == v.name, v.label());
+                                    if let Some(description) = &v.description {
+                                        r = r.on_hover_text(description);
+                                    }
+                                    if r.clicked() && *variant != v.name {
+                                        *variant = v.name.clone();
+                                        **value = v.default_payload();
+                                    }
+                                }
+                            })
+                            .response;
+
+                        if self.inherited.config.variant_help {
+                            let description = variants
+                                .iter()
+                                .find(|v| v.name == *variant)
+                                .and_then(|v| v.description.as_deref());
+
+                            if let Some(description) = description {
+                                ui.weak(description);
+                            }
+                        }
+                        r
+                    })
+                    .inner
+                }
+                Value::Enum { variant, .. } => {
+                    let desc = self.desc.unwrap();
+                    let variant = variant.clone();
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::NotInVariants {
+                            path: self.path.clone(),
+                            value: variant,
+                        });
+                        if reset_button(ui, &mut self.inherited, self.id_source, &self.path, "Reset to default variant", self.value) {
+                            *self.value = desc.default_value();
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+                _ => {
+                    let desc = self.desc.unwrap();
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                            path: self.path.clone(),
+                            expected: "enum",
+                            found: self.value.kind(),
+                        });
+                        if reset_button(ui, &mut self.inherited, self.id_source, &self.path, "Reset to default variant", self.value) {
+                            *self.value = desc.default_value();
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+            },
+            Some(Desc::Optional { inner }) => {
+                let last_id = self.id_source.with("LastSome");
+                ui.horizontal(|ui| {
+                    let mut some = !matches!(self.value, Value::Null);
+                    let hover = if some { "Unset the value" } else { "Set the value" };
+                    if ui.checkbox(&mut some, "").on_hover_text(hover).changed() {
+                        if some {
+                            let last = ui.data_mut(|d| {
+                                let last = d.get_temp::<Value>(last_id);
+                                d.remove::<Value>(last_id);
+                                last
+                            });
+                            *self.value = last.unwrap_or_else(|| inner.default_value());
+                        } else {
+                            let last = std::mem::replace(self.value, Value::Null);
+                            ui.data_mut(|d| d.insert_temp(last_id, last));
+                        }
+                    }
+
+                    if some {
+                        let mut probe = self.optional_inner(inner);
+                        probe.probe_value(ui, style);
+                        let (mydesc, myid) = (probe.mydesc, probe.myid);
+                        self.mydesc = mydesc;
+                        self.myid = myid;
+                    } else {
+                        ui.weak("none");
+                    }
+                })
+                .response
+            }
+            Some(desc @ (Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner })) => {
+                let id_source = self.id_source;
+                let mut probe = self.wrapped_inner(inner, id_source);
+                let r = probe.probe_value(ui, style);
+                let (mydesc, myid) = (probe.mydesc, probe.myid);
+                self.mydesc = mydesc;
+                self.myid = myid;
+                match desc {
+                    Desc::Documented { doc, .. } => r.on_hover_text(doc),
+                    _ => r,
+                }
+            }
+            Some(Desc::Struct { fields }) => match self.value {
+                Value::Map(values) => {
+                    for field in fields {
+                        if !values.contains_key(&field.name) && !self.inherited.read_only {
+                            values.insert(field.name.clone(), field.desc.default_value());
+                        }
+                    }
+
+                    let mut extra: Vec<String> = values
+                        .keys()
+                        .filter(|key| !fields.iter().any(|field| field.name == **key))
+                        .cloned()
+                        .collect();
+                    if extra.is_empty() {
+                        return ui.weak(format!("{} fields", fields.len()));
+                    }
+
+                    extra.sort();
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::ExtraFields {
+                            path: self.path.clone(),
+                            fields: extra.clone(),
+                        });
+                        let text = if extra.len() == 1 { "Drop extra field" } else { "Drop extra fields" };
+                        if !self.inherited.structure_locked() && ui.small_button(text).clicked() {
+                            for key in &extra {
+                                values.shift_remove(key);
+                            }
+                        }
+                    })
+                    .response
+                }
+                _ => {
+                    let desc = self.desc.unwrap();
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                            path: self.path.clone(),
+                            expected: "struct",
+                            found: self.value.kind(),
+                        });
+                        if reset_button(ui, &mut self.inherited, self.id_source, &self.path, "Reset to default", self.value) {
+                            *self.value = desc.default_value();
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+            },
+            Some(Desc::Tuple { elems: descs }) => match self.value {
+                Value::List(elems) if elems.len() == descs.len() => ui.weak(format!("{} elements", descs.len())),
+                Value::List(elems) => {
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::WrongLength {
+                            path: self.path.clone(),
+                            expected: descs.len(),
+                            found: elems.len(),
+                        });
+                        let text = if elems.len() > descs.len() { "Truncate" } else { "Pad with defaults" };
+                        if !self.inherited.read_only && ui.small_button(text).clicked() {
+                            fit_tuple(elems, descs);
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+                _ => {
+                    let desc = self.desc.unwrap();
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                            path: self.path.clone(),
+                            expected: "tuple",
+                            found: self.value.kind(),
+                        });
+                        if reset_button(ui, &mut self.inherited, self.id_source, &self.path, "Reset to default", self.value) {
+                            *self.value = desc.default_value();
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+            },
+            Some(Desc::List { elem_desc: elem, empty_hint, min_len, max_len, multi_select, unique_by, .. }) => match self.value {
+                Value::List(elems) => {
+                    let limit = add_limit(&self.inherited, elems.len(), *max_len);
+                    let r = match elem {
+                        None => {
+                            self.myid = ui.make_persistent_id(self.id_source.with("List"));
+                            self.mydesc = ui
+                                .ctx()
+                                .data(|d| d.get_temp::<Desc>(self.myid))
+                                .unwrap_or_default();
+        
+                            let r = ui.horizontal(|ui| {
+                                let kind = self.mydesc.kind();
+                                self.mydesc.probe(ui, style);
+                                limit_desc_depth(ui, &mut self.mydesc, self.depth + 1, self.inherited.config.max_depth);
+
+                                if add_button(ui, style, &self.inherited, limit.as_deref()) {
+                                    let item = self.inherited.new_item(&self.path, &self.mydesc);
+                                    push_item(ui, self.id_source, elems, item, unique_by.as_deref());
+                                }
+                                duplicate_notice(ui, self.id_source, "duplicate");
+
+                                let changed = kind != self.mydesc.kind();
+                                recoerce_offer(ui, self.myid.with("Recoerce"), changed, &self.mydesc, elems);
+                            }).response;
+
+                            if limit.is_none() && has_focus_within(ui, r.rect) && consume_shortcut(ui, self.inherited.config.shortcuts.add) {
+                                let item = self.inherited.new_item(&self.path, &self.mydesc);
+                                push_item(ui, self.id_source, elems, item, unique_by.as_deref());
+                            }
+
+                            ui.ctx().data_mut(|d| d.insert_temp(self.myid, self.mydesc.clone()));
+                            r
+                        }
+                        Some(elem) => {
+                            let r = ui.horizontal(|ui| {
+                                ui.weak(elem.kind());
+
+                                if add_button(ui, style, &self.inherited, limit.as_deref()) {
+                                    let item = self.inherited.new_item(&self.path, elem);
+                                    push_item(ui, self.id_source, elems, item, unique_by.as_deref());
+                                }
+                                duplicate_notice(ui, self.id_source, "duplicate");
+                            }).response;
+
+                            if limit.is_none() && has_focus_within(ui, r.rect) && consume_shortcut(ui, self.inherited.config.shortcuts.add) {
+                                let item = self.inherited.new_item(&self.path, elem);
+                                push_item(ui, self.id_source, elems, item, unique_by.as_deref());
+                            }
+                            r
+                        }
+                    };
+
+                    if let (true, Some(hint), false) = (elems.is_empty(), empty_hint, self.inherited.read_only) {
+                        ui.weak(hint);
+                    }
+
+                    if let Some(field) = unique_by {
+                        let duplicates = validate::duplicates(elems, field);
+                        if !duplicates.is_empty() {
+                            for &idx in &duplicates {
+                                let path = path::join(&self.path, &format!("[{idx}]"));
+                                ui.data_mut(|d| d.insert_temp(self.inherited.root.with(("NotUnique", path)), field.clone()));
+                            }
+
+                            let fix = ui.horizontal(|ui| {
+                                ui.weak(format!("{} elements share `{field}`", duplicates.len()));
+                                !self.inherited.read_only && ui.small_button("Make unique").clicked()
+                            }).inner;
+                            if fix {
+                                make_unique(elems, field);
+                            }
+                        }
+                    }
+
+                    if *multi_select && !elems.is_empty() && !self.inherited.structure_locked() {
+                        selection_toolbar(
+                            ui,
+                            self.id_source,
+                            &self.inherited,
+                            &self.path,
+                            elems,
+                            *max_len,
+                            min_len.unwrap_or(0),
+                            unique_by.as_deref(),
+                        );
+                    }
+
+                    if let Some(min) = min_len.filter(|&min| elems.len() < min && !self.inherited.read_only) {
+                        let elem = elem.as_deref().unwrap_or(&self.mydesc);
+                        elems.resize(min, elem.default_value());
+                    }
+
+                    if let Some(max) = max_len.filter(|&max| elems.len() > max) {
+                        let keep = over_limit(ui, &self.inherited, &self.path, elems.len(), max);
+                        if keep {
+                            elems.truncate(max);
+                        }
+                    }
+                    r
+                }
+                _ => {
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                            path: self.path.clone(),
+                            expected: "list",
+                            found: self.value.kind(),
+                        });
+                        if reset_button(ui, &mut self.inherited, self.id_source, &self.path, "Reset to empty list", self.value) {
+                            *self.value = Value::List(Vec::new());
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+            },
+            Some(Desc::Map { value_desc: value, empty_hint, max_entries, required_keys, required_if, strict_keys, .. }) => match self.value {
+                Value::Map(values) => {
+                    let limit = add_limit(&self.inherited, values.len(), *max_entries);
+                    #[derive(Clone)]
+                    struct NewKey(String);
+                    
+                    self.myid = ui.make_persistent_id(self.id_source.with("Map"));
+
+                    let mut new_key = ui.ctx().data(|d| d.get_temp::<NewKey>(self.myid)).unwrap_or(NewKey(String::new()));
+
+                    let r = match value {
+                        None => {
+                            self.mydesc = ui
+                                .ctx()
+                                .data(|d| d.get_temp::<Desc>(self.myid))
+                                .unwrap_or_default();
+
+                            let r = ui.horizontal(|ui| {
+                                self.mydesc.probe(ui, style);
+                                limit_desc_depth(ui, &mut self.mydesc, self.depth + 1, self.inherited.config.max_depth);
+
+                                if !self.inherited.structure_locked() {
+                                    ui.add(egui::TextEdit::singleline(&mut new_key.0).hint_text("new key"));
+                                    if values.contains_key(&new_key.0) {
+                                        ui.colored_label(ui.visuals().error_fg_color, "key exists");
+                                    }
+                                }
+
+                                let refusal = new_key_refusal(values, &new_key.0);
+                                let add = ui.add_enabled_ui(refusal.is_none(), |ui| {
+                                    add_button(ui, style, &self.inherited, limit.as_deref())
+                                });
+                                if let Some(refusal) = refusal {
+                                    add.response.on_disabled_hover_text(refusal);
+                                }
+                                if add.inner {
+                                    values.insert(std::mem::take(&mut new_key.0), self.inherited.new_item(&self.path, &self.mydesc));
+                                }
+                            }).response;
+
+                            if limit.is_none()
+                                && new_key_refusal(values, &new_key.0).is_none()
+                                && has_focus_within(ui, r.rect)
+                                && consume_shortcut(ui, self.inherited.config.shortcuts.add)
+                            {
+                                values.insert(std::mem::take(&mut new_key.0), self.inherited.new_item(&self.path, &self.mydesc));
+                            }
+
+                            ui.ctx().data_mut(|d| d.insert_temp(self.myid, self.mydesc.clone()));
+                            r
+                        }
+                        Some(elem) => {
+                            let r = ui.horizontal(|ui| {
+                                ui.weak(elem.kind());
+
+                                if !self.inherited.structure_locked() {
+                                    ui.add(egui::TextEdit::singleline(&mut new_key.0).hint_text("new key"));
+                                    if values.contains_key(&new_key.0) {
+                                        ui.colored_label(ui.visuals().error_fg_color, "key exists");
                                     }
                                 }
-                            }).response
+
+                                let refusal = new_key_refusal(values, &new_key.0);
+                                let add = ui.add_enabled_ui(refusal.is_none(), |ui| {
+                                    add_button(ui, style, &self.inherited, limit.as_deref())
+                                });
+                                if let Some(refusal) = refusal {
+                                    add.response.on_disabled_hover_text(refusal);
+                                }
+                                if add.inner {
+                                    values.insert(std::mem::take(&mut new_key.0), self.inherited.new_item(&self.path, elem));
+                                }
+                            }).response;
+
+                            if limit.is_none()
+                                && new_key_refusal(values, &new_key.0).is_none()
+                                && has_focus_within(ui, r.rect)
+                                && consume_shortcut(ui, self.inherited.config.shortcuts.add)
+                            {
+                                values.insert(std::mem::take(&mut new_key.0), self.inherited.new_item(&self.path, elem));
+                            }
+                            r
+                        }
+                    };
+
+                    ui.ctx().data_mut(|d| d.insert_temp(self.myid, new_key));
+
+                    if let (true, Some(hint), false) = (values.is_empty(), empty_hint, self.inherited.read_only) {
+                        ui.weak(hint);
+                    }
+
+                    let root = self.inherited.root;
+                    let mut missing: Vec<String> = required_keys
+                        .iter()
+                        .chain(required_if.iter().filter(|req| condition::holds(ui.ctx(), root, &req.when)).map(|req| &req.key))
+                        .filter(|key| !values.contains_key(*key))
+                        .cloned()
+                        .collect();
+                    if !missing.is_empty() {
+                        missing.sort();
+                        let add = ui.horizontal(|ui| {
+                            error::show(ui, self.inherited.root, ProbeError::MissingKeys {
+                                path: self.path.clone(),
+                                keys: missing.clone(),
+                            });
+                            !self.inherited.structure_locked() && ui.small_button("Add missing").clicked()
+                        }).inner;
+
+                        if add {
+                            let desc = value.as_deref().unwrap_or(&self.mydesc);
+                            for key in missing {
+                                let item = self.inherited.new_item(&self.path, desc);
+                                values.insert(key, item);
+                            }
+                        }
+                    }
+
+                    if *strict_keys {
+                        let mut unexpected: Vec<String> = values
+                            .keys()
+                            .filter(|key| !required_keys.contains(key) && !required_if.iter().any(|req| req.key == **key))
+                            .cloned()
+                            .collect();
+                        if !unexpected.is_empty() {
+                            unexpected.sort();
+                            error::show(ui, self.inherited.root, ProbeError::UnexpectedKeys {
+                                path: self.path.clone(),
+                                keys: unexpected,
+                            });
+                        }
+                    }
+
+                    if let Some(max) = max_entries.filter(|&max| values.len() > max) {
+                        let keep = over_limit(ui, &self.inherited, &self.path, values.len(), max);
+                        if keep {
+                            values.truncate(max);
+                        }
+                    }
+                    r
+                }
+                _ => {
+                    ui.horizontal(|ui| {
+                        error::show(ui, self.inherited.root, ProbeError::TypeMismatch {
+                            path: self.path.clone(),
+                            expected: "map",
+                            found: self.value.kind(),
+                        });
+                        if reset_button(ui, &mut self.inherited, self.id_source, &self.path, "Reset to empty map", self.value) {
+                            *self.value = Value::Map(IndexMap::new());
+                        }
+                        ui.strong("?");
+                    })
+                    .response
+                }
+            },
+        }
+    }
+
+}
+
+impl EguiProbe for ValueProbe<'_> {
+    fn probe(&mut self, ui: &mut Ui, style: &Style) -> Response {
+        if self.depth == 0 {
+            self.prepare_root(ui);
+        }
+
+        // Leaf fields remember whether they had focus to hold back their errors while edited.
+        let leaf = matches!(self.desc.map(Desc::unwrapped), Some(Desc::Bool | Desc::Int { .. } | Desc::Uint { .. } | Desc::Float { .. } | Desc::String { .. } | Desc::Bytes { .. } | Desc::Color { .. }));
+        let editing_id = self.id_source.with("Editing");
+        let defer = leaf
+            && self.inherited.config.validation_timing == ValidationTiming::OnBlur
+            && ui.data(|d| d.get_temp::<bool>(editing_id)).unwrap_or(false);
+
+        let background = ui.painter().add(egui::Shape::Noop);
+        // Set by the list that holds this value if its unique field repeats.
+        let not_unique = ui.data_mut(|d| d.remove_temp::<String>(self.inherited.root.with(("NotUnique", &self.path))));
+
+        error::defer(ui.ctx(), self.inherited.root, defer);
+        let mut r = ui
+            .add_enabled_ui(!self.inherited.read_only, |ui| match not_unique {
+                None => self.probe_value(ui, style),
+                Some(field) => {
+                    ui.horizontal(|ui| {
+                        let error = ProbeError::NotUnique { path: self.path.clone(), field };
+                        ui.colored_label(ui.visuals().error_fg_color, "⚠").on_hover_text(error.to_string());
+                        error::record(ui.ctx(), self.inherited.root, error);
+                        self.probe_value(ui, style);
+                    })
+                    .response
+                }
+            })
+            .inner;
+        error::defer(ui.ctx(), self.inherited.root, false);
+
+        // Drawn outside of the disabled block to stay usable in read-only mode.
+        if !self.value.is_null() && self.desc.is_some_and(Desc::holds_advanced) {
+            let mut show = shows_advanced(ui, self.inherited.root, &self.path);
+            if ui.checkbox(&mut show, "Show advanced").changed() {
+                let id = self.inherited.root.with(("ShowAdvanced", &self.path));
+                ui.ctx().data_mut(|d| d.insert_temp(id, show));
+            }
+        }
+
+        if self.inherited.config.section_reset && !self.inherited.read_only {
+            if let Some(desc @ (Desc::List { .. } | Desc::Map { .. } | Desc::Struct { .. } | Desc::Tuple { .. })) = self.desc.map(Desc::unwrapped) {
+                if section_reset_button(ui, self.id_source) {
+                    self.inherited.coerced(&self.path, diff::preview(self.value), CoercionReason::Reset);
+                    reset_section(self.value, desc, self.inherited.config.structure_locked);
+                }
+            }
+        }
+
+        if let Some(color) = self.inherited.style_for.and_then(|style_for| style_for(&self.path)) {
+            let rect = r.rect.expand(ui.spacing().item_spacing.y / 2.0);
+            let rounding = ui.visuals().widgets.noninteractive.rounding;
+            ui.painter().set(background, egui::Shape::rect_filled(rect, rounding, color));
+        }
+        outline::scroll_if_requested(ui, self.inherited.root, &self.path, &r);
+
+        let focused = has_focus_within(ui, r.rect);
+        if focused {
+            breadcrumb::set_focus_path(ui.ctx(), self.inherited.root, &self.path);
+        }
+        if leaf {
+            ui.data_mut(|d| d.insert_temp(editing_id, focused));
+        }
+
+        if let Some(
+            Desc::List { collapsed_by_default: true, .. } | Desc::Map { collapsed_by_default: true, .. },
+        ) = self.desc.map(Desc::unwrapped)
+        {
+            flat::mark_collapsed_by_default(ui.ctx(), self.inherited.root, &self.path);
+        }
+
+        if self.inherited.config.schema_tooltips {
+            let summary = match self.desc {
+                Some(desc) => desc.summary(),
+                None => format!("any, currently {}", self.mydesc.summary()),
+            };
+            r = r.on_hover_text(summary);
+        }
+        r
+    }
+
+    fn has_inner(&mut self) -> bool {
+        match self.desc {
+            None => true,
+            Some(Desc::Bool) => false,
+            Some(Desc::Int { .. }) => false,
+            Some(Desc::Uint { .. }) => false,
+            Some(Desc::Float { .. }) => false,
+            Some(Desc::String { .. }) => false,
+            Some(Desc::Bytes { .. }) => false,
+            Some(Desc::Color { .. }) => false,
+            Some(Desc::List { elem_desc, .. }) => elem_desc.is_none() || self.value.has_inner(),
+            Some(Desc::Map { value_desc, .. }) => value_desc.is_none() || self.value.has_inner(),
+            Some(Desc::Matrix { .. }) => false,
+            Some(Desc::Enum { variants }) => match &*self.value {
+                Value::Enum { variant, .. } => variants.iter().any(|v| v.name == *variant && v.desc.is_some()),
+                _ => false,
+            },
+            Some(Desc::Struct { fields }) => !fields.is_empty() && matches!(self.value, Value::Map(_)),
+            Some(Desc::Tuple { elems }) => !elems.is_empty() && matches!(self.value, Value::List(_)),
+            Some(Desc::Optional { inner }) => {
+                !matches!(self.value, Value::Null) && self.optional_inner(inner).has_inner()
+            }
+            Some(Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner }) => {
+                let id_source = self.id_source;
+                self.wrapped_inner(inner, id_source).has_inner()
+            }
+        }
+    }
+
+    fn iterate_inner(&mut self, ui: &mut Ui, f: &mut dyn FnMut(&str, &mut Ui, &mut dyn EguiProbe)) {
+        match self.desc {
+            None => {
+                let mut probe = ValueProbe::nested(
+                    Some(&self.mydesc),
+                    self.value,
+                    self.id_source,
+                    self.path.clone(),
+                    self.depth,
+                    self.inherited.reborrow(),
+                );
+                f("value", ui, &mut probe);
+            }
+            Some(Desc::Bool) => {}
+            Some(Desc::Int { .. }) => {}
+            Some(Desc::Uint { .. }) => {}
+            Some(Desc::Float { .. }) => {}
+            Some(Desc::String { .. }) => {}
+            Some(Desc::Bytes { .. }) => {}
+            Some(Desc::Color { .. }) => {}
+            Some(Desc::Matrix { .. }) => {}
+            Some(Desc::Enum { variants }) => {
+                if let Value::Enum { variant, value } = self.value {
+                    if let Some(desc) = variants.iter().find(|v| v.name == *variant).and_then(|v| v.desc.as_ref()) {
+                        let path = path::join(&self.path, variant);
+                        if self.inherited.is_visible(&path) {
+                            let mut probe = ValueProbe::nested(
+                                Some(desc),
+                                value,
+                                self.id_source.with("Enum"),
+                                path,
+                                self.depth + 1,
+                                self.inherited.reborrow(),
+                            );
+                            f(variant, ui, &mut probe);
+                        }
+                    }
+                }
+            }
+            Some(Desc::Optional { inner }) => {
+                if !matches!(self.value, Value::Null) {
+                    let mut probe = self.optional_inner(inner);
+                    probe.iterate_inner(ui, f);
+                    let mydesc = probe.mydesc;
+                    self.mydesc = mydesc;
+                }
+            }
+            Some(Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner }) => {
+                let id_source = self.id_source;
+                let mut probe = self.wrapped_inner(inner, id_source);
+                probe.iterate_inner(ui, f);
+                let mydesc = probe.mydesc;
+                self.mydesc = mydesc;
+            }
+            Some(Desc::Struct { fields }) => {
+                let show_advanced = shows_advanced(ui, self.inherited.root, &self.path);
+                if let Value::Map(values) = self.value {
+                    let id = self.id_source.with("Struct");
+                    for field in fields {
+                        let path = path::join(&self.path, &field.name);
+                        if !self.inherited.is_visible(&path) || (!show_advanced && field.desc.is_advanced()) {
+                            continue;
+                        }
+                        let Some(value) = values.get_mut(&field.name) else {
+                            continue;
+                        };
+                        let mut probe = ValueProbe::nested(
+                            Some(&field.desc),
+                            value,
+                            id.with(&field.name),
+                            path,
+                            self.depth + 1,
+                            self.inherited.reborrow(),
+                        );
+                        f(&field.name, ui, &mut probe);
+                    }
+                }
+            }
+            Some(Desc::Tuple { elems: descs }) => {
+                let show_advanced = shows_advanced(ui, self.inherited.root, &self.path);
+                if let Value::List(elems) = self.value {
+                    let id = self.id_source.with("Tuple");
+                    for (idx, (desc, value)) in descs.iter().zip(elems).enumerate() {
+                        let label = format!("[{idx}]");
+                        let path = path::join(&self.path, &label);
+                        if !self.inherited.is_visible(&path) || (!show_advanced && desc.is_advanced()) {
+                            continue;
+                        }
+                        let mut probe = ValueProbe::nested(Some(desc), value, id.with(idx), path, self.depth + 1, self.inherited.reborrow());
+                        f(&label, ui, &mut probe);
+                    }
+                }
+            }
+            Some(Desc::List { elem_desc: elem, min_len, max_len, multi_select, unique_by, .. }) => {
+                let elem = match elem {
+                    None => {
+                        if self.mydesc.has_inner() {
+                            self.mydesc.iterate_inner(ui, f);
+                        }
+                        self.mydesc.truncate_depth(
+                            self.inherited.config.max_depth.saturating_sub(self.depth + 1).max(1),
+                        );
+                        ui.ctx().data_mut(|d| d.insert_temp(self.myid, self.mydesc.clone()));
+
+                        &self.mydesc
+                    },
+                    Some(elem) => &**elem,
+                };
+
+                let hide_elems = elem.is_advanced() && !shows_advanced(ui, self.inherited.root, &self.path);
+                if let Value::List(elems) = self.value {
+                    let id = self.id_source.with("List");
+                    let depth = self.depth + 1;
+                    let mut inherited = self.inherited.reborrow();
+
+                    let selection_id = self.id_source.with("Selection");
+                    let mut selection = multi_select.then(|| Selection::load(ui, selection_id, elems.len()));
+                    let mut deleted = Vec::new();
+
+                    let mut idx = 0;
+                    let mut kept = 0;
+                    let mut focused = false;
+                    let mut len = elems.len();
+                    let min_len = min_len.unwrap_or(0);
+                    let count = elems.len();
+                    let mut moved = None;
+                    let mut dropped_at = None;
+                    elems.retain_mut(|value| {
+                        let label = format!("[{idx}]");
+                        let path = path::join(&self.path, &label);
+                        if hide_elems || !inherited.is_visible(&path) {
+                            idx += 1;
+                            kept += 1;
+                            return true;
+                        }
+
+                        let structure_locked = inherited.structure_locked();
+                        let delete_shortcut = inherited.config.shortcuts.delete;
+                        let mut probe =
+                            ValueProbe::nested(Some(elem), value, id.with(idx), path, depth, inherited.reborrow());
+                        if structure_locked {
+                            f(&label, ui, &mut probe);
+                            idx += 1;
+                            kept += 1;
+                            return true;
+                        }
+
+                        let mut reorder = ReorderMe {
+                            value: &mut probe,
+                            can_move_up: idx > 0,
+                            can_move_down: idx + 1 < count,
+                            offset: 0,
+                            drag: (count > 1).then_some((id, idx)),
+                            dropped: None,
+                        };
+                        let mut item = ItemMe {
+                            value: &mut reorder,
+                            delete: false,
+                            shortcut: delete_shortcut,
+                            focused: false,
+                            selected: selection.as_ref().map(|selection| selection.is_selected(idx)),
+                            keep_reason: (len <= min_len).then(|| format!("At least {min_len} elements are required")),
+                        };
+                        f(&label, ui, &mut item);
+                        if let (Some(selection), Some(selected)) = (&mut selection, item.selected) {
+                            selection.set_selected(idx, selected);
+                        }
+                        idx += 1;
+                        focused |= item.focused;
+                        if item.delete {
+                            deleted.push(idx - 1);
+                            len -= 1;
+                            return false;
+                        }
+                        if reorder.offset != 0 {
+                            moved = Some((kept, reorder.offset));
+                        }
+                        if let Some(dropped) = reorder.dropped {
+                            dropped_at = Some(dropped);
+                        }
+                        kept += 1;
+                        true
+                    });
+
+                    // Ids of the selection follow their items when they are deleted, moved or dropped.
+                    if let Some(selection) = &mut selection {
+                        let mut idx = 0;
+                        selection.ids.retain(|_| {
+                            idx += 1;
+                            !deleted.contains(&(idx - 1))
+                        });
+                    }
+
+                    if let Some((from, offset)) = moved {
+                        if move_item(elems, from, offset).is_some() {
+                            if let Some(selection) = &mut selection {
+                                move_item(&mut selection.ids, from, offset);
+                            }
+                        }
+                    }
+
+                    // Indices of a drop refer to the list before this frame's deletions.
+                    if let Some((from, to)) = dropped_at.filter(|_| elems.len() == count) {
+                        if drop_item(elems, from, to).is_some() {
+                            if let Some(selection) = &mut selection {
+                                drop_item(&mut selection.ids, from, to);
+                            }
+                        }
+                    }
+
+                    if let Some(selection) = selection {
+                        selection.store(ui, selection_id);
+                    }
+
+                    let can_add = add_limit(&inherited, elems.len(), *max_len).is_none();
+                    if can_add && focused && consume_shortcut(ui, inherited.config.shortcuts.add) {
+                        let item = inherited.new_item(&self.path, elem);
+                        push_item(ui, self.id_source, elems, item, unique_by.as_deref());
+                    }
+                }
+            }
+            Some(Desc::Map { value_desc: value, tabs, required_keys, required_if, .. }) => {
+                let desc = match value {
+                    None => {
+                        if self.mydesc.has_inner() {
+                            self.mydesc.iterate_inner(ui, f);
+                        }
+                        self.mydesc.truncate_depth(
+                            self.inherited.config.max_depth.saturating_sub(self.depth + 1).max(1),
+                        );
+                        ui.ctx().data_mut(|d| d.insert_temp(self.myid, self.mydesc.clone()));
+
+                        &self.mydesc
+                    },
+                    Some(value) => &**value,
+                };
+
+                let hide_values = desc.is_advanced() && !shows_advanced(ui, self.inherited.root, &self.path);
+                if let Value::Map(values) = self.value {
+                    let id: Id = self.id_source.with("List");
+                    let depth = self.depth + 1;
+                    let mut inherited = self.inherited.reborrow();
+
+                    let declared = required_keys
+                        .iter()
+                        .chain(required_if.iter().map(|req| &req.key))
+                        .chain(tabs.iter().flat_map(|tab| &tab.keys));
+                    let mut renames = Vec::new();
+                    for key in ordered_keys(values, declared) {
+                        let path = path::join(&self.path, &key);
+                        if hide_values || !inherited.is_visible(&path) {
+                            continue;
+                        }
+
+                        let Some(value) = values.get_mut(&key) else {
+                            continue;
+                        };
+                        let structure_locked = inherited.structure_locked();
+                        let delete_shortcut = inherited.config.shortcuts.delete;
+                        let mut probe =
+                            ValueProbe::nested(Some(desc), value, id.with(&key), path, depth, inherited.reborrow());
+                        if structure_locked {
+                            f(&key, ui, &mut probe);
+                            continue;
+                        }
+
+                        let mut item = ItemMe {
+                            value: &mut probe,
+                            delete: false,
+                            shortcut: delete_shortcut,
+                            focused: false,
+                            selected: None,
+                            keep_reason: None,
+                        };
+                        let mut rename = RenameMe {
+                            value: &mut item,
+                            key: &key,
+                            id: id.with(&key).with("Key"),
+                            renamed: None,
+                        };
+                        f(&key, ui, &mut rename);
+                        if let Some(new_key) = rename.renamed {
+                            renames.push((key.clone(), new_key));
+                        }
+                        if item.delete {
+                            values.shift_remove(&key);
                         }
                     }
 
-                }
-                Value::Bool(value) if variants.is_none() => {
-                    let (r, s) = convert_to_string(ui, value, "bool");
-                    if let Some(s) = s {
-                        *self.value = Value::String(s);
-                    }
-                    r
-                }
-                Value::Int(value) if variants.is_none() => {
-                    let (r, s) = convert_to_string(ui, value, "int");
-                    if let Some(s) = s {
-                        *self.value = Value::String(s);
-                    }
-                    r
-                }
-                Value::Float(value) if variants.is_none() => {
-                    let (r, s) = convert_to_string(ui, value, "float");
-                    if let Some(s) = s {
-                        *self.value = Value::String(s);
-                    }
-                    r
-                }
-                _  if variants.is_none() => {
-                    ui.horizontal(|ui| {
-                        ui.strong(format!(
-                            "Expected string, but is {} instead",
-                            self.value.kind()
-                        ));
-                        if ui.small_button("Reset to empty string").clicked() {
-                            *self.value = Value::String(String::new());
-                        }
-                        ui.strong("?");
-                    })
-                    .response
-                }
-                _  => {
-                    ui.horizontal(|ui| {
-                        ui.strong(format!(
-                            "Expected string, but is {} instead",
-                            self.value.kind()
-                        ));
-                        if ui.small_button("Reset to default value").clicked() {
-                            *self.value = Value::String( variants.as_ref().unwrap().first().map_or(String::new(), |s| s.clone()) );
-                        }
-                        ui.strong("?");
-                    })
-                    .response
-                }
-            },
-            Some(&Desc::List { elem_desc: ref elem }) => match self.value {
-                Value::List(elems) => {
-                    match elem {
-                        None => {
-                            self.myid = ui.make_persistent_id(self.id_source.with("List"));
-                            self.mydesc = ui
-                                .ctx()
-                                .data(|d| d.get_temp::<Desc>(self.myid))
-                                .unwrap_or_default();
-        
-                            let r = ui.horizontal(|ui| {
-                                self.mydesc.probe(ui, style);
+                    for (key, new_key) in renames {
+                        if let Err(refusal) = rename_key(values, &key, new_key) {
+                            let now = ui.input(|i| i.time);
+                            let key_id = id.with(&key).with("Key");
+                            ui.data_mut(|d| {
+                                d.insert_temp(key_id.with("Duplicate"), now);
+                                d.insert_temp(key_id.with("Refusal"), refusal);
+                            });
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Renames the entry `from` of the map to `to`, keeping its value and position.
+///
+/// Returns the reason and leaves the map unchanged if `to` is refused by `new_key_refusal`.
+fn rename_key(values: &mut IndexMap<String, Value>, from: &str, to: String) -> Result<(), &'static str> {
+    if from == to {
+        return Ok(());
+    }
+    if let Some(refusal) = new_key_refusal(values, &to) {
+        return Err(refusal);
+    }
+    if let Some((idx, _, value)) = values.shift_remove_full(from) {
+        values.shift_insert(idx, to, value);
+    }
+    Ok(())
+}
+
+/// Returns keys of the map in the order they are declared in the descriptor,
+/// followed by undeclared keys in insertion order.
+fn ordered_keys<'a>(values: &IndexMap<String, Value>, declared: impl Iterator<Item = &'a String>) -> Vec<String> {
+    let mut keys: Vec<String> = Vec::with_capacity(values.len());
+    for key in declared {
+        if values.contains_key(key) && !keys.contains(key) {
+            keys.push(key.clone());
+        }
+    }
+
+    let rest: Vec<String> = values.keys().filter(|key| !keys.contains(key)).cloned().collect();
+    keys.extend(rest);
+    keys
+}
+
+/// Limits the nesting of the descriptor edited in schemaless mode,
+/// so that values can't be nested deeper than `max_depth`.
+///
+/// `depth` is the depth of values described by `desc`.
+/// Shows a notice if the limit is reached.
+fn limit_desc_depth(ui: &mut Ui, desc: &mut Desc, depth: usize, max_depth: usize) {
+    let remaining = max_depth.saturating_sub(depth).max(1);
+    desc.truncate_depth(remaining);
+
+    let at_limit = match desc.unwrapped() {
+        Desc::List { .. } | Desc::Map { .. } | Desc::Enum { .. } | Desc::Struct { .. } | Desc::Tuple { .. } => {
+            desc.depth() >= remaining
+        }
+        _ => remaining < 2,
+    };
+
+    if at_limit {
+        ui.weak("max depth").on_hover_text(format!(
+            "Values can't be nested deeper than {max_depth} levels"
+        ));
+    }
+}
+
+/// Reports invalid text kept in the number field `id`.
+fn report_raw_text(ctx: &egui::Context, root: Id, id: Id, path: &str) {
+    if let Some(text) = number::raw_text(ctx, id) {
+        error::record(
+            ctx,
+            root,
+            ProbeError::InvalidInput {
+                path: path.to_owned(),
+                text,
+            },
+        );
+    }
+}
+
+/// Reports text entered into the number field `id` in this frame
+/// to the `on_coerce` hook if it was clamped or rejected.
+fn report_entered_text(ctx: &egui::Context, inherited: &mut Inherited, id: Id, path: &str) {
+    if let Some((text, reason)) = number::take_coerced_text(ctx, id) {
+        inherited.coerced(path, text, reason);
+    }
+}
+
+/// Truncates `elems` to the length of the tuple or pads them with defaults of the missing positions.
+fn fit_tuple(elems: &mut Vec<Value>, descs: &[Desc]) {
+    elems.truncate(descs.len());
+    elems.extend(descs[elems.len()..].iter().map(Desc::default_value));
+}
+
+fn clamp<T: PartialOrd>(value: T, min: Option<T>, max: Option<T>) -> T {
+    match (min, max) {
+        (Some(min), _) if value < min => min,
+        (_, Some(max)) if value > max => max,
+        _ => value,
+    }
+}
+
+/// Rounds `value` to the nearest multiple of `step`, with halfway values rounded away from zero.
+///
+/// Integral steps are applied with integer arithmetic, so integers beyond 2^53 are kept exact.
+fn snap_int(value: i64, step: f64) -> i64 {
+    if step.fract() != 0.0 || step >= i64::MAX as f64 {
+        return ((value as f64 / step).round() * step) as i64;
+    }
+    let (value, step) = (i128::from(value), step as i128);
+    let snapped = (value + value.signum() * (step / 2)) / step * step;
+    snapped.clamp(i64::MIN.into(), i64::MAX.into()) as i64
+}
+
+/// Returns whether the container at `path` shows its advanced values.
+fn shows_advanced(ui: &Ui, root: Id, path: &str) -> bool {
+    ui.ctx()
+        .data(|d| d.get_temp::<bool>(root.with(("ShowAdvanced", path))))
+        .unwrap_or(false)
+}
+
+/// Cuts the text to at most `max` characters.
+fn truncate_chars(text: &mut String, max: usize) {
+    if let Some((idx, _)) = text.char_indices().nth(max) {
+        text.truncate(idx);
+    }
+}
+
+/// Removes leading and trailing whitespace if `trim` is set,
+/// and replaces inner runs of whitespace with a single space if `collapse` is set.
+fn normalize_whitespace(value: &str, trim: bool, collapse: bool) -> String {
+    let value = if trim { value.trim() } else { value };
+    if !collapse {
+        return value.to_owned();
+    }
+
+    let mut result = String::with_capacity(value.len());
+    let mut in_whitespace = false;
+    for c in value.chars() {
+        if c.is_whitespace() {
+            if !in_whitespace {
+                result.push(' ');
+            }
+            in_whitespace = true;
+        } else {
+            result.push(c);
+            in_whitespace = false;
+        }
+    }
+    result
+}
+
+/// Offers to convert list items after the kind of the element descriptor
+/// was changed in schemaless mode.
+///
+/// The offer is shown until accepted or dismissed.
+fn recoerce_offer(ui: &mut Ui, id: Id, changed: bool, desc: &Desc, elems: &mut [Value]) {
+    let mismatched = elems.iter().filter(|elem| elem.kind() != desc.kind()).count();
+
+    let pending = changed || ui.ctx().data(|d| d.get_temp::<bool>(id)).unwrap_or(false);
+    if !pending || mismatched == 0 {
+        ui.ctx().data_mut(|d| d.remove::<bool>(id));
+        return;
+    }
+
+    let lost = elems
+        .iter()
+        .filter(|elem| elem.kind() != desc.kind() && elem.coerce(desc).is_none())
+        .count();
+
+    let mut text = format!("Convert {mismatched} items to {}?", desc.kind());
+    if lost > 0 {
+        text.push_str(&format!(" {lost} will be reset."));
+    }
+    ui.weak(text);
+
+    let convert = ui.small_button("Convert").clicked();
+    if convert {
+        for elem in elems.iter_mut().filter(|elem| elem.kind() != desc.kind()) {
+            *elem = elem.coerce(desc).unwrap_or_else(|| desc.default_value());
+        }
+    }
+
+    let done = convert || ui.small_button("Keep").clicked();
+    ui.ctx().data_mut(|d| {
+        if done {
+            d.remove::<bool>(id);
+        } else {
+            d.insert_temp(id, true);
+        }
+    });
+}
+
+fn invalid_range<T: Display>(ui: &mut Ui, root: Id, path: &str, min: T, max: T) -> Response {
+    error::show(
+        ui,
+        root,
+        ProbeError::InvalidRange {
+            path: path.to_owned(),
+            min: min.to_string(),
+            max: max.to_string(),
+        },
+    )
+}
+
+fn convert_to_string(ui: &mut Ui, inherited: &Inherited, path: &str, value: &Value) -> (Response, Option<Value>) {
+    let mut convert = false;
+    let converted = value.converted(Kind::String).ok();
+
+    let r = ui
+        .horizontal(|ui| {
+            error::show(
+                ui,
+                inherited.root,
+                ProbeError::TypeMismatch {
+                    path: path.to_owned(),
+                    expected: "string",
+                    found: value.kind(),
+                },
+            );
+            if let Some(Value::String(text)) = &converted {
+                if !inherited.read_only && ui.small_button(format!("Convert to {text:?}")).clicked() {
+                    convert = true;
+                }
+            }
+            ui.strong("?");
+        })
+        .response;
+
+    (r, converted.filter(|_| convert))
+}
+
+/// Shows a button that resets a value of unexpected kind.
+///
+/// If `confirm` is set and the reset would discard data,
+/// the first click only arms the button and shows what will be lost,
+/// and the reset happens after the second click.
+///
+/// The reset is reported to the `on_coerce` hook.
+fn reset_button(
+    ui: &mut Ui,
+    inherited: &mut Inherited,
+    id_source: Id,
+    path: &str,
+    text: impl Into<WidgetText>,
+    value: &Value,
+) -> bool {
+    if inherited.read_only {
+        return false;
+    }
+
+    let confirm = inherited.config.confirm_kind_switch;
+    let mut report = || inherited.coerced(path, diff::preview(value), CoercionReason::Reset);
+
+    let lost = match value.data_lost_on_reset() {
+        Some(lost) if confirm => lost,
+        _ => {
+            let reset = ui.small_button(text).clicked();
+            if reset {
+                report();
+            }
+            return reset;
+        }
+    };
+
+    let id = ui.make_persistent_id(id_source.with("Reset"));
+    let armed = ui.ctx().data(|d| d.get_temp::<bool>(id)).unwrap_or(false);
+
+    if !armed {
+        let r = ui.small_button(text).on_hover_text(format!("Discards {lost}"));
+        if r.clicked() {
+            ui.ctx().data_mut(|d| d.insert_temp(id, true));
+        }
+        return false;
+    }
+
+    ui.weak(format!("Discard {lost}?"));
+    let confirmed = ui.small_button("Confirm").clicked();
+    if confirmed || ui.small_button("Cancel").clicked() {
+        ui.ctx().data_mut(|d| d.remove::<bool>(id));
+    }
+    if confirmed {
+        report();
+    }
+    confirmed
+}
+
+/// Shows a button that resets a section to its defaults.
+///
+/// The first click only arms the button and the reset happens after confirmation.
+fn section_reset_button(ui: &mut Ui, id_source: Id) -> bool {
+    let id = ui.make_persistent_id(id_source.with("ResetSection"));
+    let armed = ui.ctx().data(|d| d.get_temp::<bool>(id)).unwrap_or(false);
+
+    if !armed {
+        let r = ui.small_button("⟲").on_hover_text("Reset section to defaults");
+        if r.clicked() {
+            ui.ctx().data_mut(|d| d.insert_temp(id, true));
+        }
+        return false;
+    }
+
+    ui.weak("Reset section?");
+    let confirmed = ui.small_button("Confirm").clicked();
+    if confirmed || ui.small_button("Cancel").clicked() {
+        ui.ctx().data_mut(|d| d.remove::<bool>(id));
+    }
+    confirmed
+}
+
+/// Resets the value to the default of its descriptor.
+///
+/// With `keep_structure` set, as when the structure is locked,
+/// items of lists and maps are kept and reset one by one instead.
+fn reset_section(value: &mut Value, desc: &Desc, keep_structure: bool) {
+    if !keep_structure {
+        *value = desc.default_value();
+        return;
+    }
+
+    match (desc, value) {
+        (Desc::List { elem_desc, .. }, Value::List(elems)) => {
+            if let Some(elem_desc) = elem_desc {
+                for elem in elems {
+                    reset_section(elem, elem_desc, true);
+                }
+            }
+        }
+        (Desc::Map { value_desc, .. }, Value::Map(values)) => {
+            if let Some(value_desc) = value_desc {
+                for value in values.values_mut() {
+                    reset_section(value, value_desc, true);
+                }
+            }
+        }
+        (Desc::Struct { fields }, Value::Map(values)) => {
+            for field in fields {
+                if let Some(value) = values.get_mut(&field.name) {
+                    reset_section(value, &field.desc, true);
+                }
+            }
+        }
+        (Desc::Tuple { elems: descs }, Value::List(elems)) => {
+            for (desc, elem) in descs.iter().zip(elems) {
+                reset_section(elem, desc, true);
+            }
+        }
+        (Desc::Documented { inner, .. } | Desc::Advanced { inner }, value) => reset_section(value, inner, true),
+        (Desc::WithDefault { inner, .. }, value)
+            if matches!(inner.unwrapped(), Desc::List { .. } | Desc::Map { .. } | Desc::Struct { .. } | Desc::Tuple { .. }) =>
+        {
+            reset_section(value, inner, true)
+        }
+        (desc, value) => *value = desc.default_value(),
+    }
+}
+
+/// Shows the button that adds an item to a collection,
+/// disabled with the `limit` shown next to it while adding is refused,
+/// e.g. once the collection or the document size limit is reached.
+fn add_button(ui: &mut Ui, style: &Style, inherited: &Inherited, limit: Option<&str>) -> bool {
+    if inherited.structure_locked() {
+        return false;
+    }
+    let r = ui.add_enabled(limit.is_none(), egui::Button::new(style.add_button_text()).small());
+    if let Some(limit) = limit {
+        ui.weak(limit);
+    }
+    r.clicked()
+}
+
+/// Returns the reason why an entry with `key` can't be added to the map,
+/// so that an existing entry is never overwritten.
+fn new_key_refusal(values: &IndexMap<String, Value>, key: &str) -> Option<&'static str> {
+    if key.is_empty() {
+        Some("key is empty")
+    } else if values.contains_key(key) {
+        Some("key exists")
+    } else {
+        None
+    }
+}
+
+/// Returns the reason why no more items can be added to a collection of `len` items.
+fn add_limit(inherited: &Inherited, len: usize, max: Option<usize>) -> Option<String> {
+    if inherited.structure_locked() {
+        return Some("structure is locked".to_owned());
+    }
+    if !inherited.can_add() {
+        return Some("size limit reached".to_owned());
+    }
+    match max {
+        Some(max) if len >= max => Some(format!("limit of {max} reached")),
+        _ => None,
+    }
+}
+
+/// Reports a collection with more than `max` items, e.g. after a paste,
+/// and offers to keep only the first `max` of them.
+///
+/// Returns `true` if the collection should be truncated.
+fn over_limit(ui: &mut Ui, inherited: &Inherited, path: &str, len: usize, max: usize) -> bool {
+    ui.horizontal(|ui| {
+        error::show(
+            ui,
+            inherited.root,
+            ProbeError::TooLong {
+                path: path.to_owned(),
+                limit: max,
+                found: len,
+            },
+        );
+        !inherited.structure_locked() && ui.small_button(format!("Keep first {max}")).clicked()
+    })
+    .inner
+}
+
+/// Selection of a multi-select list, kept in the temporary data of the list.
+///
+/// Items are identified by ids that move with them,
+/// so the selection survives reordering.
+#[derive(Clone, Default)]
+struct Selection {
+    /// Id of each item of the list, in order.
+    ids: Vec<u64>,
+
+    /// Ids of the selected items.
+    selected: Vec<u64>,
+
+    /// Id given to the next new item.
+    next_id: u64,
+}
+
+impl Selection {
+    /// Loads the selection `id` of a list of `len` items.
+    ///
+    /// Items appended since the selection was stored get new ids,
+    /// and ids of items removed from the end are forgotten.
+    fn load(ui: &Ui, id: Id, len: usize) -> Self {
+        let mut selection: Selection = ui.data(|d| d.get_temp(id)).unwrap_or_default();
+        selection.ids.truncate(len);
+        while selection.ids.len() < len {
+            let new = selection.new_id();
+            selection.ids.push(new);
+        }
+        let Selection { ids, selected, .. } = &mut selection;
+        selected.retain(|id| ids.contains(id));
+        selection
+    }
+
+    fn store(self, ui: &Ui, id: Id) {
+        ui.data_mut(|d| d.insert_temp(id, self));
+    }
+
+    fn new_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn is_selected(&self, idx: usize) -> bool {
+        self.selected.contains(&self.ids[idx])
+    }
+
+    fn set_selected(&mut self, idx: usize, selected: bool) {
+        let id = self.ids[idx];
+        self.selected.retain(|&other| other != id);
+        if selected {
+            self.selected.push(id);
+        }
+    }
+
+    /// Returns the indices of the selected items in ascending order.
+    fn indices(&self) -> Vec<usize> {
+        (0..self.ids.len()).filter(|&idx| self.is_selected(idx)).collect()
+    }
+}
+
+/// Shows the selection toolbar of the multi-select list `id` at `path`
+/// and deletes or duplicates the selected items when requested.
+///
+/// Duplicates are inserted right after their originals and added like new items:
+/// they pass the `on_add` hook, are refused if they repeat the `unique_by` field,
+/// and are only offered while the size limit and `max_len` leave room for all of them.
+/// Selected items are deleted only if at least `min_len` items remain.
+#[allow(clippy::too_many_arguments)]
+fn selection_toolbar(
+    ui: &mut Ui,
+    id: Id,
+    inherited: &Inherited,
+    path: &str,
+    elems: &mut Vec<Value>,
+    max_len: Option<usize>,
+    min_len: usize,
+    unique_by: Option<&str>,
+) {
+    let selection_id = id.with("Selection");
+    let mut selection = Selection::load(ui, selection_id, elems.len());
+    let selected = selection.indices();
+
+    ui.horizontal(|ui| {
+        ui.weak(format!("{} selected", selected.len()));
+
+        let any = !selected.is_empty();
+        let keeps_min = elems.len().saturating_sub(selected.len()) >= min_len;
+        let delete = ui
+            .add_enabled(any && keeps_min, egui::Button::new("Delete selected").small())
+            .on_disabled_hover_text(match keeps_min {
+                true => "Nothing is selected".to_owned(),
+                false => format!("At least {min_len} elements are required"),
+            });
+        if delete.clicked() {
+            let mut idx = 0;
+            elems.retain(|_| {
+                idx += 1;
+                !selected.contains(&(idx - 1))
+            });
+            let Selection { ids, selected, .. } = &mut selection;
+            ids.retain(|id| !selected.contains(id));
+            selected.clear();
+        }
+
+        let limit = add_limit(inherited, elems.len(), max_len).or_else(|| {
+            let max = max_len.filter(|&max| elems.len() + selected.len() > max)?;
+            Some(format!("limit of {max} leaves no room for {} copies", selected.len()))
+        });
+        let duplicate = ui
+            .add_enabled(any && limit.is_none(), egui::Button::new("Duplicate selected").small())
+            .on_disabled_hover_text(limit.as_deref().unwrap_or("Nothing is selected"));
+        if duplicate.clicked() {
+            for &idx in selected.iter().rev() {
+                let item = inherited.copied_item(path, &elems[idx]);
+                if !repeats_unique(ui, id, elems, &item, unique_by) {
+                    elems.insert(idx + 1, item);
+                    let new = selection.new_id();
+                    selection.ids.insert(idx + 1, new);
+                }
+            }
+            selection.selected.clear();
+        }
+
+        if ui.add_enabled(any, egui::Button::new("Clear selection").small()).clicked() {
+            selection.selected.clear();
+        }
+    });
+
+    selection.store(ui, selection_id);
+}
+
+/// Changes the `field` of elements that repeat it of an earlier element,
+/// so that all elements differ.
+///
+/// Strings get the first free numeric suffix, e.g. `id-2`,
+/// and integers the number after the greatest one.
+/// Fields of other kinds are left as is.
+fn make_unique(elems: &mut [Value], field: &str) {
+    let taken = |elems: &[Value], candidate: &Value| {
+        elems
+            .iter()
+            .any(|elem| path::lookup(elem, field).is_some_and(|other| other == candidate))
+    };
+
+    for idx in 0..elems.len() {
+        let Some(value) = path::lookup(&elems[idx], field).cloned() else {
+            continue;
+        };
+        if !taken(&elems[..idx], &value) {
+            continue;
+        }
+
+        let unique = match &value {
+            Value::String(base) => (2..)
+                .map(|n| Value::String(format!("{base}-{n}")))
+                .find(|candidate| !taken(elems, candidate)),
+            Value::Int(_) => elems
+                .iter()
+                .filter_map(|elem| match path::lookup(elem, field) {
+                    Some(Value::Int(n)) => Some(*n),
+                    _ => None,
+                })
+                .max()
+                .and_then(|max| max.checked_add(1))
+                .map(Value::Int),
+            _ => None,
+        };
+
+        if let (Some(unique), Some(value)) = (unique, path::lookup_mut(&mut elems[idx], field)) {
+            *value = unique;
+        }
+    }
+}
+
+/// Returns `true` if the currently focused widget lies within `rect`.
+fn has_focus_within(ui: &Ui, rect: Rect) -> bool {
+    ui.memory(|m| m.focused())
+        .and_then(|id| ui.ctx().read_response(id))
+        .is_some_and(|r| rect.contains_rect(r.rect))
+}
+
+/// Adds `item` to the list unless elements must be unique by the `unique_by` field
+/// and an element with an equal field is already there.
+///
+/// A refused item is reported by `duplicate_notice` of the list `id` for a moment.
+fn push_item(ui: &Ui, id: Id, elems: &mut Vec<Value>, item: Value, unique_by: Option<&str>) {
+    if repeats_unique(ui, id, elems, &item, unique_by) {
+        return;
+    }
+    elems.push(item);
+}
+
+/// Returns `true` if `item` repeats the `unique_by` field of an element of the list `id`,
+/// and shows the duplicate notice of the list if so.
+fn repeats_unique(ui: &Ui, id: Id, elems: &[Value], item: &Value, unique_by: Option<&str>) -> bool {
+    let duplicate = unique_by.is_some_and(|field| {
+        path::lookup(item, field).is_some_and(|new| elems.iter().any(|elem| path::lookup(elem, field) == Some(new)))
+    });
+    if duplicate {
+        let now = ui.input(|i| i.time);
+        ui.data_mut(|d| d.insert_temp(id.with("Duplicate"), now));
+    }
+    duplicate
+}
+
+/// Shows `text` for two seconds after `push_item` refused a duplicate item of the list `id`,
+/// or a map entry `id` refused a rename, e.g. to an existing key.
+fn duplicate_notice(ui: &mut Ui, id: Id, text: &str) {
+    const SHOWN_FOR: f64 = 2.0;
+
+    let Some(refused) = ui.data(|d| d.get_temp::<f64>(id.with("Duplicate"))) else {
+        return;
+    };
+    let elapsed = ui.input(|i| i.time) - refused;
+    if elapsed < SHOWN_FOR {
+        ui.colored_label(ui.visuals().warn_fg_color, text);
+        ui.ctx().request_repaint_after(std::time::Duration::from_secs_f64(SHOWN_FOR - elapsed));
+    }
+}
+
+/// Moves the item at `idx` by `offset` positions, swapping it with its neighbor for `±1`.
+///
+/// Returns the new index of the item, or `None` if it would leave the list.
+fn move_item<T>(elems: &mut [T], idx: usize, offset: isize) -> Option<usize> {
+    let to = idx.checked_add_signed(offset).filter(|&to| to < elems.len())?;
+    elems.swap(idx, to);
+    Some(to)
+}
+
+/// Moves the item at `from` so that it is inserted before the item that was at `to`,
+/// or at the end if `to` is the length of the list.
+///
+/// Returns the new index of the item, or `None` if the indices are out of the list.
+fn drop_item<T>(elems: &mut Vec<T>, from: usize, to: usize) -> Option<usize> {
+    if from >= elems.len() || to > elems.len() {
+        return None;
+    }
+    let item = elems.remove(from);
+    let to = if from < to { to - 1 } else { to };
+    elems.insert(to, item);
+    Some(to)
+}
+
+/// Consumes the shortcut if it is configured and was pressed this frame.
+fn consume_shortcut(ui: &Ui, shortcut: Option<KeyboardShortcut>) -> bool {
+    shortcut.is_some_and(|shortcut| ui.input_mut(|i| i.consume_shortcut(&shortcut)))
+}
+
+/// Modifier to add a delete button to an item probe UI.
+pub struct DeleteMe<'a, T> {
+    pub value: &'a mut T,
+    pub delete: bool,
+}
+
+impl<T> EguiProbe for DeleteMe<'_, T>
+where
+    T: EguiProbe,
+{
+    fn probe(&mut self, ui: &mut egui::Ui, style: &Style) -> egui::Response {
+        ui.horizontal(|ui| {
+            self.value.probe(ui, style);
+            ui.add_space(ui.spacing().item_spacing.x);
+            if ui.small_button(style.remove_button_text()).clicked() {
+                self.delete = true;
+            };
+        })
+        .response
+    }
+
+    fn has_inner(&mut self) -> bool {
+        self.value.has_inner() && !self.delete
+    }
+
+    fn iterate_inner(&mut self, ui: &mut Ui, f: &mut dyn FnMut(&str, &mut Ui, &mut dyn EguiProbe)) {
+        self.value.iterate_inner(ui, f);
+    }
+}
+
+/// Modifier of items of collections shown by `ValueProbe`,
+/// like `DeleteMe` with a delete shortcut, a selection checkbox and a reason to keep the item.
+struct ItemMe<'a, T> {
+    value: &'a mut T,
+    delete: bool,
+
+    /// Shortcut that deletes the item while it has focus.
+    shortcut: Option<KeyboardShortcut>,
+
+    /// Set to `true` if the item had focus when it was probed.
+    focused: bool,
+
+    /// Selection state shown as a checkbox before the item, if set.
+    selected: Option<bool>,
+
+    /// Reason the item can't be deleted, if set.
+    /// The delete button is disabled and shows the reason on hover.
+    keep_reason: Option<String>,
+}
+
+impl<T> EguiProbe for ItemMe<'_, T>
+where
+    T: EguiProbe,
+{
+    fn probe(&mut self, ui: &mut egui::Ui, style: &Style) -> egui::Response {
+        let r = ui
+            .horizontal(|ui| {
+                if let Some(selected) = &mut self.selected {
+                    ui.checkbox(selected, "");
+                }
+                self.value.probe(ui, style);
+                ui.add_space(ui.spacing().item_spacing.x);
+                let button = egui::Button::new(style.remove_button_text()).small();
+                let r = ui.add_enabled(self.keep_reason.is_none(), button);
+                let r = match &self.keep_reason {
+                    Some(reason) => r.on_disabled_hover_text(reason),
+                    None => r,
+                };
+                if r.clicked() {
+                    self.delete = true;
+                };
+            })
+            .response;
+
+        self.focused = has_focus_within(ui, r.rect);
+        if self.focused && self.keep_reason.is_none() && consume_shortcut(ui, self.shortcut) {
+            self.delete = true;
+        }
+        r
+    }
+
+    fn has_inner(&mut self) -> bool {
+        self.value.has_inner() && !self.delete
+    }
+
+    fn iterate_inner(&mut self, ui: &mut Ui, f: &mut dyn FnMut(&str, &mut Ui, &mut dyn EguiProbe)) {
+        self.value.iterate_inner(ui, f);
+    }
+}
+
+/// Modifier to add buttons moving an item of a list up and down.
+pub struct ReorderMe<'a, T> {
+    pub value: &'a mut T,
+
+    /// Whether the item can move up, i.e. is not the first one.
+    pub can_move_up: bool,
+
+    /// Whether the item can move down, i.e. is not the last one.
+    pub can_move_down: bool,
+
+    /// Set to `-1` or `1` if the item should move up or down.
+    pub offset: isize,
+
+    /// Id of the list and index of the item, if the item can be dragged by its grip.
+    pub drag: Option<(Id, usize)>,
+
+    /// Set to `(from, to)` if an item of the same list was dropped onto this one,
+    /// where `to` is the index the dragged item should be inserted before.
+    pub dropped: Option<(usize, usize)>,
+}
+
+/// Modifier to add a field editing the key of a map entry.
+pub struct RenameMe<'a, T> {
+    pub value: &'a mut T,
+
+    /// Current key of the entry.
+    pub key: &'a str,
+
+    /// Id to keep the key while it is edited.
+    pub id: Id,
+
+    /// Set to the new key when the field loses focus with a changed key.
+    pub renamed: Option<String>,
+}
+
+impl<T> EguiProbe for RenameMe<'_, T>
+where
+    T: EguiProbe,
+{
+    fn probe(&mut self, ui: &mut egui::Ui, style: &Style) -> egui::Response {
+        ui.horizontal(|ui| {
+            let mut text = ui.data(|d| d.get_temp::<String>(self.id)).unwrap_or_else(|| self.key.to_owned());
+            let r = ui.add(egui::TextEdit::singleline(&mut text).desired_width(80.0)).on_hover_text("Key");
+            if r.lost_focus() {
+                ui.data_mut(|d| d.remove::<String>(self.id));
+                if text != self.key {
+                    self.renamed = Some(text);
+                }
+            } else if r.has_focus() {
+                ui.data_mut(|d| d.insert_temp(self.id, text));
+            }
+            let refusal = ui.data(|d| d.get_temp::<&'static str>(self.id.with("Refusal")));
+            duplicate_notice(ui, self.id, refusal.unwrap_or("key exists"));
+            self.value.probe(ui, style);
+        })
+        .response
+    }
+
+    fn has_inner(&mut self) -> bool {
+        self.value.has_inner()
+    }
+
+    fn iterate_inner(&mut self, ui: &mut Ui, f: &mut dyn FnMut(&str, &mut Ui, &mut dyn EguiProbe)) {
+        self.value.iterate_inner(ui, f);
+    }
+}
+
+/// Item of a list dragged by its grip.
+struct DraggedItem {
+    list: Id,
+    idx: usize,
+}
+
+impl<T> EguiProbe for ReorderMe<'_, T>
+where
+    T: EguiProbe,
+{
+    fn probe(&mut self, ui: &mut egui::Ui, style: &Style) -> egui::Response {
+        let r = ui
+            .horizontal(|ui| {
+                if let Some((list, idx)) = self.drag {
+                    ui.dnd_drag_source(list.with(("Grip", idx)), DraggedItem { list, idx }, |ui| {
+                        ui.weak("☰").on_hover_text("Drag to reorder");
+                    });
+                }
+                self.value.probe(ui, style);
+                ui.add_space(ui.spacing().item_spacing.x);
+                if ui.add_enabled(self.can_move_up, egui::Button::new("⬆").small()).on_hover_text("Move up").clicked() {
+                    self.offset = -1;
+                }
+                if ui.add_enabled(self.can_move_down, egui::Button::new("⬇").small()).on_hover_text("Move down").clicked() {
+                    self.offset = 1;
+                }
+            })
+            .response;
+
+        if let Some((list, idx)) = self.drag {
+            let same_list = |item: &DraggedItem| item.list == list;
+            let pointer_y = ui.input(|i| i.pointer.interact_pos()).map_or(r.rect.center().y, |pos| pos.y);
+            let to = if pointer_y < r.rect.center().y { idx } else { idx + 1 };
+
+            if r.dnd_hover_payload::<DraggedItem>().is_some_and(|item| same_list(&item)) {
+                let y = if to == idx { r.rect.top() } else { r.rect.bottom() };
+                ui.painter().hline(r.rect.x_range(), y, ui.visuals().selection.stroke);
+            }
+            if let Some(item) = r.dnd_release_payload::<DraggedItem>().filter(|item| same_list(item)) {
+                self.dropped = Some((item.idx, to));
+            }
+        }
+        r
+    }
+
+    fn has_inner(&mut self) -> bool {
+        self.value.has_inner()
+    }
+
+    fn iterate_inner(&mut self, ui: &mut Ui, f: &mut dyn FnMut(&str, &mut Ui, &mut dyn EguiProbe)) {
+        self.value.iterate_inner(ui, f);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Shows `add_contents` for a few frames and returns the texts painted in the last one.
+    pub(crate) fn shown_texts(add_contents: impl FnMut(&mut Ui)) -> Vec<String> {
+        shown_texts_in(&egui::Context::default(), add_contents)
+    }
+
+    /// Like `shown_texts`, but keeps the state of `ctx` between calls.
+    pub(crate) fn shown_texts_in(ctx: &egui::Context, mut add_contents: impl FnMut(&mut Ui)) -> Vec<String> {
+        let mut texts = Vec::new();
+        for _ in 0..3 {
+            let output = ctx.run(Default::default(), |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| add_contents(ui));
+            });
+            texts.clear();
+            for clipped in &output.shapes {
+                collect_texts(&clipped.shape, &mut texts);
+            }
+        }
+        texts
+    }
+
+    fn collect_texts(shape: &egui::Shape, texts: &mut Vec<String>) {
+        match shape {
+            egui::Shape::Text(text) => texts.push(text.galley.job.text.clone()),
+            egui::Shape::Vec(shapes) => shapes.iter().for_each(|shape| collect_texts(shape, texts)),
+            _ => {}
+        }
+    }
+
+    /// Shows the contents until they settle, clicks the first text equal to `text`
+    /// and returns the texts shown afterwards, or `None` if `text` isn't shown.
+    pub(crate) fn click_text(ctx: &egui::Context, text: &str, add_contents: impl FnMut(&mut Ui)) -> Option<Vec<String>> {
+        interact_with_text(ctx, text, 0, |pos, _| click(pos), add_contents)
+    }
+
+    /// Clicks the unlabeled checkbox to the left of the `nth` text equal to `text`
+    /// and returns the texts shown afterwards, or `None` if `text` isn't shown.
+    pub(crate) fn click_checkbox_before_text(
+        ctx: &egui::Context,
+        text: &str,
+        nth: usize,
+        add_contents: impl FnMut(&mut Ui),
+    ) -> Option<Vec<String>> {
+        let spacing = ctx.style().spacing.clone();
+        let events = |pos: egui::Pos2, width: f32| {
+            let offset = width / 2.0 + spacing.item_spacing.x + spacing.icon_width / 2.0;
+            click(pos - egui::vec2(offset, 0.0))
+        };
+        interact_with_text(ctx, text, nth, events, add_contents)
+    }
+
+    /// Clicks the `nth` text equal to `text`, e.g. a text field, replaces its contents with `typed`
+    /// and presses enter, returning the texts shown afterwards, or `None` if `text` isn't shown.
+    pub(crate) fn type_into_text(
+        ctx: &egui::Context,
+        text: &str,
+        nth: usize,
+        typed: &str,
+        add_contents: impl FnMut(&mut Ui),
+    ) -> Option<Vec<String>> {
+        type_text(ctx, text, nth, egui::Event::Text(typed.to_owned()), true, add_contents)
+    }
+
+    /// Like `type_into_text`, but pastes `pasted` instead of typing it.
+    fn paste_into_text(
+        ctx: &egui::Context,
+        text: &str,
+        pasted: &str,
+        add_contents: impl FnMut(&mut Ui),
+    ) -> Option<Vec<String>> {
+        type_text(ctx, text, 0, egui::Event::Paste(pasted.to_owned()), true, add_contents)
+    }
+
+    fn type_text(
+        ctx: &egui::Context,
+        text: &str,
+        nth: usize,
+        input: egui::Event,
+        enter: bool,
+        add_contents: impl FnMut(&mut Ui),
+    ) -> Option<Vec<String>> {
+        let key = |key, modifiers| egui::Event::Key {
+            key,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers,
+        };
+        let events = |pos, _| {
+            let mut events = click(pos);
+            events.push(vec![key(egui::Key::A, egui::Modifiers::COMMAND)]);
+            events.push(vec![input]);
+            if enter {
+                events.push(vec![key(egui::Key::Enter, egui::Modifiers::NONE)]);
+            }
+            events
+        };
+        interact_with_text(ctx, text, nth, events, add_contents)
+    }
+
+    /// Like `type_into_text`, but doesn't press enter, so the field keeps focus.
+    fn type_keeping_focus(
+        ctx: &egui::Context,
+        text: &str,
+        typed: &str,
+        add_contents: impl FnMut(&mut Ui),
+    ) -> Option<Vec<String>> {
+        type_text(ctx, text, 0, egui::Event::Text(typed.to_owned()), false, add_contents)
+    }
+
+    /// Drags the first text equal to `text`, e.g. a drag value, by `delta` points
+    /// and returns the texts shown afterwards, or `None` if `text` isn't shown.
+    ///
+    /// The drag starts only once `delta` exceeds the click distance of egui.
+    pub(crate) fn drag_text(
+        ctx: &egui::Context,
+        text: &str,
+        delta: egui::Vec2,
+        add_contents: impl FnMut(&mut Ui),
+    ) -> Option<Vec<String>> {
+        let events = |pos, _| {
+            vec![
+                vec![egui::Event::PointerMoved(pos), pointer_button(pos, true)],
+                vec![egui::Event::PointerMoved(pos + delta / 2.0)],
+                vec![egui::Event::PointerMoved(pos + delta)],
+                vec![pointer_button(pos + delta, false)],
+            ]
+        };
+        interact_with_text(ctx, text, 0, events, add_contents)
+    }
+
+    fn pointer_button(pos: egui::Pos2, pressed: bool) -> egui::Event {
+        egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: Default::default(),
+        }
+    }
+
+    fn click(pos: egui::Pos2) -> Vec<Vec<egui::Event>> {
+        vec![
+            vec![egui::Event::PointerMoved(pos), pointer_button(pos, true)],
+            vec![pointer_button(pos, false)],
+        ]
+    }
+
+    /// Finds the `nth` text equal to `text` once the contents settle,
+    /// then sends each batch of `events` for its center and width in its own frame.
+    fn interact_with_text(
+        ctx: &egui::Context,
+        text: &str,
+        nth: usize,
+        events: impl FnOnce(egui::Pos2, f32) -> Vec<Vec<egui::Event>>,
+        mut add_contents: impl FnMut(&mut Ui),
+    ) -> Option<Vec<String>> {
+        let mut run = |events: Vec<egui::Event>| {
+            let input = egui::RawInput {
+                events,
+                ..Default::default()
+            };
+            ctx.run(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| add_contents(ui));
+            })
+        };
+
+        let mut output = run(Vec::new());
+        for _ in 0..2 {
+            output = run(Vec::new());
+        }
+        let (pos, width) = find_text(&output, text, nth)?;
+
+        for events in events(pos, width) {
+            run(events);
+        }
+        run(vec![egui::Event::PointerGone]);
+
+        let texts = shown_texts_in(ctx, add_contents);
+        Some(texts)
+    }
+
+    /// Returns the center and width of the `nth` text equal to `text` painted in `output`.
+    fn find_text(output: &egui::FullOutput, text: &str, nth: usize) -> Option<(egui::Pos2, f32)> {
+        fn find(shape: &egui::Shape, text: &str, found: &mut Vec<(egui::Pos2, f32)>) {
+            match shape {
+                egui::Shape::Text(shape) if shape.galley.job.text == text => {
+                    found.push((shape.pos + shape.galley.rect.center().to_vec2(), shape.galley.rect.width()));
+                }
+                egui::Shape::Vec(shapes) => shapes.iter().for_each(|shape| find(shape, text, found)),
+                _ => {}
+            }
+        }
+
+        let mut found = Vec::new();
+        for clipped in &output.shapes {
+            find(&clipped.shape, text, &mut found);
+        }
+        found.get(nth).copied()
+    }
+
+    /// Keeps the pointer over the first text equal to `text` without delaying tooltips
+    /// and returns the texts shown meanwhile, or `None` if `text` isn't shown.
+    fn hover_text(text: &str, mut add_contents: impl FnMut(&mut Ui)) -> Option<Vec<String>> {
+        let ctx = egui::Context::default();
+        ctx.style_mut(|style| {
+            style.interaction.tooltip_delay = 0.0;
+            style.interaction.show_tooltips_only_when_still = false;
+        });
+        shown_texts_in(&ctx, &mut add_contents);
+
+        let output = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| add_contents(ui));
+        });
+        let (pos, _) = find_text(&output, text, 0)?;
+
+        let mut texts = Vec::new();
+        for _ in 0..3 {
+            let input = egui::RawInput {
+                events: vec![egui::Event::PointerMoved(pos)],
+                ..Default::default()
+            };
+            let output = ctx.run(input, |ctx| {
+                egui::CentralPanel::default().show(ctx, |ui| add_contents(ui));
+            });
+            texts.clear();
+            for clipped in &output.shapes {
+                collect_texts(&clipped.shape, &mut texts);
+            }
+        }
+        Some(texts)
+    }
+
+    #[test]
+    fn hidden_subtree_is_not_shown_and_kept() {
+        let secret: Value = [("token".to_owned(), Value::from("abc"))].into_iter().collect();
+        let mut value: Value = [("name".to_owned(), Value::from("x")), ("secret".to_owned(), secret)]
+            .into_iter()
+            .collect();
+        let original = value.clone();
+        let desc = Desc::from_value(&value);
+        let visible = |path: &str| !path.starts_with("secret");
+
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").with_visibility(&visible).show_flat(ui);
+        });
+
+        assert!(texts.iter().any(|text| text == "name"));
+        assert!(!texts.iter().any(|text| text == "secret" || text == "token" || text == "abc"));
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn retain_matching_removes_nulls() {
+        let inner: Value = [("a".to_owned(), Value::Null), ("b".to_owned(), Value::from(1))].into_iter().collect();
+        let mut value: Value = [
+            ("x".to_owned(), Value::Null),
+            ("list".to_owned(), Value::from(vec![Value::Null, Value::from(true), Value::Null, inner])),
+        ]
+        .into_iter()
+        .collect();
+
+        let paths = std::cell::RefCell::new(Vec::new());
+        value.retain_matching(&|path, value| {
+            paths.borrow_mut().push(path.to_owned());
+            !value.is_null()
+        });
+
+        let inner: Value = [("b".to_owned(), Value::from(1))].into_iter().collect();
+        let expected: Value = [("list".to_owned(), Value::from(vec![Value::from(true), inner]))].into_iter().collect();
+        assert_eq!(value, expected);
+        assert_eq!(paths.into_inner(), ["x", "list[0]", "list[1]", "list[2]", "list[3].a", "list[3].b", "list[3]", "list"]);
+    }
+
+    fn list_of(elem: Desc) -> Desc {
+        Desc::List {
+            elem_desc: Some(Box::new(elem)),
+            empty_hint: None,
+            collapsed_by_default: false,
+            min_len: None,
+            max_len: None,
+            multi_select: false,
+            unique_by: None,
+        }
+    }
+
+    #[test]
+    fn nesting_schemaless_desc_is_capped() {
+        let mut desc = Desc::Bool;
+        for _ in 0..50 {
+            desc = list_of(desc);
+            desc.truncate_depth(5);
+            assert!(desc.depth() <= 5);
+        }
+        assert_eq!(desc.depth(), 5);
+
+        let mut fits = list_of(list_of(Desc::Bool));
+        assert!(!fits.truncate_depth(5));
+        assert_eq!(fits, list_of(list_of(Desc::Bool)));
+    }
+
+    #[test]
+    fn desc_depth_limit_shows_notice() {
+        let texts = shown_texts(|ui| {
+            let mut desc = list_of(list_of(list_of(Desc::Bool)));
+            limit_desc_depth(ui, &mut desc, 1, 3);
+            assert_eq!(desc.depth(), 2);
+        });
+        assert!(texts.iter().any(|text| text == "max depth"));
+
+        let texts = shown_texts(|ui| {
+            let mut desc = list_of(Desc::Bool);
+            limit_desc_depth(ui, &mut desc, 0, 16);
+        });
+        assert!(!texts.iter().any(|text| text == "max depth"));
+    }
+
+    #[test]
+    fn type_histogram_counts_kinds() {
+        let list = Value::from(vec![Value::from(1), Value::from("a"), Value::from(2), Value::Null, Value::from(0.5)]);
+        let histogram = list.type_histogram();
+        assert_eq!(histogram.len(), 4);
+        assert_eq!(histogram["int"], 2);
+        assert_eq!(histogram["string"], 1);
+        assert_eq!(histogram["null"], 1);
+        assert_eq!(histogram["float"], 1);
+
+        let map: Value = [("a".to_owned(), Value::from(true)), ("b".to_owned(), Value::from(false))].into_iter().collect();
+        assert_eq!(map.type_histogram()["bool"], 2);
+
+        assert!(Value::from(1).type_histogram().is_empty());
+        assert!(Value::from(Vec::new()).type_histogram().is_empty());
+    }
+
+
+    #[test]
+    fn advanced_fields_are_hidden_until_shown() {
+        let desc = Desc::Struct {
+            fields: vec![
+                StructField::new("name", Desc::from_value(&Value::from("x"))),
+                StructField::new("debug", Desc::Bool.advanced()),
+            ],
+        };
+        let mut value = desc.default_value();
+        let original = value.clone();
+
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert!(texts.iter().any(|text| text == "Show advanced"));
+        assert!(texts.iter().any(|text| text == "name"));
+        assert!(!texts.iter().any(|text| text == "debug"));
+        assert_eq!(value, original);
+
+        let texts = shown_texts(|ui| {
+            let id = Id::new("value").with(("ShowAdvanced", ""));
+            ui.ctx().data_mut(|d| d.insert_temp(id, true));
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert!(texts.iter().any(|text| text == "debug"));
+    }
+
+    #[test]
+    fn advanced_mark_is_transparent() {
+        let desc = Desc::Bool.advanced();
+        assert!(desc.is_advanced());
+        assert_eq!(desc.kind(), "bool");
+        assert_eq!(desc.default_value(), Value::Bool(false));
+        assert_eq!(desc.validate(&Value::Bool(true)), Ok(()));
+        assert!(desc.merged(&Desc::Bool).is_advanced());
+        assert!(Desc::Bool.merged(&desc).is_advanced());
+        assert!(!Desc::Bool.is_advanced());
+    }
+
+
+    #[test]
+    fn size_limit_blocks_adds_at_cap() {
+        let config = ProbeConfig {
+            size_limit: Some(SizeLimit::Nodes(3)),
+            ..ProbeConfig::DEFAULT
+        };
+        let desc = Desc::from_value(&Value::from(vec![Value::from(1)]));
+        let add_refusal = |len: usize| {
+            let mut value = Value::from(vec![Value::from(1); len]);
+            let mut refusal = None;
+            shown_texts(|ui| {
+                let mut probe = ValueProbe::new(Some(&desc), &mut value, "value").with_config(&config);
+                probe.prepare_root(ui);
+                refusal = add_limit(&probe.inherited, len, None);
+            });
+            refusal
+        };
+
+        // The list and its elements are counted, so two elements reach the cap of three nodes.
+        assert_eq!(add_refusal(1), None);
+        assert_eq!(add_refusal(2).as_deref(), Some("size limit reached"));
+        assert_eq!(add_refusal(1), None);
+    }
+
+
+    #[test]
+    fn padded_paste_is_normalized() {
+        let pasted = "  hello \t  world\n";
+        assert_eq!(normalize_whitespace(pasted, true, false), "hello \t  world");
+        assert_eq!(normalize_whitespace(pasted, false, true), " hello world ");
+        assert_eq!(normalize_whitespace(pasted, true, true), "hello world");
+        assert_eq!(normalize_whitespace(pasted, false, false), pasted);
+    }
+
+
+    #[test]
+    fn over_limit_paste_offers_truncation() {
+        let desc = Desc::List {
+            elem_desc: Some(Box::new(Desc::from_value(&Value::from(1)))),
+            empty_hint: None,
+            collapsed_by_default: false,
+            min_len: None,
+            max_len: Some(3),
+            multi_select: false,
+            unique_by: None,
+        };
+        // A pasted list longer than allowed.
+        let mut value = Value::from(vec![Value::from(1); 5]);
+
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert!(texts.iter().any(|text| text == "Keep first 3"));
+        assert_eq!(value, Value::from(vec![Value::from(1); 5]));
+
+        let errors = desc.validate(&value).unwrap_err();
+        assert_eq!(errors[0].to_string(), "5 items would exceed limit of 3");
+
+        let probe = ValueProbe::new(Some(&desc), &mut value, "value");
+        assert_eq!(add_limit(&probe.inherited, 3, Some(3)).as_deref(), Some("limit of 3 reached"));
+    }
+
+
+    #[test]
+    fn value_equality_is_structural() {
+        let ab: Value = [("a".to_owned(), Value::Int(1)), ("b".to_owned(), Value::Null)].into_iter().collect();
+        let ba: Value = [("b".to_owned(), Value::Null), ("a".to_owned(), Value::Int(1))].into_iter().collect();
+        assert_eq!(ab, ba);
+        assert_ne!(ab, [("a".to_owned(), Value::Int(1))].into_iter().collect());
+
+        assert_ne!(Value::Int(1), Value::Float(1.0));
+        assert_ne!(Value::Int(1), Value::Uint(1));
+        assert_ne!(Value::from(vec![Value::Int(1), Value::Int(2)]), Value::from(vec![Value::Int(2), Value::Int(1)]));
+        assert_ne!(Value::Float(f64::NAN), Value::Float(f64::NAN));
+    }
+
+
+    #[test]
+    fn debug_sorts_map_keys() {
+        let value: Value = [
+            ("b".to_owned(), Value::from(vec![Value::Int(42), Value::Null])),
+            ("a".to_owned(), Value::Bool(true)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(format!("{value:?}"), r#"Map({"a": Bool(true), "b": List([Int(42), Null])})"#);
+
+        let variant = Value::Enum {
+            variant: "Some".to_owned(),
+            value: Box::new(Value::from("x")),
+        };
+        assert_eq!(format!("{variant:?}"), r#"Enum { variant: "Some", value: String("x") }"#);
+    }
+
+
+    #[test]
+    fn struct_fills_missing_fields_and_keeps_extra() {
+        let desc = Desc::Struct {
+            fields: vec![
+                StructField::new("name", Desc::from_value(&Value::from("x"))),
+                StructField::new("enabled", Desc::Bool),
+            ],
+        };
+        let mut value: Value = [("name".to_owned(), Value::from("a")), ("old".to_owned(), Value::Int(1))]
+            .into_iter()
+            .collect();
+
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+
+        let expected: Value = [
+            ("name".to_owned(), Value::from("a")),
+            ("old".to_owned(), Value::Int(1)),
+            ("enabled".to_owned(), Value::Bool(false)),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(value, expected);
+        assert!(texts.iter().any(|text| text == "Drop extra field"));
+        assert!(!texts.iter().any(|text| text == "old"));
+    }
+
+
+    #[test]
+    fn section_reset_restores_defaults() {
+        let desc = Desc::Struct {
+            fields: vec![
+                StructField::new("retries", Desc::from_value(&Value::Int(0)).with_default(Value::Int(3)).unwrap()),
+                StructField::new("tags", Desc::from_value(&Value::from(vec![Value::from("x")]))),
+            ],
+        };
+        let edited: Value = [
+            ("retries".to_owned(), Value::Int(9)),
+            ("tags".to_owned(), Value::from(vec![Value::from("a"), Value::from("b")])),
+        ]
+        .into_iter()
+        .collect();
+
+        let mut value = edited.clone();
+        reset_section(&mut value, &desc, false);
+        assert_eq!(value, desc.default_value());
+        assert_eq!(path::lookup(&value, "retries"), Some(&Value::Int(3)));
+
+        // With locked structure the list keeps its items, which are reset one by one.
+        let mut value = edited;
+        reset_section(&mut value, &desc, true);
+        assert_eq!(path::lookup(&value, "retries"), Some(&Value::Int(3)));
+        assert_eq!(path::lookup(&value, "tags"), Some(&Value::from(vec![Value::from(""), Value::from("")])));
+    }
+
+
+    pub(crate) fn int_range(min: Option<i64>, max: Option<i64>) -> Desc {
+        Desc::Int {
+            min,
+            max,
+            min_path: None,
+            max_path: None,
+            humanize: None,
+            stepper: None,
+            step: None,
+        }
+    }
+
+    fn float_range(min: Option<f64>, max: Option<f64>) -> Desc {
+        Desc::Float {
+            min,
+            max,
+            min_path: None,
+            max_path: None,
+            stepper: None,
+            step: None,
+        }
+    }
+
+    #[test]
+    fn out_of_range_numbers_offer_clamp() {
+        let cases = [
+            (int_range(Some(0), Some(10)), Value::Int(-5), "Clamp to 0"),
+            (int_range(Some(0), Some(10)), Value::Int(50), "Clamp to 10"),
+            (float_range(Some(1.5), None), Value::Float(0.5), "Clamp to 1.5"),
+            (float_range(None, Some(2.5)), Value::Float(7.0), "Clamp to 2.5"),
+        ];
+        for (desc, value, button) in cases {
+            let mut shown = value.clone();
+            let ctx = egui::Context::default();
+            let texts = shown_texts_in(&ctx, |ui| {
+                ValueProbe::new(Some(&desc), &mut shown, "value").show_flat(ui);
+            });
+
+            assert!(texts.iter().any(|text| text == button), "{button} not in {texts:?}");
+            assert_eq!(shown, value, "the value is only clamped on click");
+        }
+    }
+
+    #[test]
+    fn in_range_numbers_offer_no_clamp() {
+        let desc = int_range(Some(0), Some(10));
+        let mut value = Value::Int(5);
+
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+
+        assert!(!texts.iter().any(|text| text.starts_with("Clamp to")));
+    }
+
+
+    pub(crate) fn string_desc(variants: Option<Vec<Variant>>, min_len: Option<usize>, pattern: Option<&str>) -> Desc {
+        Desc::String {
+            variants,
+            trim: false,
+            collapse_whitespace: false,
+            min_len,
+            max_len: None,
+            pattern: pattern.map(str::to_owned),
+            multiline: false,
+        }
+    }
+
+    #[test]
+    fn string_default_is_empty_or_first_variant() {
+        let required = string_desc(None, Some(3), Some("[a-z]+"));
+        assert_eq!(required.default_value(), Value::from(""));
+        assert!(matches!(
+            &required.validate(&required.default_value()).unwrap_err()[..],
+            [ProbeError::TextLength { min: Some(3), found: 0, .. }, ..]
+        ));
+
+        let choice = string_desc(Some(vec![Variant::new("low"), Variant::new("high")]), Some(10), None);
+        assert_eq!(choice.default_value(), Value::from("low"));
+        assert_eq!(choice.validate(&choice.default_value()), Ok(()));
+    }
+
+
+    #[test]
+    fn short_tuple_is_padded_to_valid() {
+        let descs = vec![Desc::Bool, int_range(Some(1), None), string_desc(None, None, None)];
+        let desc = Desc::Tuple { elems: descs.clone() };
+        let mut value = Value::List(vec![Value::Bool(true)]);
+        assert!(matches!(
+            &desc.validate(&value).unwrap_err()[..],
+            [ProbeError::WrongLength { expected: 3, found: 1, .. }]
+        ));
+
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert!(texts.iter().any(|text| text == "Pad with defaults"));
+
+        let Value::List(elems) = &mut value else { unreachable!() };
+        fit_tuple(elems, &descs);
+        assert_eq!(value, Value::List(vec![Value::Bool(true), Value::Int(1), Value::from("")]));
+        assert_eq!(desc.validate(&value), Ok(()));
+    }
+
+    #[test]
+    fn long_tuple_is_truncated() {
+        let descs = [Desc::Bool];
+        let mut elems = vec![Value::Bool(true), Value::Int(2)];
+
+        fit_tuple(&mut elems, &descs);
+
+        assert_eq!(elems, [Value::Bool(true)]);
+    }
+
+
+    #[test]
+    fn clamp_button_writes_clamped_value() {
+        let desc = int_range(Some(0), Some(10));
+        let mut value = Value::Int(50);
+
+        let texts = click_text(&egui::Context::default(), "Clamp to 10", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+
+        assert!(texts.is_some());
+        assert_eq!(value, Value::Int(10));
+    }
+
+
+    pub(crate) fn list_desc(elem: Desc, min_len: Option<usize>, max_len: Option<usize>) -> Desc {
+        Desc::List {
+            elem_desc: Some(Box::new(elem)),
+            empty_hint: None,
+            collapsed_by_default: false,
+            min_len,
+            max_len,
+            multi_select: false,
+            unique_by: None,
+        }
+    }
+
+    #[test]
+    fn list_at_max_len_does_not_grow() {
+        let desc = list_desc(Desc::Bool, None, Some(2));
+        let add = |len: usize| {
+            let mut value = Value::List(vec![Value::Bool(true); len]);
+            let texts = click_text(&egui::Context::default(), "+", |ui| {
+                ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+            });
+            assert!(texts.is_some());
+            value
+        };
+
+        assert_eq!(add(1), Value::List(vec![Value::Bool(true), Value::Bool(false)]));
+        assert_eq!(add(2), Value::List(vec![Value::Bool(true); 2]));
+    }
+
+    #[test]
+    fn list_min_len_fills_defaults() {
+        let desc = list_desc(int_range(Some(3), None), Some(2), None);
+        assert_eq!(desc.default_value(), Value::List(vec![Value::Int(3); 2]));
+
+        let mut value = Value::List(Vec::new());
+        shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert_eq!(value, Value::List(vec![Value::Int(3); 2]));
+    }
+
+
+    #[test]
+    fn nested_values_compare_deeply() {
+        let nested = |leaf: Value| -> Value {
+            let inner: Value = [("x".to_owned(), leaf), ("y".to_owned(), Value::Bool(true))].into_iter().collect();
+            [("items".to_owned(), Value::from(vec![inner, Value::Null]))].into_iter().collect()
+        };
+        assert_eq!(nested(Value::Float(1.5)), nested(Value::Float(1.5)));
+        assert_ne!(nested(Value::Float(1.5)), nested(Value::Float(2.5)));
+        assert_ne!(nested(Value::Float(f64::NAN)), nested(Value::Float(f64::NAN)));
+        assert_eq!(Value::Float(0.0), Value::Float(-0.0));
+
+        let variant = |name: &str, value: Value| Value::Enum {
+            variant: name.to_owned(),
+            value: Box::new(value),
+        };
+        assert_eq!(variant("a", Value::Int(1)), variant("a", Value::Int(1)));
+        assert_ne!(variant("a", Value::Int(1)), variant("b", Value::Int(1)));
+        assert_ne!(variant("a", Value::Int(1)), variant("a", Value::Int(2)));
+    }
+
+
+    fn elem_desc(desc: &Desc) -> Option<&Desc> {
+        match desc {
+            Desc::List { elem_desc, .. } => elem_desc.as_deref(),
+            Desc::Map { value_desc, .. } => value_desc.as_deref(),
+            _ => panic!("not a collection: {desc:?}"),
+        }
+    }
+
+    #[test]
+    fn homogeneous_list_infers_elem_desc() {
+        let desc = Desc::from_value(&Value::from(vec![Value::Int(1), Value::Int(2)]));
+        assert_eq!(elem_desc(&desc), Some(&int_range(None, None)));
+
+        let desc = Desc::from_value(&Value::from(vec![Value::from("a"), Value::from("b\nc")]));
+        assert!(matches!(elem_desc(&desc), Some(Desc::String { multiline: true, .. })));
+
+        let desc = Desc::from_value(&Value::from(vec![Value::Null, Value::Bool(true)]));
+        assert!(matches!(elem_desc(&desc), Some(Desc::Optional { inner }) if **inner == Desc::Bool));
+    }
+
+    #[test]
+    fn heterogeneous_list_infers_no_elem_desc() {
+        let desc = Desc::from_value(&Value::from(vec![Value::Int(1), Value::from("a")]));
+        assert_eq!(elem_desc(&desc), None);
+
+        let desc = Desc::from_value(&Value::from(vec![Value::Int(1), Value::Uint(1)]));
+        assert_eq!(elem_desc(&desc), None);
+    }
+
+    #[test]
+    fn map_infers_shared_value_desc() {
+        let same: Value = [("a".to_owned(), Value::Bool(true)), ("b".to_owned(), Value::Bool(false))]
+            .into_iter()
+            .collect();
+        assert_eq!(elem_desc(&Desc::from_value(&same)), Some(&Desc::Bool));
+
+        let mixed: Value = [("a".to_owned(), Value::Bool(true)), ("b".to_owned(), Value::Int(1))]
+            .into_iter()
+            .collect();
+        assert_eq!(elem_desc(&Desc::from_value(&mixed)), None);
+    }
+
+    #[test]
+    fn inferred_desc_validates_its_value() {
+        let value: Value = [
+            ("name".to_owned(), Value::from("x")),
+            ("ports".to_owned(), Value::from(vec![Value::Uint(80), Value::Uint(443)])),
+            ("color".to_owned(), Value::Color([1, 2, 3, 4])),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(Desc::from_value(&value).validate(&value), Ok(()));
+    }
+
+
+    #[cfg(feature = "regex")]
+    #[test]
+    fn string_pattern_flags_mismatch() {
+        let desc = string_desc(None, None, Some("[a-z]+"));
+        assert_eq!(desc.validate(&Value::from("abc")), Ok(()));
+        assert!(matches!(
+            &desc.validate(&Value::from("ABC")).unwrap_err()[..],
+            [ProbeError::PatternMismatch { value, .. }] if value == "ABC"
+        ));
+
+        let invalid = string_desc(None, None, Some("[a-"));
+        assert!(matches!(
+            &invalid.validate(&Value::from("a")).unwrap_err()[..],
+            [ProbeError::InvalidPattern { .. }]
+        ));
+    }
+
+
+    fn ints(values: &[i64]) -> Vec<Value> {
+        values.iter().copied().map(Value::Int).collect()
+    }
+
+    #[test]
+    fn move_item_swaps_with_neighbor() {
+        let mut elems = ints(&[1, 2, 3]);
+        assert_eq!(move_item(&mut elems, 0, 1), Some(1));
+        assert_eq!(elems, ints(&[2, 1, 3]));
+        assert_eq!(move_item(&mut elems, 2, -1), Some(1));
+        assert_eq!(elems, ints(&[2, 3, 1]));
+    }
+
+    #[test]
+    fn move_item_stops_at_ends() {
+        let mut elems = ints(&[1, 2]);
+        assert_eq!(move_item(&mut elems, 0, -1), None);
+        assert_eq!(move_item(&mut elems, 1, 1), None);
+        assert_eq!(elems, ints(&[1, 2]));
+    }
+
+    #[test]
+    fn drop_item_inserts_before_target() {
+        let mut elems = ints(&[1, 2, 3, 4]);
+        assert_eq!(drop_item(&mut elems, 0, 2), Some(1));
+        assert_eq!(elems, ints(&[2, 1, 3, 4]));
+        assert_eq!(drop_item(&mut elems, 3, 0), Some(0));
+        assert_eq!(elems, ints(&[4, 2, 1, 3]));
+        assert_eq!(drop_item(&mut elems, 0, 4), Some(3));
+        assert_eq!(elems, ints(&[2, 1, 3, 4]));
+        assert_eq!(drop_item(&mut elems, 4, 0), None);
+        assert_eq!(drop_item(&mut elems, 0, 5), None);
+    }
+
+    #[test]
+    fn move_down_button_reorders_list() {
+        let desc = list_desc(int_range(None, None), None, None);
+        let mut value = Value::List(ints(&[1, 2]));
+
+        let texts = click_text(&egui::Context::default(), "⬇", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+
+        assert!(texts.is_some());
+        assert_eq!(value, Value::List(ints(&[2, 1])));
+    }
+
+
+    #[test]
+    fn list_at_min_len_does_not_shrink() {
+        let desc = list_desc(int_range(None, None), Some(1), None);
+        let delete = |values: &[i64]| {
+            let mut value = Value::List(ints(values));
+            let texts = click_text(&egui::Context::default(), "-", |ui| {
+                ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+            });
+            assert!(texts.is_some());
+            value
+        };
+
+        assert_eq!(delete(&[1, 2]), Value::List(ints(&[2])));
+        assert_eq!(delete(&[1]), Value::List(ints(&[1])));
+    }
+
+
+    #[test]
+    fn unique_list_refuses_duplicate_add() {
+        let mut desc = list_desc(int_range(None, None), None, None);
+        if let Desc::List { unique_by, .. } = &mut desc {
+            *unique_by = Some(String::new());
+        }
+        let add = |values: &[i64]| {
+            let mut value = Value::List(ints(values));
+            let texts = click_text(&egui::Context::default(), "+", |ui| {
+                ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+            });
+            (value, texts.unwrap())
+        };
+
+        assert_eq!(add(&[1]).0, Value::List(ints(&[1, 0])));
+        let (value, texts) = add(&[0]);
+        assert_eq!(value, Value::List(ints(&[0])));
+        assert!(texts.iter().any(|text| text == "duplicate"), "{texts:?}");
+    }
+
+
+    fn int_map(entries: &[(&str, i64)]) -> IndexMap<String, Value> {
+        entries.iter().map(|&(key, value)| (key.to_owned(), Value::Int(value))).collect()
+    }
+
+    fn keys(values: &IndexMap<String, Value>) -> Vec<&str> {
+        values.keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn rename_key_keeps_value_and_position() {
+        let mut values = int_map(&[("a", 1), ("b", 2), ("c", 3)]);
+
+        assert_eq!(rename_key(&mut values, "b", "x".to_owned()), Ok(()));
+
+        assert_eq!(keys(&values), ["a", "x", "c"]);
+        assert_eq!(values["x"], Value::Int(2));
+    }
+
+    #[test]
+    fn rename_key_to_existing_is_refused() {
+        let mut values = int_map(&[("a", 1), ("b", 2)]);
+
+        assert_eq!(rename_key(&mut values, "a", "b".to_owned()), Err("key exists"));
+
+        assert_eq!(values, int_map(&[("a", 1), ("b", 2)]));
+    }
+
+    #[test]
+    fn rename_key_to_same_is_noop() {
+        let mut values = int_map(&[("a", 1), ("b", 2)]);
+
+        assert_eq!(rename_key(&mut values, "a", "a".to_owned()), Ok(()));
+
+        assert_eq!(keys(&values), ["a", "b"]);
+        assert_eq!(values["a"], Value::Int(1));
+    }
+
+
+    #[test]
+    fn map_keeps_insertion_order() {
+        let mut values = IndexMap::new();
+        for key in ["c", "a", "b"] {
+            values.insert(key.to_owned(), Value::Bool(true));
+        }
+        assert_eq!(keys(&values), ["c", "a", "b"]);
+        assert_eq!(ordered_keys(&values, [].iter()), ["c", "a", "b"]);
+        assert_eq!(ordered_keys(&values, ["b".to_owned()].iter()), ["b", "c", "a"]);
+
+        let mut value = Value::Map(values);
+        let desc = Desc::from_value(&value);
+
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        // Each row shows its key as the label and in the key field.
+        let mut shown: Vec<_> = texts.iter().filter(|text| ["a", "b", "c"].contains(&text.as_str())).collect();
+        shown.dedup();
+        assert_eq!(shown, ["c", "a", "b"]);
+    }
+
+
+    #[test]
+    fn move_item_swaps_indices_two_and_three() {
+        let mut elems = ints(&[0, 1, 2, 3, 4]);
+
+        assert_eq!(move_item(&mut elems, 2, 1), Some(3));
+        assert_eq!(elems, ints(&[0, 1, 3, 2, 4]));
+        assert_eq!(move_item(&mut elems, 3, -1), Some(2));
+        assert_eq!(elems, ints(&[0, 1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn move_up_button_is_disabled_on_first_item() {
+        let desc = list_desc(int_range(None, None), None, None);
+        let mut value = Value::List(ints(&[1]));
+
+        // The first up button shown belongs to the first item, which can't move up.
+        click_text(&egui::Context::default(), "⬆", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert_eq!(value, Value::List(ints(&[1])));
+
+        let mut value = Value::List(ints(&[1, 2]));
+        click_text(&egui::Context::default(), "⬆", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert_eq!(value, Value::List(ints(&[1, 2])));
+    }
+
+
+    #[test]
+    fn new_key_refusal_rejects_empty_and_existing() {
+        let values = int_map(&[("a", 1)]);
+
+        assert_eq!(new_key_refusal(&values, ""), Some("key is empty"));
+        assert_eq!(new_key_refusal(&values, "a"), Some("key exists"));
+        assert_eq!(new_key_refusal(&values, "b"), None);
+    }
+
+    #[test]
+    fn map_add_with_empty_key_does_nothing() {
+        let mut value = Value::Map(int_map(&[("a", 1)]));
+        let desc = Desc::from_value(&value);
+
+        let texts = click_text(&egui::Context::default(), "+", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+
+        assert!(texts.is_some());
+        assert_eq!(value, Value::Map(int_map(&[("a", 1)])));
+    }
+
+
+    #[test]
+    fn parse_color_button_converts_hex_string() {
+        let desc = Desc::Color { alpha: false };
+        let mut value = Value::from("#ff8000");
+
+        let texts = click_text(&egui::Context::default(), "Parse color", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+
+        assert!(texts.is_some());
+        assert_eq!(value, Value::Color([0xff, 0x80, 0, 255]));
+        assert_eq!(desc.default_value(), Value::Color([255; 4]));
+    }
+
+
+    #[test]
+    fn renaming_key_to_existing_is_refused() {
+        let mut value = Value::Map(int_map(&[("a", 1), ("b", 2)]));
+        let desc = Desc::from_value(&value);
+        let ctx = egui::Context::default();
+
+        // The second "a" is the key field next to the row label.
+        let texts = type_into_text(&ctx, "a", 1, "b", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+
+        assert!(texts.unwrap().iter().any(|text| text == "key exists"));
+        assert_eq!(value, Value::Map(int_map(&[("a", 1), ("b", 2)])));
+
+        type_into_text(&ctx, "a", 1, "c", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert_eq!(keys(value.as_map().unwrap()), ["c", "b"]);
+    }
+
+
+
+
+    /// Returns the value after dragging its field showing `text` by 20 points to the right.
+    fn dragged(desc: &Desc, mut value: Value, text: &str) -> Value {
+        let texts = drag_text(&egui::Context::default(), text, egui::vec2(20.0, 0.0), |ui| {
+            ValueProbe::new(Some(desc), &mut value, "value").show_flat(ui);
+        });
+        assert!(texts.is_some(), "{text} is not shown");
+        value
+    }
+
+    fn with_step(mut desc: Desc, new_step: f64) -> Desc {
+        if let Desc::Int { step, .. } | Desc::Float { step, .. } = &mut desc {
+            *step = Some(new_step);
+        }
+        desc
+    }
+
+    #[test]
+    fn int_drag_follows_step_and_snaps() {
+        let Value::Int(by_one) = dragged(&with_step(int_range(None, None), 1.0), Value::Int(0), "0") else {
+            panic!("not an int");
+        };
+        let Value::Int(by_five) = dragged(&with_step(int_range(None, None), 5.0), Value::Int(0), "0") else {
+            panic!("not an int");
+        };
+
+        assert!(by_one > 0);
+        assert!(by_five > by_one, "{by_five} <= {by_one}");
+        assert_eq!(by_five % 5, 0);
+    }
+
+    #[test]
+    fn float_drag_follows_step() {
+        let Value::Float(by_one) = dragged(&with_step(float_range(None, None), 1.0), Value::Float(0.0), "0") else {
+            panic!("not a float");
+        };
+        let Value::Float(by_five) = dragged(&with_step(float_range(None, None), 5.0), Value::Float(0.0), "0") else {
+            panic!("not a float");
+        };
+
+        assert!(by_one > 0.0);
+        assert!(by_five > by_one, "{by_five} <= {by_one}");
+    }
+
+
+    #[test]
+    fn adding_existing_key_keeps_both_values() {
+        let mut value = Value::Map(int_map(&[("a", 1)]));
+        let desc = Desc::from_value(&value);
+        let ctx = egui::Context::default();
+
+        let texts = type_into_text(&ctx, "new key", 0, "a", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        })
+        .unwrap();
+        assert!(texts.iter().any(|text| text == "key exists"));
+
+        let texts = click_text(&ctx, "+", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        })
+        .unwrap();
+        assert_eq!(value, Value::Map(int_map(&[("a", 1)])));
+        assert!(texts.iter().any(|text| text == "key exists"), "typed key is kept");
+
+        // The new key field is drawn before the rows.
+        type_into_text(&ctx, "a", 0, "b", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        click_text(&ctx, "+", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert_eq!(value, Value::Map(int_map(&[("a", 1), ("b", 0)])));
+    }
+
+
+    #[test]
+    fn infer_matches_from_value_for_flat_lists() {
+        let homogeneous = Value::from(vec![Value::Int(1), Value::Int(2)]);
+        assert_eq!(Desc::infer(&homogeneous), Desc::from_value(&homogeneous));
+        assert_eq!(elem_desc(&Desc::infer(&homogeneous)), Some(&int_range(None, None)));
+
+        let mixed = Value::from(vec![Value::Int(1), Value::from("a")]);
+        assert_eq!(elem_desc(&Desc::infer(&mixed)), None);
+    }
+
+    #[test]
+    fn infer_pools_nested_items() {
+        let value = Value::from(vec![
+            Value::from(Vec::new()),
+            Value::from(vec![Value::Int(1)]),
+            Value::Null,
+        ]);
+
+        let desc = Desc::infer(&value);
+
+        let Some(Desc::Optional { inner }) = elem_desc(&desc) else {
+            panic!("{desc:?}");
+        };
+        assert_eq!(elem_desc(inner), Some(&int_range(None, None)));
+        assert_eq!(elem_desc(&Desc::from_value(&value)), None);
+        assert_eq!(desc.validate(&value), Ok(()));
+    }
+
+    #[test]
+    fn infer_collects_enum_variants() {
+        let variant = |name: &str, value: Value| Value::Enum {
+            variant: name.to_owned(),
+            value: Box::new(value),
+        };
+        let value = Value::from(vec![variant("on", Value::Int(1)), variant("off", Value::Null), variant("on", Value::Int(2))]);
+
+        let desc = Desc::infer(&value);
+
+        let Some(Desc::Enum { variants }) = elem_desc(&desc) else {
+            panic!("{desc:?}");
+        };
+        assert_eq!(variants, &[EnumVariant::new("on", int_range(None, None)), EnumVariant::unit("off")]);
+        assert_eq!(desc.validate(&value), Ok(()));
+    }
+
+    #[test]
+    fn infer_gives_up_on_disagreeing_nested_items() {
+        let value = Value::from(vec![Value::from(vec![Value::Int(1)]), Value::from(vec![Value::from("a")])]);
+
+        let desc = Desc::infer(&value);
+
+        assert!(matches!(elem_desc(&desc), Some(Desc::List { elem_desc: None, .. })));
+        assert_eq!(desc.validate(&value), Ok(()));
+        assert_eq!(Desc::infer(&Value::Null), Desc::from_value(&Value::Null));
+    }
+
+
+    #[test]
+    fn optional_toggles_between_none_and_some() {
+        // A tuple shows a plain label next to the checkbox.
+        let desc = Desc::Optional {
+            inner: Box::new(Desc::Tuple { elems: vec![int_range(Some(7), None)] }),
+        };
+        let mut value = Value::Null;
+        let ctx = egui::Context::default();
+
+        let texts = click_checkbox_before_text(&ctx, "none", 0, |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert!(texts.is_some());
+        assert_eq!(value, Value::from(vec![Value::Int(7)]));
+
+        value = Value::from(vec![Value::Int(9)]);
+        let texts = click_checkbox_before_text(&ctx, "1 elements", 0, |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert!(texts.unwrap().iter().any(|text| text == "none"));
+        assert_eq!(value, Value::Null);
+
+        // Turning it back on restores the last value.
+        click_checkbox_before_text(&ctx, "none", 0, |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert_eq!(value, Value::from(vec![Value::Int(9)]));
+    }
+
+    #[test]
+    fn optional_recurses_only_into_present_value() {
+        let desc = Desc::Optional {
+            inner: Box::new(list_desc(Desc::Bool, None, None)),
+        };
+        let mut value = Value::Null;
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert!(!texts.iter().any(|text| text == "[0]"));
+
+        let mut value = Value::from(vec![Value::Bool(true)]);
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert!(texts.iter().any(|text| text == "[0]"));
+    }
+
+
+    #[test]
+    fn optional_with_mismatched_value_offers_reset() {
+        let desc = Desc::Optional { inner: Box::new(Desc::Bool) };
+        let mut value = Value::from("yes");
+
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+
+        assert!(texts.iter().any(|text| text == "Reset to false"), "{texts:?}");
+    }
+
+
+    #[test]
+    fn negative_int_in_uint_field_clamps_to_zero() {
+        let desc = Desc::Uint { min: None, max: None };
+        let mut value = Value::Int(-5);
+
+        let texts = click_text(&egui::Context::default(), "Clamp to 0", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+
+        assert!(texts.is_some());
+        assert_eq!(value, Value::Uint(0));
+    }
+
+
+    #[test]
+    fn accessors_match_their_variant() {
+        assert_eq!(Value::Bool(true).as_bool(), Some(true));
+        assert_eq!(Value::Int(-3).as_int(), Some(-3));
+        assert_eq!(Value::Uint(3).as_uint(), Some(3));
+        assert_eq!(Value::Float(0.5).as_float(), Some(0.5));
+        assert_eq!(Value::String("hi".into()).as_str(), Some("hi"));
+        assert_eq!(Value::Bytes(vec![1, 2]).as_bytes(), Some(&[1, 2][..]));
+        assert_eq!(Value::Color([1, 2, 3, 4]).as_color(), Some([1, 2, 3, 4]));
+        assert_eq!(Value::List(ints(&[1, 2])).as_list(), Some(&[Value::Int(1), Value::Int(2)][..]));
+        assert_eq!(Value::Map(int_map(&[("a", 1)])).as_map().map(|map| map["a"].clone()), Some(Value::Int(1)));
+
+        let value = Value::Enum {
+            variant: "Some".into(),
+            value: Box::new(Value::Int(1)),
+        };
+        assert_eq!(value.as_enum(), Some(("Some", &Value::Int(1))));
+        assert!(Value::Null.is_null());
+    }
+
+    #[test]
+    fn accessors_do_not_convert() {
+        assert_eq!(Value::Int(1).as_bool(), None);
+        assert_eq!(Value::Uint(1).as_int(), None);
+        assert_eq!(Value::Int(1).as_uint(), None);
+        assert_eq!(Value::Int(1).as_float(), None);
+        assert_eq!(Value::Bytes(b"hi".to_vec()).as_str(), None);
+        assert_eq!(Value::String("hi".into()).as_bytes(), None);
+        assert_eq!(Value::String("#ffffff".into()).as_color(), None);
+        assert_eq!(Value::Map(IndexMap::new()).as_list(), None);
+        assert_eq!(Value::List(Vec::new()).as_map(), None);
+        assert_eq!(Value::String("Some".into()).as_enum(), None);
+        assert!(!Value::Bool(false).is_null());
+    }
+
+    #[test]
+    fn mut_accessors_edit_in_place() {
+        let mut value = Value::String("a".into());
+        value.as_string_mut().unwrap().push('b');
+        assert_eq!(value, Value::String("ab".into()));
+        assert!(value.as_list_mut().is_none());
+
+        let mut value = Value::Bytes(vec![1]);
+        value.as_bytes_mut().unwrap().push(2);
+        assert_eq!(value, Value::Bytes(vec![1, 2]));
+        assert!(value.as_string_mut().is_none());
+
+        let mut value = Value::List(ints(&[1]));
+        value.as_list_mut().unwrap().push(Value::Int(2));
+        assert_eq!(value, Value::List(ints(&[1, 2])));
+        assert!(value.as_map_mut().is_none());
+
+        let mut value = Value::Map(int_map(&[("a", 1)]));
+        value.as_map_mut().unwrap().insert("b".into(), Value::Int(2));
+        assert_eq!(value, Value::Map(int_map(&[("a", 1), ("b", 2)])));
+        assert!(value.as_bytes_mut().is_none());
+    }
+
+
+    fn multiline_desc(variants: Option<Vec<Variant>>) -> Desc {
+        let mut desc = string_desc(variants, None, None);
+        if let Desc::String { multiline, .. } = &mut desc {
+            *multiline = true;
+        }
+        desc
+    }
+
+    #[test]
+    fn multiline_string_is_shown() {
+        let desc = multiline_desc(None);
+        let mut value = Value::from("first\nsecond");
+        egui::__run_test_ui(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert!(texts.iter().any(|text| text == "first\nsecond"), "{texts:?}");
+    }
+
+    #[test]
+    fn multiline_does_not_affect_variants() {
+        let desc = multiline_desc(Some(vec!["a".into(), "b".into()]));
+        assert_eq!(desc.default_value(), Value::from("a"));
+
+        let mut value = Value::from("b");
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert!(texts.iter().any(|text| text == "b"), "{texts:?}");
+    }
+
+    #[test]
+    fn multiline_string_accepts_coerced_values() {
+        let desc = multiline_desc(None);
+        assert_eq!(desc.default_value(), Value::from(""));
+        assert_eq!(Value::Int(3).coerce(&desc), Some(Value::from("3")));
+
+        let mut value = Value::Int(3);
+        value.convert(Kind::String).unwrap();
+        assert_eq!(value, Value::from("3"));
+    }
+
+
+    #[test]
+    fn primitives_convert_into_values() {
+        let value: Value = "hi".into();
+        assert_eq!(value, Value::String("hi".to_owned()));
+        assert_eq!(Value::from(String::from("hi")), Value::String("hi".to_owned()));
+        assert_eq!(Value::from(true), Value::Bool(true));
+        assert_eq!(Value::from(42), Value::Int(42));
+        assert_eq!(Value::from(-1i64), Value::Int(-1));
+        assert_eq!(Value::from(1u64), Value::Uint(1));
+        assert_eq!(Value::from(0.5), Value::Float(0.5));
+        assert_eq!(Value::from(vec![Value::from(1), "a".into()]), Value::List(vec![Value::Int(1), Value::from("a")]));
+    }
+
+    #[test]
+    fn entries_collect_into_map_in_order() {
+        let value: Value = [("b", 1), ("a", 2), ("b", 3)]
+            .into_iter()
+            .map(|(key, value)| (key.to_owned(), Value::from(value)))
+            .collect();
+        let map = value.as_map().unwrap();
+        assert_eq!(keys(map), ["b", "a"]);
+        assert_eq!(map["b"], Value::Int(3));
+    }
+
+
+    #[test]
+    fn doc_is_shown_on_hover() {
+        let desc = Desc::Documented {
+            doc: "Port to listen on".to_owned(),
+            inner: Box::new(Desc::from_value(&Value::from("x"))),
+        };
+        let mut value = Value::from("8080");
+
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert!(!texts.iter().any(|text| text == "Port to listen on"));
+
+        let texts = hover_text("8080", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        })
+        .unwrap();
+        assert!(texts.iter().any(|text| text == "Port to listen on"), "{texts:?}");
+    }
+
+    #[test]
+    fn element_docs_are_shown_for_every_element() {
+        let elem = Desc::Documented {
+            doc: "A tag".to_owned(),
+            inner: Box::new(Desc::from_value(&Value::from("x"))),
+        };
+        let desc = list_desc(elem, None, None);
+        let mut value = Value::from(vec!["first".into(), "second".into()]);
+
+        for text in ["first", "second"] {
+            let texts = hover_text(text, |ui| {
+                ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+            })
+            .unwrap();
+            assert_eq!(texts.iter().filter(|text| *text == "A tag").count(), 1, "{texts:?}");
+        }
+    }
+
+
+    #[test]
+    fn read_only_hides_add_and_delete_buttons() {
+        let desc = list_desc(int_range(None, None), None, None);
+        let buttons = ["+", "-", "⬆", "⬇"];
+
+        for read_only in [false, true] {
+            let mut value = Value::List(ints(&[1, 2]));
+            let texts = shown_texts(|ui| {
+                ValueProbe::new(Some(&desc), &mut value, "value").read_only(read_only).show_flat(ui);
+            });
+            for button in buttons {
+                assert_eq!(texts.iter().any(|text| text == button), !read_only, "{button} in {texts:?}");
+            }
+        }
+
+        let desc = Desc::from_value(&Value::Map(int_map(&[("a", 1)])));
+        let mut value = Value::Map(int_map(&[("a", 1)]));
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").read_only(true).show_flat(ui);
+        });
+        assert!(!texts.iter().any(|text| text == "+" || text == "-" || text == "new key"), "{texts:?}");
+    }
+
+    #[test]
+    fn read_only_does_not_fix_values() {
+        let desc = Desc::Struct {
+            fields: vec![
+                StructField::new("name", Desc::from_value(&Value::from("x"))),
+                StructField::new("short", list_desc(Desc::Bool, Some(2), None)),
+            ],
+        };
+        let mut value: Value = [("short".to_owned(), Value::List(Vec::new()))].into_iter().collect();
+        let original = value.clone();
+
+        shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").read_only(true).show_flat(ui);
+        });
+        assert_eq!(value, original);
+    }
+
+    #[test]
+    fn read_only_hides_fix_buttons_and_empty_hint() {
+        let tuple = Desc::Tuple { elems: vec![Desc::Bool] };
+        let mut list = list_desc(Desc::Bool, None, None);
+        if let Desc::List { empty_hint, .. } = &mut list {
+            *empty_hint = Some("no items yet".to_owned());
+        }
+        let cases = [
+            (int_range(Some(0), Some(10)), Value::Int(50), "Clamp to 10"),
+            (int_range(None, None), Value::Float(1.0), "Convert to 1"),
+            (tuple, Value::List(vec![Value::Bool(true); 2]), "Truncate"),
+            (list, Value::List(Vec::new()), "no items yet"),
+        ];
+
+        for (desc, value, text) in cases {
+            for read_only in [false, true] {
+                let mut value = value.clone();
+                let texts = shown_texts(|ui| {
+                    ValueProbe::new(Some(&desc), &mut value, "value").read_only(read_only).show_flat(ui);
+                });
+                assert_eq!(texts.iter().any(|shown| shown == text), !read_only, "{text} in {texts:?}");
+            }
+        }
+    }
+
+
+    #[test]
+    fn overridden_default_is_used() {
+        let desc = Desc::Bool.with_default(Value::Bool(true)).unwrap();
+        assert_eq!(desc.default_value(), Value::Bool(true));
+
+        let variants = string_desc(Some(vec!["low".into(), "high".into()]), None, None);
+        assert_eq!(variants.default_value(), Value::from("low"));
+        let desc = variants.with_default(Value::from("high")).unwrap();
+        assert_eq!(desc.default_value(), Value::from("high"));
+
+        let desc = int_range(None, None).with_default(Value::Int(3)).unwrap();
+        assert_eq!(list_desc(desc, Some(2), None).default_value(), Value::List(vec![Value::Int(3); 2]));
+    }
+
+    #[test]
+    fn default_is_built_in_without_override() {
+        assert_eq!(Desc::Bool.default_value(), Value::Bool(false));
+        assert_eq!(int_range(None, None).default_value(), Value::Int(0));
+        assert_eq!(string_desc(None, None, None).default_value(), Value::from(""));
+    }
+
+    #[test]
+    fn default_of_another_kind_is_rejected() {
+        assert!(Desc::Bool.with_default(Value::Int(1)).is_err());
+        assert!(int_range(None, None).with_default(Value::Float(1.0)).is_err());
+        assert!(Desc::Bool.with_default(Value::Null).is_err());
+
+        let optional = Desc::Optional { inner: Box::new(Desc::Bool) };
+        assert!(optional.clone().with_default(Value::Null).is_ok());
+        assert_eq!(optional.with_default(Value::Bool(true)).unwrap().default_value(), Value::Bool(true));
+
+        // Built directly, a default of the wrong kind is ignored.
+        let desc = Desc::WithDefault {
+            default: Box::new(Value::Int(1)),
+            inner: Box::new(Desc::Bool),
+        };
+        assert_eq!(desc.default_value(), Value::Bool(false));
+    }
+
+    #[test]
+    fn new_elements_get_overridden_default() {
+        let desc = list_desc(Desc::Bool.with_default(Value::Bool(true)).unwrap(), None, None);
+        let mut value = Value::List(Vec::new());
+
+        click_text(&egui::Context::default(), "+", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        })
+        .unwrap();
+        assert_eq!(value, Value::List(vec![Value::Bool(true)]));
+    }
+
+    #[test]
+    fn changing_element_kind_offers_to_convert_items() {
+        let desc = Desc::List {
+            elem_desc: None,
+            empty_hint: None,
+            collapsed_by_default: false,
+            min_len: None,
+            max_len: None,
+            multi_select: false,
+            unique_by: None,
+        };
+        let mut value = Value::List(ints(&[1, 2]));
+        let ctx = egui::Context::default();
+        let mut show = |ui: &mut Ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        };
+
+        // Pick int, then string in the combo box of the element descriptor.
+        click_text(&ctx, "Bool", &mut show).unwrap();
+        let texts = click_text(&ctx, "Int", &mut show).unwrap();
+        assert!(!texts.iter().any(|text| text.starts_with("Convert")), "{texts:?}");
+        click_text(&ctx, "Int", &mut show).unwrap();
+        let texts = click_text(&ctx, "String", &mut show).unwrap();
+        assert!(texts.iter().any(|text| text == "Convert 2 items to string?"), "{texts:?}");
+
+        let texts = click_text(&ctx, "Convert", &mut show).unwrap();
+        assert!(!texts.iter().any(|text| text.starts_with("Convert")), "{texts:?}");
+        assert_eq!(value, Value::from(vec![Value::from("1"), Value::from("2")]));
+    }
+
+
+    #[test]
+    fn editing_bound_field_reclamps_dependent_one() {
+        let mut current = int_range(Some(0), None);
+        if let Desc::Int { max_path, .. } = &mut current {
+            *max_path = Some("max_value".to_owned());
+        }
+        let desc = Desc::Struct {
+            fields: vec![
+                StructField::new("max_value", int_range(None, None)),
+                StructField::new("current", current),
+            ],
+        };
+        let mut value: Value = [("max_value".to_owned(), Value::Int(10)), ("current".to_owned(), Value::Int(8))]
+            .into_iter()
+            .collect();
 
-                                let r = ui.small_button(style.add_button_text());
-                                if r.clicked() {
-                                    elems.push(self.mydesc.default_value());
-                                }
-                            }).response;
+        let mut events = Vec::new();
+        let texts = type_into_text(&egui::Context::default(), "10", 0, "5", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value")
+                .with_on_coerce(&mut |event| events.push(event))
+                .show_flat(ui);
+        })
+        .unwrap();
 
-                            ui.ctx().data_mut(|d| d.insert_temp(self.myid, self.mydesc.clone()));
-                            r
-                        }
-                        Some(elem) => {
-                            ui.horizontal(|ui| {
-                                ui.weak(elem.kind());
+        assert_eq!(path::lookup(&value, "max_value"), Some(&Value::Int(5)));
+        assert_eq!(path::lookup(&value, "current"), Some(&Value::Int(5)));
+        assert!(!texts.iter().any(|text| text.starts_with("Clamp to")), "{texts:?}");
+        assert_eq!(events.len(), 1);
+        assert_eq!(events[0].path, "current");
+    }
 
-                                let r = ui.small_button(style.add_button_text());
-                                if r.clicked() {
-                                    elems.push(elem.default_value());
-                                }
-                            }).response
-                        }
-                    }
-                }
-                _ => {
-                    ui.horizontal(|ui| {
-                        ui.strong(format!(
-                            "Expected list, but is {} instead",
-                            self.value.kind()
-                        ));
-                        if ui.small_button("Reset to empty list").clicked() {
-                            *self.value = Value::List(Vec::new());
-                        }
-                        ui.strong("?");
-                    })
-                    .response
-                }
-            },
-            Some(&Desc::Map { value_desc: ref value }) => match self.value {
-                Value::Map(values) => {
-                    #[derive(Clone)]
-                    struct NewKey(String);
-                    
-                    self.myid = ui.make_persistent_id(self.id_source.with("Map"));
 
-                    let mut new_key = ui.ctx().data(|d| d.get_temp::<NewKey>(self.myid)).unwrap_or(NewKey(String::new()));
+    #[test]
+    fn on_add_stamps_new_items() {
+        let item = Desc::Struct {
+            fields: vec![
+                StructField::new("id", int_range(None, None)),
+                StructField::new("name", Desc::from_value(&Value::from("x"))),
+            ],
+        };
+        let desc = Desc::Struct {
+            fields: vec![StructField::new("items", list_desc(item, None, None))],
+        };
+        let mut value = desc.default_value();
 
-                    let r = match value {
-                        None => {
-                            self.mydesc = ui
-                                .ctx()
-                                .data(|d| d.get_temp::<Desc>(self.myid))
-                                .unwrap_or_default();
+        let next_id = std::cell::Cell::new(1);
+        let stamped_at = std::cell::RefCell::new(Vec::new());
+        let on_add = |path: &str, item: &mut Value| {
+            stamped_at.borrow_mut().push(path.to_owned());
+            if let Value::Map(fields) = item {
+                fields.insert("id".to_owned(), Value::Int(next_id.get()));
+                next_id.set(next_id.get() + 1);
+            }
+        };
 
-                            let r = ui.horizontal(|ui| {
-                                self.mydesc.probe(ui, style);
+        let ctx = egui::Context::default();
+        for _ in 0..2 {
+            click_text(&ctx, "+", |ui| {
+                ValueProbe::new(Some(&desc), &mut value, "value").with_on_add(&on_add).show_flat(ui);
+            })
+            .unwrap();
+        }
 
-                                ui.text_edit_singleline(&mut new_key.0);
+        assert_eq!(path::lookup(&value, "items[0].id"), Some(&Value::Int(1)));
+        assert_eq!(path::lookup(&value, "items[1].id"), Some(&Value::Int(2)));
+        assert_eq!(path::lookup(&value, "items[1].name"), Some(&Value::from("")));
+        assert_eq!(*stamped_at.borrow(), ["items", "items"]);
+    }
 
-                                let r = ui.small_button(style.add_button_text());
-                                if r.clicked() {
-                                    values.insert(std::mem::take(&mut new_key.0), self.mydesc.default_value());
-                                }
-                            }).response;
 
-                            ui.ctx().data_mut(|d| d.insert_temp(self.myid, self.mydesc.clone()));
-                            r
-                        }
-                        Some(elem) => {
-                            ui.horizontal(|ui| {
-                                ui.weak(elem.kind());
+    #[test]
+    fn invalid_number_text_persists_until_valid() {
+        let desc = int_range(None, None);
+        let mut value = Value::Int(12);
+        let ctx = egui::Context::default();
 
-                                ui.text_edit_singleline(&mut new_key.0);
+        let texts = type_into_text(&ctx, "12", 0, "12x", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        })
+        .unwrap();
+        assert!(texts.iter().any(|text| text == "12x"), "{texts:?}");
+        assert!(texts.iter().any(|text| text == "⚠"), "{texts:?}");
 
-                                let r = ui.small_button(style.add_button_text());
-                                if r.clicked() {
-                                    values.insert(std::mem::take(&mut new_key.0), elem.default_value());
-                                }
-                            }).response
-                        }
-                    };
+        for _ in 0..3 {
+            let texts = shown_texts_in(&ctx, |ui| {
+                ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+            });
+            assert!(texts.iter().any(|text| text == "12x"), "{texts:?}");
+        }
+        assert_eq!(value, Value::Int(12));
 
-                    ui.ctx().data_mut(|d| d.insert_temp(self.myid, new_key));
-                    r
-                }
-                _ => {
-                    ui.horizontal(|ui| {
-                        ui.strong(format!(
-                            "Expected list, but is {} instead",
-                            self.value.kind()
-                        ));
-                        if ui.small_button("Reset to empty map").clicked() {
-                            *self.value = Value::Map(HashMap::new());
-                        }
-                        ui.strong("?");
-                    })
-                    .response
-                }
-            },
+        let texts = type_into_text(&ctx, "12x", 0, "15", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        })
+        .unwrap();
+        assert_eq!(value, Value::Int(15));
+        assert!(!texts.iter().any(|text| text == "12x" || text == "⚠"), "{texts:?}");
+    }
+
+
+    #[test]
+    fn missing_required_keys_are_reported_and_added() {
+        let desc = Desc::Map {
+            value_desc: Some(Box::new(Desc::from_value(&Value::from("x")))),
+            tabs: Vec::new(),
+            empty_hint: None,
+            collapsed_by_default: false,
+            max_entries: None,
+            required_keys: vec!["host".to_owned(), "port".to_owned(), "user".to_owned()],
+            required_if: Vec::new(),
+            strict_keys: false,
+        };
+        let mut value: Value = [("host".to_owned(), Value::from("a")), ("extra".to_owned(), Value::from("b"))]
+            .into_iter()
+            .collect();
+
+        let errors = desc.validate(&value).unwrap_err();
+        let missing = ProbeError::MissingKeys {
+            path: String::new(),
+            keys: vec!["port".to_owned(), "user".to_owned()],
+        };
+        assert_eq!(errors, std::slice::from_ref(&missing));
+
+        let ctx = egui::Context::default();
+        let texts = shown_texts_in(&ctx, |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        assert!(texts.iter().any(|text| text == &missing.to_string()), "{texts:?}");
+
+        click_text(&ctx, "Add missing", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        })
+        .unwrap();
+        assert_eq!(desc.validate(&value), Ok(()));
+        assert_eq!(keys(value.as_map().unwrap()), ["host", "extra", "port", "user"]);
+        assert_eq!(path::lookup(&value, "port"), Some(&Value::from("")));
+    }
+
+
+    #[test]
+    fn enum_variant_description_is_shown_as_help() {
+        let desc = Desc::Enum {
+            variants: vec![
+                EnumVariant::unit("fast").with_description("Skips checks"),
+                EnumVariant::unit("safe"),
+            ],
+        };
+        let mut value = desc.default_value();
+
+        for variant_help in [false, true] {
+            let config = ProbeConfig {
+                variant_help,
+                ..ProbeConfig::DEFAULT
+            };
+            let texts = shown_texts(|ui| {
+                ValueProbe::new(Some(&desc), &mut value, "value").with_config(&config).show_flat(ui);
+            });
+            assert!(texts.iter().any(|text| text == "fast"), "{texts:?}");
+            assert_eq!(texts.iter().any(|text| text == "Skips checks"), variant_help, "{texts:?}");
         }
     }
 
-    fn has_inner(&mut self) -> bool {
-        match self.desc {
-            None => true,
-            Some(Desc::Bool) => false,
-            Some(Desc::Int { .. }) => false,
-            Some(Desc::Float { .. }) => false,
-            Some(Desc::String { .. }) => false,
-            Some(Desc::List { elem_desc }) => elem_desc.is_none() || self.value.has_inner(),
-            Some(Desc::Map { value_desc }) => value_desc.is_none() || self.value.has_inner(),
 
+    #[test]
+    fn enum_variant_icons_are_shown_but_not_stored() {
+        let desc = Desc::Enum {
+            variants: vec![EnumVariant::unit("sun").with_icon("☀"), EnumVariant::unit("moon")],
+        };
+        let mut value = desc.default_value();
+        let ctx = egui::Context::default();
+
+        let texts = click_text(&ctx, "☀ sun", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        })
+        .unwrap();
+        assert!(texts.iter().any(|text| text == "moon"), "{texts:?}");
+
+        click_text(&ctx, "moon", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        })
+        .unwrap();
+        assert_eq!(value, EnumVariant::unit("moon").default_value());
+        assert_eq!(desc.default_value().as_enum(), Some(("sun", &Value::Null)));
+    }
+
+
+    #[test]
+    fn pasted_number_follows_range_policy() {
+        let desc = int_range(Some(0), Some(100));
+
+        let config = ProbeConfig {
+            range_policy: RangePolicy::Clamp,
+            ..ProbeConfig::DEFAULT
+        };
+        let mut value = Value::Int(42);
+        let texts = paste_into_text(&egui::Context::default(), "42", "500", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").with_config(&config).show_flat(ui);
+        })
+        .unwrap();
+        assert_eq!(value, Value::Int(100));
+        assert!(texts.iter().any(|text| text == "clamped from 500"), "{texts:?}");
+
+        let config = ProbeConfig {
+            range_policy: RangePolicy::Reject,
+            ..ProbeConfig::DEFAULT
+        };
+        let mut value = Value::Int(42);
+        let texts = paste_into_text(&egui::Context::default(), "42", "500", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").with_config(&config).show_flat(ui);
+        })
+        .unwrap();
+        assert_eq!(value, Value::Int(42));
+        assert!(texts.iter().any(|text| text == "500"), "{texts:?}");
+        assert!(texts.iter().any(|text| text == "⚠"), "{texts:?}");
+    }
+
+
+    #[test]
+    fn declared_keys_render_in_schema_order() {
+        let desc = Desc::Map {
+            value_desc: Some(Box::new(int_range(None, None))),
+            tabs: Vec::new(),
+            empty_hint: None,
+            collapsed_by_default: false,
+            max_entries: None,
+            required_keys: vec!["port".to_owned(), "host".to_owned()],
+            required_if: Vec::new(),
+            strict_keys: false,
+        };
+        let mut value = Value::Map(int_map(&[("zeta", 1), ("host", 2), ("alpha", 3), ("port", 4)]));
+
+        let mut texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        texts.retain(|text| ["zeta", "host", "alpha", "port"].contains(&text.as_str()));
+        texts.dedup();
+        // Undeclared keys follow in insertion order.
+        assert_eq!(texts, ["port", "host", "zeta", "alpha"]);
+
+        let desc = Desc::Struct {
+            fields: vec![
+                StructField::new("port", int_range(None, None)),
+                StructField::new("host", int_range(None, None)),
+            ],
+        };
+        let mut value = Value::Map(int_map(&[("host", 2), ("port", 4)]));
+        let mut texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        });
+        texts.retain(|text| text == "host" || text == "port");
+        assert_eq!(texts, ["port", "host"]);
+    }
+
+
+    fn multi_select(desc: Desc, unique_by: Option<&str>) -> Desc {
+        match desc {
+            Desc::List { elem_desc, max_len, .. } => Desc::List {
+                elem_desc,
+                empty_hint: None,
+                collapsed_by_default: false,
+                min_len: None,
+                max_len,
+                multi_select: true,
+                unique_by: unique_by.map(str::to_owned),
+            },
+            _ => unreachable!(),
         }
     }
 
-    fn iterate_inner(&mut self, ui: &mut Ui, f: &mut dyn FnMut(&str, &mut Ui, &mut dyn EguiProbe)) {
-        match self.desc {
-            None => {
-                let mut probe = ValueProbe::new(Some(&self.mydesc), self.value, self.id_source);
-                f("value", ui, &mut probe);
-            }
-            Some(Desc::Bool) => {}
-            Some(Desc::Int { .. }) => {}
-            Some(Desc::Float { .. }) => {}
-            Some(Desc::String { .. }) => {}
-            Some(Desc::List { elem_desc: elem }) => {
-                let elem = match elem {
-                    None => {
-                        if self.mydesc.has_inner() {
-                            self.mydesc.iterate_inner(ui, f);
-                        }
-                        ui.ctx().data_mut(|d| d.insert_temp(self.myid, self.mydesc.clone()));
+    #[test]
+    fn selecting_two_items_and_deleting_them_in_one_action() {
+        let desc = multi_select(list_desc(int_range(None, None), None, None), None);
+        let mut value = Value::List(ints(&[10, 20, 30]));
+        let ctx = egui::Context::default();
+        let mut show = |ui: &mut Ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        };
 
-                        &self.mydesc
-                    },
-                    Some(elem) => &**elem,
-                };
+        click_checkbox_before_text(&ctx, "☰", 0, &mut show).unwrap();
+        let texts = click_checkbox_before_text(&ctx, "☰", 2, &mut show).unwrap();
+        assert!(texts.iter().any(|text| text == "2 selected"));
+        click_text(&ctx, "Delete selected", &mut show).unwrap();
 
-                match self.value {
-                    Value::List(elems) => {
-                        let id = self.id_source.with("List");
+        assert_eq!(value, Value::List(ints(&[20])));
+    }
 
-                        let mut idx = 0;
-                        elems.retain_mut(|value| {
-                            let mut probe = ValueProbe::new(Some(elem), value, id.with(idx));
-                            let mut item = DeleteMe {
-                                value: &mut probe,
-                                delete: false,
-                            };
-                            f(&format!("[{idx}]"), ui, &mut item);
-                            idx += 1;
-                            !item.delete
-                        });
-                    }
-                    _ => {}
-                }
+    #[test]
+    fn duplicating_selection_picked_out_of_order_inserts_after_each_original() {
+        let desc = multi_select(list_desc(int_range(None, None), None, None), None);
+        let mut value = Value::List(ints(&[10, 20, 30]));
+        let ctx = egui::Context::default();
+        let mut show = |ui: &mut Ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        };
+
+        click_checkbox_before_text(&ctx, "☰", 2, &mut show).unwrap();
+        click_checkbox_before_text(&ctx, "☰", 0, &mut show).unwrap();
+        click_text(&ctx, "Duplicate selected", &mut show).unwrap();
+
+        assert_eq!(value, Value::List(ints(&[10, 10, 20, 30, 30])));
+    }
+
+    #[test]
+    fn selection_follows_item_moved_down() {
+        let desc = multi_select(list_desc(int_range(None, None), None, None), None);
+        let mut value = Value::List(ints(&[10, 20, 30]));
+        let ctx = egui::Context::default();
+        let mut show = |ui: &mut Ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        };
+
+        click_checkbox_before_text(&ctx, "☰", 0, &mut show).unwrap();
+        click_text(&ctx, "⬇", &mut show).unwrap();
+        click_text(&ctx, "Delete selected", &mut show).unwrap();
+
+        assert_eq!(value, Value::List(ints(&[20, 30])));
+    }
+
+    #[test]
+    fn duplicates_of_selection_are_added_like_new_items() {
+        let desc = multi_select(list_desc(int_range(None, None), None, None), Some(""));
+        let ctx = egui::Context::default();
+
+        // Copies repeating the unique field are refused.
+        let mut value = Value::List(ints(&[10, 20]));
+        let mut show = |ui: &mut Ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        };
+        click_checkbox_before_text(&ctx, "☰", 0, &mut show).unwrap();
+        click_text(&ctx, "Duplicate selected", &mut show).unwrap();
+        assert_eq!(value, Value::List(ints(&[10, 20])));
+
+        // The `on_add` hook can make them unique.
+        let ctx = egui::Context::default();
+        let on_add = |_: &str, value: &mut Value| {
+            if let Value::Int(n) = value {
+                *n += 1;
             }
-            Some(Desc::Map { value_desc: value }) => {
-                let desc = match value {
-                    None => {
-                        if self.mydesc.has_inner() {
-                            self.mydesc.iterate_inner(ui, f);
-                        }
-                        ui.ctx().data_mut(|d| d.insert_temp(self.myid, self.mydesc.clone()));
+        };
+        let mut value = Value::List(ints(&[10, 20]));
+        let mut show = |ui: &mut Ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").with_on_add(&on_add).show_flat(ui);
+        };
+        click_checkbox_before_text(&ctx, "☰", 0, &mut show).unwrap();
+        click_text(&ctx, "Duplicate selected", &mut show).unwrap();
+        assert_eq!(value, Value::List(ints(&[10, 11, 20])));
+    }
 
-                        &self.mydesc
-                    },
-                    Some(value) => &**value,
-                };
+    #[test]
+    fn duplicating_selection_respects_max_len() {
+        let desc = multi_select(list_desc(int_range(None, None), None, Some(3)), None);
+        let mut value = Value::List(ints(&[10, 20]));
+        let ctx = egui::Context::default();
+        let mut show = |ui: &mut Ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
+        };
 
-                match self.value {
-                    Value::Map(values) => {
-                        let id: Id = self.id_source.with("List");
+        click_checkbox_before_text(&ctx, "☰", 0, &mut show).unwrap();
+        click_checkbox_before_text(&ctx, "☰", 1, &mut show).unwrap();
+        click_text(&ctx, "Duplicate selected", &mut show).unwrap();
 
-                        let mut idx = 0;
-                        values.retain(|key, value| {
-                            let mut probe = ValueProbe::new(Some(desc), value, id.with(idx));
-                            let mut item = DeleteMe {
-                                value: &mut probe,
-                                delete: false,
-                            };
-                            f(key, ui, &mut item);
-                            idx += 1;
-                            !item.delete
-                        });
-                    }
-                    _ => {}
-                }
+        assert_eq!(value, Value::List(ints(&[10, 20])));
+    }
+
+
+    #[test]
+    fn on_blur_errors_wait_until_the_field_loses_focus() {
+        let desc = string_desc(None, Some(3), None);
+        let config = ProbeConfig {
+            validation_timing: ValidationTiming::OnBlur,
+            ..ProbeConfig::DEFAULT
+        };
+        let mut value = Value::from("abcdef");
+        let ctx = egui::Context::default();
+        let error = "2 characters, at least 3 required";
+
+        let texts = type_keeping_focus(&ctx, "abcdef", "ab", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").with_config(&config).show_flat(ui);
+        })
+        .unwrap();
+        assert_eq!(value, Value::from("ab"));
+        assert!(!texts.iter().any(|text| text == error), "{texts:?}");
+
+        ctx.memory_mut(|m| m.stop_text_input());
+        let texts = shown_texts_in(&ctx, |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").with_config(&config).show_flat(ui);
+        });
+        assert!(texts.iter().any(|text| text == error), "{texts:?}");
+    }
+
+
+    #[test]
+    fn styled_path_gets_background_behind_its_value() {
+        let mut value = Value::Map(
+            [("host".to_owned(), Value::from("alpha")), ("user".to_owned(), Value::from("beta"))].into_iter().collect(),
+        );
+        let desc = Desc::from_value(&value);
+        let style_for = |path: &str| (path == "user").then_some(Color32::RED);
+        let ctx = egui::Context::default();
+        let mut show = |ui: &mut Ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").with_style_for(&style_for).show_flat(ui);
+        };
+
+        shown_texts_in(&ctx, &mut show);
+        let output = ctx.run(Default::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| show(ui));
+        });
+
+        fn flatten(shape: &egui::Shape, rects: &mut Vec<egui::Rect>, texts: &mut Vec<(String, egui::Rect)>) {
+            match shape {
+                egui::Shape::Rect(rect) if rect.fill == Color32::RED => rects.push(rect.rect),
+                egui::Shape::Text(text) => texts.push((text.galley.job.text.clone(), text.visual_bounding_rect())),
+                egui::Shape::Vec(shapes) => shapes.iter().for_each(|shape| flatten(shape, rects, texts)),
+                _ => {}
             }
         }
+        let (mut rects, mut texts) = (Vec::new(), Vec::new());
+        for clipped in &output.shapes {
+            flatten(&clipped.shape, &mut rects, &mut texts);
+        }
+        let tinted = |shown: &str| {
+            let (_, bounds) = texts.iter().find(|(text, _)| text == shown).unwrap();
+            rects.iter().any(|rect| rect.contains(bounds.center()))
+        };
+
+        assert_eq!(rects.len(), 1, "{rects:?}");
+        assert!(tinted("beta"));
+        assert!(!tinted("alpha"));
     }
-}
 
-fn invalid_range<T: Display>(ui: &mut Ui, min: T, max: T) -> Response {
-    ui.strong(format!(
-        "Invalid range. `min = {}` must be not greater than `max = {}`.",
-        min, max
-    ))
-}
 
-fn convert_to_string<T: ToString>(
-    ui: &mut Ui,
-    value: &T,
-    kind: &str,
-) -> (Response, Option<String>) {
-    let mut convert = false;
-    let s = value.to_string();
+    #[test]
+    fn locked_structure_hides_adds_and_deletes_but_keeps_scalar_edits() {
+        let desc = list_desc(int_range(None, None), None, None);
+        let config = ProbeConfig {
+            structure_locked: true,
+            ..ProbeConfig::DEFAULT
+        };
+        let mut value = Value::List(ints(&[1, 2]));
+        let ctx = egui::Context::default();
 
-    let r = ui
-        .horizontal(|ui| {
-            ui.strong(format!("Expected string, but is {} instead", kind));
-            if ui.small_button(format!("Convert to {s:?}")).clicked() {
-                convert = true;
-            }
-            ui.strong("?");
+        let texts = shown_texts_in(&ctx, |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").with_config(&config).show_flat(ui);
+        });
+        for button in ["+", "-", "⬆", "⬇", "☰"] {
+            assert!(!texts.iter().any(|text| text == button), "{button} in {texts:?}");
+        }
+
+        type_into_text(&ctx, "2", 0, "7", |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").with_config(&config).show_flat(ui);
         })
-        .response;
+        .unwrap();
+        assert_eq!(value, Value::List(ints(&[1, 7])));
 
-    (r, if convert { Some(s) } else { None })
-}
+        let desc = Desc::from_value(&Value::Map(int_map(&[("a", 1)])));
+        let mut value = Value::Map(int_map(&[("a", 1)]));
+        let texts = shown_texts(|ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").with_config(&config).show_flat(ui);
+        });
+        assert!(!texts.iter().any(|text| text == "+" || text == "-" || text == "new key"), "{texts:?}");
+    }
 
-/// Modifier to add a delete button to an item probe UI.
-pub struct DeleteMe<'a, T> {
-    pub value: &'a mut T,
-    pub delete: bool,
-}
 
-impl<T> EguiProbe for DeleteMe<'_, T>
-where
-    T: EguiProbe,
-{
-    fn probe(&mut self, ui: &mut egui::Ui, style: &Style) -> egui::Response {
-        ui.horizontal(|ui| {
-            self.value.probe(ui, style);
-            ui.add_space(ui.spacing().item_spacing.x);
-            if ui.small_button(style.remove_button_text()).clicked() {
-                self.delete = true;
-            };
+    #[test]
+    fn enum_without_variants_defaults_to_unnamed_variant_without_payload() {
+        let desc = Desc::Enum { variants: Vec::new() };
+        let value = desc.default_value();
+        assert_eq!(value.as_enum(), Some(("", &Value::Null)));
+        assert_eq!(value.kind(), desc.kind());
+    }
+
+
+    #[test]
+    fn rename_key_to_empty_is_refused() {
+        let mut values = int_map(&[("a", 1)]);
+
+        assert_eq!(rename_key(&mut values, "a", String::new()), Err("key is empty"));
+
+        assert_eq!(values, int_map(&[("a", 1)]));
+    }
+
+    #[test]
+    fn clearing_key_field_keeps_entry_and_shows_refusal() {
+        let mut value = Value::Map(int_map(&[("a", 1), ("b", 2)]));
+        let desc = Desc::from_value(&value);
+        let ctx = egui::Context::default();
+        let backspace = egui::Event::Key {
+            key: egui::Key::Backspace,
+            physical_key: None,
+            pressed: true,
+            repeat: false,
+            modifiers: egui::Modifiers::NONE,
+        };
+
+        // The first "a" is the label of the row, the second one its key field.
+        let texts = type_text(&ctx, "a", 1, backspace, true, |ui| {
+            ValueProbe::new(Some(&desc), &mut value, "value").show_flat(ui);
         })
-        .response
+        .unwrap();
+
+        assert_eq!(value, Value::Map(int_map(&[("a", 1), ("b", 2)])));
+        assert!(texts.iter().any(|text| text == "key is empty"), "{texts:?}");
     }
 
-    fn has_inner(&mut self) -> bool {
-        self.value.has_inner() && !self.delete
+
+    #[test]
+    fn snap_int_rounds_to_nearest_multiple() {
+        assert_eq!(snap_int(7, 5.0), 5);
+        assert_eq!(snap_int(8, 5.0), 10);
+        assert_eq!(snap_int(5, 2.0), 6);
+        assert_eq!(snap_int(-5, 2.0), -6);
+        assert_eq!(snap_int(-7, 5.0), -5);
+        assert_eq!(snap_int(7, 2.5), 7);
     }
 
-    fn iterate_inner(&mut self, ui: &mut Ui, f: &mut dyn FnMut(&str, &mut Ui, &mut dyn EguiProbe)) {
-        self.value.iterate_inner(ui, f);
+    #[test]
+    fn snap_int_keeps_large_values_exact() {
+        let large = (1 << 53) + 1;
+        assert_eq!(snap_int(large, 1.0), large);
+        assert_eq!(snap_int(large + 2, 2.0), large + 3);
+        assert_eq!(snap_int(i64::MAX, 1.0), i64::MAX);
+        assert_eq!(snap_int(i64::MAX, 10.0), i64::MAX);
+        assert_eq!(snap_int(i64::MIN, 1.0), i64::MIN);
     }
 }
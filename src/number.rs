@@ -0,0 +1,693 @@
+use std::ops::RangeInclusive;
+
+use egui::{emath::Numeric, Context, Id, Response, Ui};
+
+use crate::{CoercionReason, ProbeConfig, RangePolicy, ValidationTiming};
+
+/// Describes how numbers are displayed and parsed.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct NumberFormat {
+    /// Character between the integer and the fractional part.
+    pub decimal_separator: char,
+
+    /// Character inserted between groups of integer digits.
+    /// `None` disables grouping.
+    pub group_separator: Option<char>,
+
+    /// Number of integer digits in each group.
+    pub group_size: usize,
+}
+
+impl NumberFormat {
+    /// Plain format without grouping, e.g. `1234567.89`.
+    pub const PLAIN: Self = NumberFormat {
+        decimal_separator: '.',
+        group_separator: None,
+        group_size: 3,
+    };
+
+    /// English format, e.g. `1,234,567.89`.
+    pub const EN: Self = NumberFormat {
+        decimal_separator: '.',
+        group_separator: Some(','),
+        group_size: 3,
+    };
+
+    /// German format, e.g. `1.234.567,89`.
+    pub const DE: Self = NumberFormat {
+        decimal_separator: ',',
+        group_separator: Some('.'),
+        group_size: 3,
+    };
+
+    /// French format, e.g. `1 234 567,89` with narrow no-break spaces.
+    pub const FR: Self = NumberFormat {
+        decimal_separator: ',',
+        group_separator: Some('\u{202F}'),
+        group_size: 3,
+    };
+
+    /// Formats a number with the given range of decimal places.
+    pub fn format(&self, value: f64, decimals: RangeInclusive<usize>) -> String {
+        self.localize(&egui::emath::format_with_decimals_in_range(value, decimals))
+    }
+
+    /// Converts a number formatted with `.` as decimal separator and no grouping
+    /// into this format.
+    pub fn localize(&self, plain: &str) -> String {
+        let (sign, unsigned) = match plain.strip_prefix('-') {
+            Some(unsigned) => ("-", unsigned),
+            None => ("", plain),
+        };
+
+        let int_len = unsigned
+            .find(|c: char| !c.is_ascii_digit())
+            .unwrap_or(unsigned.len());
+        let (int, rest) = unsigned.split_at(int_len);
+
+        let mut result = String::with_capacity(plain.len() + int.len() / 3);
+        result.push_str(sign);
+
+        match self.group_separator {
+            Some(separator) if self.group_size > 0 => {
+                for (idx, digit) in int.chars().enumerate() {
+                    if idx > 0 && (int.len() - idx) % self.group_size == 0 {
+                        result.push(separator);
+                    }
+                    result.push(digit);
+                }
+            }
+            _ => result.push_str(int),
+        }
+
+        match rest.strip_prefix('.') {
+            Some(fract) => {
+                result.push(self.decimal_separator);
+                result.push_str(fract);
+            }
+            None => result.push_str(rest),
+        }
+
+        result
+    }
+
+    /// Parses a number written in this format.
+    ///
+    /// Group separators and whitespace are ignored wherever they appear.
+    pub fn parse(&self, text: &str) -> Option<f64> {
+        let plain: String = text
+            .chars()
+            .filter(|&c| Some(c) != self.group_separator && !c.is_whitespace())
+            .map(|c| if c == self.decimal_separator { '.' } else { c })
+            .collect();
+
+        plain.parse().ok()
+    }
+}
+
+impl Default for NumberFormat {
+    #[inline]
+    fn default() -> Self {
+        NumberFormat::PLAIN
+    }
+}
+
+/// Human-readable units for integers, e.g. `1048576` shown as `1 MiB`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, egui_probe::EguiProbe)]
+pub enum HumanizeKind {
+    /// Number of bytes, shown in binary units like `KiB` and `MiB`.
+    #[default]
+    Bytes,
+
+    /// Duration in milliseconds.
+    Millis,
+
+    /// Duration in seconds.
+    Seconds,
+}
+
+impl HumanizeKind {
+    /// Units from the smallest to the largest.
+    /// The first unit is the one the integer is stored in.
+    fn units(&self) -> &'static [(&'static str, i64)] {
+        match self {
+            HumanizeKind::Bytes => &[
+                ("B", 1),
+                ("KiB", 1 << 10),
+                ("MiB", 1 << 20),
+                ("GiB", 1 << 30),
+                ("TiB", 1 << 40),
+            ],
+            HumanizeKind::Millis => &[
+                ("ms", 1),
+                ("s", 1000),
+                ("min", 60_000),
+                ("h", 3_600_000),
+                ("d", 86_400_000),
+            ],
+            HumanizeKind::Seconds => &[("s", 1), ("min", 60), ("h", 3600), ("d", 86_400)],
+        }
+    }
+
+    /// Additional units accepted by `parse`.
+    fn extra_units(&self) -> &'static [(&'static str, i64)] {
+        match self {
+            HumanizeKind::Bytes => &[
+                ("KB", 1_000),
+                ("MB", 1_000_000),
+                ("GB", 1_000_000_000),
+                ("TB", 1_000_000_000_000),
+            ],
+            HumanizeKind::Millis | HumanizeKind::Seconds => &[],
+        }
+    }
+
+    /// Formats the integer in the largest unit that represents it
+    /// with at most three decimal places, so that `parse` restores it exactly.
+    pub fn format(&self, value: i64) -> String {
+        let (name, size) = self
+            .units()
+            .iter()
+            .rev()
+            .find(|&&(_, size)| {
+                value.unsigned_abs() >= size as u64 && (value as i128 * 1000) % size as i128 == 0
+            })
+            .unwrap_or(&self.units()[0]);
+
+        format!("{} {name}", value as f64 / *size as f64)
+    }
+
+    /// Parses humanized input like `1 MiB`, `1.5h` or `1 h 30 min`.
+    ///
+    /// Numbers without unit are in the unit the integer is stored in.
+    /// Units are case-insensitive.
+    pub fn parse(&self, text: &str) -> Option<i64> {
+        let text = text.trim();
+        let (sign, mut rest) = match text.strip_prefix('-') {
+            Some(rest) => (-1.0, rest),
+            None => (1.0, text),
+        };
+
+        let mut total = 0.0;
+        let mut any = false;
+
+        while !rest.trim_start().is_empty() {
+            rest = rest.trim_start();
+
+            let number_len = rest
+                .find(|c: char| !c.is_ascii_digit() && c != '.')
+                .unwrap_or(rest.len());
+            let number: f64 = rest[..number_len].parse().ok()?;
+            rest = rest[number_len..].trim_start();
+
+            let unit_len = rest
+                .find(|c: char| !c.is_alphabetic())
+                .unwrap_or(rest.len());
+            let unit = &rest[..unit_len];
+            rest = &rest[unit_len..];
+
+            let size = if unit.is_empty() {
+                1
+            } else {
+                self.units()
+                    .iter()
+                    .chain(self.extra_units())
+                    .find(|(name, _)| name.eq_ignore_ascii_case(unit))?
+                    .1
+            };
+
+            total += number * size as f64;
+            any = true;
+        }
+
+        any.then(|| (sign * total).round() as i64)
+    }
+}
+
+/// Returns the clamp range of a drag value within optional bounds,
+/// widened to include `value`.
+///
+/// The drag value clamps on every frame,
+/// so an out-of-range value is kept until the user clamps it explicitly.
+fn drag_range<N: Numeric>(value: N, min: Option<N>, max: Option<N>) -> RangeInclusive<N> {
+    let min = min.map_or(N::MIN, |min| if value < min { value } else { min });
+    let max = max.map_or(N::MAX, |max| if value > max { value } else { max });
+    min..=max
+}
+
+/// Creates drag value for a number within optional bounds
+/// formatted according to `format`.
+pub(crate) fn drag_number<'a, N>(
+    value: &'a mut N,
+    min: Option<N>,
+    max: Option<N>,
+    format: &'a NumberFormat,
+) -> egui::DragValue<'a>
+where
+    N: Numeric,
+{
+    let range = drag_range(*value, min, max);
+
+    egui::DragValue::new(value)
+        .clamp_range(range)
+        .custom_formatter(|n, decimals| format.format(n, decimals))
+        .custom_parser(|s| format.parse(s))
+}
+
+/// Shows drag value for a number within optional bounds
+/// formatted according to `config.number_format`.
+///
+/// `id` identifies the field to keep entered text for, see `show_raw_text`.
+/// `speed` overrides the default drag speed of the value per pixel.
+pub(crate) fn probe_number<N>(
+    ui: &mut Ui,
+    id: Id,
+    value: &mut N,
+    min: Option<N>,
+    max: Option<N>,
+    speed: Option<f64>,
+    config: &ProbeConfig,
+) -> Response
+where
+    N: Numeric + ToString,
+{
+    let format = &config.number_format;
+    let range = f64_range(min, max);
+    let parse = |s: &str| format.parse(s);
+    let accept = |s: &str| accepted(parse(s), &range, config.range_policy);
+
+    if let Some(r) = show_raw_text(ui, id, value, min, max, &accept, config.validation_timing) {
+        return r;
+    }
+
+    let mut drag = drag_number(value, min, max, format).custom_parser(accept);
+    if let Some(speed) = speed {
+        drag = drag.speed(speed);
+    }
+
+    let hint = match (min, max) {
+        (None, None) => return add_drag(ui, id, drag, &range, &parse, &accept),
+        (Some(min), None) => format!("{}..", format.localize(&min.to_string())),
+        (None, Some(max)) => format!("..={}", format.localize(&max.to_string())),
+        (Some(min), Some(max)) => format!(
+            "{}..={}",
+            format.localize(&min.to_string()),
+            format.localize(&max.to_string())
+        ),
+    };
+
+    ui.horizontal(|ui| {
+        add_drag(ui, id, drag, &range, &parse, &accept);
+        ui.weak(hint);
+    })
+    .response
+}
+
+/// Shows drag value for an integer within optional bounds
+/// displayed and edited in human-readable units.
+///
+/// `speed` overrides the default drag speed of the value per pixel.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn probe_humanized(
+    ui: &mut Ui,
+    id: Id,
+    value: &mut i64,
+    min: Option<i64>,
+    max: Option<i64>,
+    speed: Option<f64>,
+    kind: HumanizeKind,
+    config: &ProbeConfig,
+) -> Response {
+    let range = f64_range(min, max);
+    let parse = |s: &str| kind.parse(s).map(|n| n as f64);
+    let accept = |s: &str| accepted(parse(s), &range, config.range_policy);
+
+    if let Some(r) = show_raw_text(ui, id, value, min, max, &accept, config.validation_timing) {
+        return r;
+    }
+
+    let drag_range = drag_range(*value, min, max);
+    let mut drag = egui::DragValue::new(value)
+        .clamp_range(drag_range)
+        .custom_formatter(|n, _| kind.format(n as i64))
+        .custom_parser(accept);
+    if let Some(speed) = speed {
+        drag = drag.speed(speed);
+    }
+
+    let hint = match (min, max) {
+        (None, None) => return add_drag(ui, id, drag, &range, &parse, &accept),
+        (Some(min), None) => format!("{}..", kind.format(min)),
+        (None, Some(max)) => format!("..={}", kind.format(max)),
+        (Some(min), Some(max)) => format!("{}..={}", kind.format(min), kind.format(max)),
+    };
+
+    ui.horizontal(|ui| {
+        add_drag(ui, id, drag, &range, &parse, &accept);
+        ui.weak(hint);
+    })
+    .response
+}
+
+/// Shows a number between −/+ buttons that change it by `step`
+/// and keep it within optional bounds.
+///
+/// The number is displayed with `text` and can't be edited directly.
+pub(crate) fn probe_stepper<N>(
+    ui: &mut Ui,
+    value: &mut N,
+    step: N,
+    min: Option<N>,
+    max: Option<N>,
+    text: impl Fn(N) -> String,
+) -> Response
+where
+    N: Stepped,
+{
+    let current = *value;
+
+    ui.horizontal(|ui| {
+        let can_down = min.is_none_or(|min| current > min);
+        let down = ui.add_enabled(can_down, egui::Button::new("−").small());
+        ui.label(text(current));
+        let can_up = max.is_none_or(|max| current < max);
+        let up = ui.add_enabled(can_up, egui::Button::new("+").small());
+
+        match (down.clicked(), up.clicked()) {
+            (true, false) => *value = step_within(current, step, false, min, max),
+            (false, true) => *value = step_within(current, step, true, min, max),
+            _ => {}
+        }
+    })
+    .response
+}
+
+/// Numbers changed by the stepper in their own type,
+/// so that integers beyond the precision of `f64` stay exact.
+pub(crate) trait Stepped: Numeric {
+    /// Returns the number moved by the magnitude of `step` up or down,
+    /// saturating at the limits of the type.
+    fn stepped(self, step: Self, up: bool) -> Self;
+}
+
+impl Stepped for i64 {
+    fn stepped(self, step: i64, up: bool) -> i64 {
+        match up {
+            true => self.saturating_add_unsigned(step.unsigned_abs()),
+            false => self.saturating_sub_unsigned(step.unsigned_abs()),
+        }
+    }
+}
+
+impl Stepped for u64 {
+    fn stepped(self, step: u64, up: bool) -> u64 {
+        match up {
+            true => self.saturating_add(step),
+            false => self.saturating_sub(step),
+        }
+    }
+}
+
+impl Stepped for f64 {
+    fn stepped(self, step: f64, up: bool) -> f64 {
+        match up {
+            true => self + step.abs(),
+            false => self - step.abs(),
+        }
+    }
+}
+
+/// Returns `value` moved by `step` up or down and clamped to the bounds.
+fn step_within<N: Stepped>(value: N, step: N, up: bool, min: Option<N>, max: Option<N>) -> N {
+    let next = value.stepped(step, up);
+    match (min, max) {
+        (Some(min), _) if next < min => min,
+        (_, Some(max)) if next > max => max,
+        _ => next,
+    }
+}
+
+fn f64_range<N: Numeric>(min: Option<N>, max: Option<N>) -> RangeInclusive<f64> {
+    min.map_or(f64::NEG_INFINITY, N::to_f64)..=max.map_or(f64::INFINITY, N::to_f64)
+}
+
+/// Returns the parsed number if it is accepted under `policy`.
+fn accepted(parsed: Option<f64>, range: &RangeInclusive<f64>, policy: RangePolicy) -> Option<f64> {
+    match policy {
+        RangePolicy::Clamp => parsed,
+        RangePolicy::Reject => parsed.filter(|n| range.contains(n)),
+    }
+}
+
+/// Text entered into a number field that is not accepted.
+/// It is kept until fixed, so that the input is not lost.
+#[derive(Clone)]
+struct RawText(String);
+
+/// Text entered into a number field that was clamped to the range.
+#[derive(Clone)]
+struct ClampedText(String);
+
+/// Text entered into a number field in the current frame that was clamped or rejected.
+#[derive(Clone)]
+struct CoercedText(String, CoercionReason);
+
+/// Takes text entered into the number field `id` in the current frame
+/// if it was clamped or rejected.
+pub(crate) fn take_coerced_text(ctx: &Context, id: Id) -> Option<(String, CoercionReason)> {
+    let coerced_id = id.with("CoercedText");
+    ctx.data_mut(|d| {
+        let coerced = d.get_temp::<CoercedText>(coerced_id)?;
+        d.remove::<CoercedText>(coerced_id);
+        Some((coerced.0, coerced.1))
+    })
+}
+
+fn mark_coerced(ui: &Ui, id: Id, text: &str, reason: CoercionReason) {
+    ui.data_mut(|d| d.insert_temp(id.with("CoercedText"), CoercedText(text.to_owned(), reason)));
+}
+
+/// Returns text entered into the number field `id` that is not accepted yet.
+pub(crate) fn raw_text(ctx: &Context, id: Id) -> Option<String> {
+    ctx.data(|d| d.get_temp::<RawText>(id.with("RawText")))
+        .map(|raw| raw.0)
+}
+
+/// Shows `drag` and keeps the entered text if it is not accepted when editing is finished.
+/// If the text was accepted but clamped, it is shown next to the field until it is edited again.
+fn add_drag(
+    ui: &mut Ui,
+    id: Id,
+    drag: egui::DragValue,
+    range: &RangeInclusive<f64>,
+    parse: &dyn Fn(&str) -> Option<f64>,
+    accept: &dyn Fn(&str) -> Option<f64>,
+) -> Response {
+    // `DragValue` keeps the text being edited in temp data under its own id.
+    let drag_id = ui.next_auto_id();
+    let before = ui.data(|d| d.get_temp::<String>(drag_id));
+    let clamped_id = id.with("ClampedText");
+
+    let r = ui.add(drag);
+
+    if r.gained_focus() {
+        ui.data_mut(|d| d.remove::<ClampedText>(clamped_id));
+    }
+
+    if r.lost_focus() {
+        let text = ui.data(|d| d.get_temp::<String>(drag_id)).or(before);
+        if let Some(text) = text {
+            if accept(&text).is_none() {
+                mark_coerced(ui, id, &text, CoercionReason::Rejected);
+                ui.data_mut(|d| d.insert_temp(id.with("RawText"), RawText(text)));
+            } else if parse(&text).is_some_and(|n| !range.contains(&n)) {
+                mark_coerced(ui, id, &text, CoercionReason::Clamped);
+                ui.data_mut(|d| d.insert_temp(clamped_id, ClampedText(text)));
+            }
+        }
+    }
+
+    if let Some(ClampedText(text)) = ui.data(|d| d.get_temp::<ClampedText>(clamped_id)) {
+        ui.weak(format!("clamped from {text}"));
+    }
+    r
+}
+
+/// Shows the invalid text kept for the number field `id`, if any, in a text field.
+/// The number is updated and the text is dropped as soon as it parses.
+///
+/// Returns `None` if there is no kept text.
+fn show_raw_text<N: Numeric>(
+    ui: &mut Ui,
+    id: Id,
+    value: &mut N,
+    min: Option<N>,
+    max: Option<N>,
+    accept: &dyn Fn(&str) -> Option<f64>,
+    timing: ValidationTiming,
+) -> Option<Response> {
+    let raw_id = id.with("RawText");
+    let RawText(mut text) = ui.data(|d| d.get_temp::<RawText>(raw_id))?;
+
+    let mut discard = false;
+    let r = ui
+        .horizontal(|ui| {
+            let r = ui.add(
+                egui::TextEdit::singleline(&mut text)
+                    .desired_width(ui.spacing().interact_size.x * 2.0),
+            );
+            if timing == ValidationTiming::Live || !r.has_focus() {
+                ui.colored_label(ui.visuals().warn_fg_color, "⚠")
+                    .on_hover_text(
+                    "Not a valid number in range. The value is not changed until the text is fixed",
+                );
+            }
+            discard = ui
+                .small_button("×")
+                .on_hover_text("Discard the text")
+                .clicked();
+            r
+        })
+        .inner;
+
+    match accept(&text) {
+        Some(parsed) => {
+            let min = min.map_or(f64::NEG_INFINITY, N::to_f64);
+            let max = max.map_or(f64::INFINITY, N::to_f64);
+            if !(min..=max).contains(&parsed) {
+                mark_coerced(ui, id, &text, CoercionReason::Clamped);
+            }
+            *value = N::from_f64(parsed.clamp(min, max.max(min)));
+            ui.data_mut(|d| d.remove::<RawText>(raw_id));
+        }
+        None if discard => ui.data_mut(|d| d.remove::<RawText>(raw_id)),
+        None => ui.data_mut(|d| d.insert_temp(raw_id, RawText(text))),
+    }
+
+    Some(r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn localize_groups_and_separators() {
+        assert_eq!(NumberFormat::PLAIN.localize("-1234567.89"), "-1234567.89");
+        assert_eq!(NumberFormat::EN.localize("-1234567.89"), "-1,234,567.89");
+        assert_eq!(NumberFormat::DE.localize("1234567.89"), "1.234.567,89");
+        assert_eq!(
+            NumberFormat::FR.localize("1234567.89"),
+            "1\u{202F}234\u{202F}567,89"
+        );
+        assert_eq!(NumberFormat::EN.localize("123"), "123");
+        assert_eq!(NumberFormat::DE.localize("1000"), "1.000");
+    }
+
+    #[test]
+    fn format_uses_locale() {
+        assert_eq!(NumberFormat::EN.format(1234.5, 1..=1), "1,234.5");
+        assert_eq!(NumberFormat::DE.format(1234.5, 1..=1), "1.234,5");
+    }
+
+    #[test]
+    fn parse_reads_locale() {
+        assert_eq!(NumberFormat::EN.parse("1,234,567.89"), Some(1234567.89));
+        assert_eq!(NumberFormat::DE.parse("1.234.567,89"), Some(1234567.89));
+        assert_eq!(NumberFormat::FR.parse("1 234 567,89"), Some(1234567.89));
+        assert_eq!(NumberFormat::DE.parse("-0,5"), Some(-0.5));
+        assert_eq!(NumberFormat::DE.parse("1,2,3"), None);
+        assert_eq!(NumberFormat::EN.parse("abc"), None);
+    }
+
+    #[test]
+    fn parse_restores_localized() {
+        for format in [
+            NumberFormat::PLAIN,
+            NumberFormat::EN,
+            NumberFormat::DE,
+            NumberFormat::FR,
+        ] {
+            for value in [0.0, -1.5, 1234567.25, 1e9] {
+                assert_eq!(
+                    format.parse(&format.format(value, 2..=2)),
+                    Some(value),
+                    "{format:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn humanize_parses_units() {
+        assert_eq!(HumanizeKind::Bytes.parse("1 MiB"), Some(1_048_576));
+        assert_eq!(HumanizeKind::Bytes.parse("2kb"), Some(2_000));
+        assert_eq!(HumanizeKind::Bytes.parse("512"), Some(512));
+        assert_eq!(HumanizeKind::Millis.parse("1 h 30 min"), Some(5_400_000));
+        assert_eq!(HumanizeKind::Seconds.parse("1.5h"), Some(5_400));
+        assert_eq!(HumanizeKind::Seconds.parse("-2 min"), Some(-120));
+        assert_eq!(HumanizeKind::Seconds.parse("2 parsecs"), None);
+        assert_eq!(HumanizeKind::Seconds.parse(""), None);
+    }
+
+    #[test]
+    fn humanize_round_trips() {
+        assert_eq!(HumanizeKind::Bytes.format(1_048_576), "1 MiB");
+        assert_eq!(HumanizeKind::Millis.format(1_500), "1.5 s");
+        assert_eq!(HumanizeKind::Seconds.format(59), "59 s");
+
+        for kind in [
+            HumanizeKind::Bytes,
+            HumanizeKind::Millis,
+            HumanizeKind::Seconds,
+        ] {
+            for value in [
+                0,
+                1,
+                999,
+                1_000,
+                1_024,
+                1_536,
+                86_400,
+                5_400_000,
+                -3_600,
+                123_456_789,
+            ] {
+                assert_eq!(
+                    kind.parse(&kind.format(value)),
+                    Some(value),
+                    "{kind:?} {value}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn stepper_keeps_large_integers_exact() {
+        let big = (1i64 << 60) + 1;
+        assert_eq!(step_within(big, 1, true, None, None), big + 1);
+        assert_eq!(step_within(big, -1, false, None, None), big - 1);
+        assert_eq!(step_within(u64::MAX - 2, 1, true, None, None), u64::MAX - 1);
+    }
+
+    #[test]
+    fn stepper_saturates_and_clamps() {
+        assert_eq!(step_within(i64::MAX - 1, 5, true, None, None), i64::MAX);
+        assert_eq!(
+            step_within(i64::MIN + 1, i64::MIN, false, None, None),
+            i64::MIN
+        );
+        assert_eq!(step_within(1u64, 5, false, None, None), 0);
+        assert_eq!(step_within(8i64, 5, true, Some(0), Some(10)), 10);
+        assert_eq!(step_within(2i64, 5, false, Some(0), Some(10)), 0);
+        assert_eq!(step_within(0.5, 0.25, true, None, Some(0.6)), 0.6);
+    }
+
+    #[test]
+    fn drag_range_keeps_out_of_range_value() {
+        assert_eq!(drag_range(5i64, Some(0), Some(10)), 0..=10);
+        assert_eq!(drag_range(-5i64, Some(0), Some(10)), -5..=10);
+        assert_eq!(drag_range(50i64, Some(0), Some(10)), 0..=50);
+        assert_eq!(drag_range(7.0, None, Some(2.5)), f64::MIN..=7.0);
+    }
+}
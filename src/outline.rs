@@ -0,0 +1,121 @@
+use std::hash::Hash;
+
+use egui::{Align, Context, Id, Response, Ui};
+
+use crate::{path, Value};
+
+/// Path requested by the outline, waiting for the next frame of the probe.
+#[derive(Clone, Default)]
+struct PendingScroll(String);
+
+/// Path to scroll to in the current frame.
+#[derive(Clone)]
+struct ActiveScroll {
+    path: String,
+    frame: u64,
+}
+
+/// Outline of the first two levels of a value.
+///
+/// Clicking an entry scrolls the `ValueProbe` created with the same `id_source` to that entry.
+/// If the entry is inside a collapsed collection, its closest shown ancestor is scrolled to instead.
+pub struct Outline<'a> {
+    value: &'a Value,
+    id_source: Id,
+}
+
+impl<'a> Outline<'a> {
+    pub fn new(value: &'a Value, id_source: impl Hash) -> Self {
+        Outline {
+            value,
+            id_source: Id::new(id_source),
+        }
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let mut clicked = None;
+
+        let r = ui
+            .vertical(|ui| {
+                for (label, value) in entries(self.value) {
+                    let path = path::join("", &label);
+                    if ui.link(&label).clicked() {
+                        clicked = Some(path.clone());
+                    }
+
+                    ui.indent(&path, |ui| {
+                        for (label, _) in entries(value) {
+                            let path = path::join(&path, &label);
+                            if ui.link(&label).clicked() {
+                                clicked = Some(path);
+                            }
+                        }
+                    });
+                }
+            })
+            .response;
+
+        if let Some(path) = clicked {
+            request_scroll(ui.ctx(), self.id_source, path);
+        }
+
+        r
+    }
+}
+
+/// Returns labels and values of the items of a collection.
+fn entries(value: &Value) -> Vec<(String, &Value)> {
+    match value {
+        Value::List(elems) => elems
+            .iter()
+            .enumerate()
+            .map(|(idx, elem)| (format!("[{idx}]"), elem))
+            .collect(),
+        Value::Map(values) => values.iter().map(|(key, value)| (key.clone(), value)).collect(),
+        Value::Enum { variant, value } => vec![(variant.clone(), &**value)],
+        _ => Vec::new(),
+    }
+}
+
+/// Requests the probe with the given root id to scroll to `path` in the next frame.
+pub(crate) fn request_scroll(ctx: &Context, root: Id, path: String) {
+    ctx.data_mut(|d| d.insert_temp(root.with("ScrollTo"), PendingScroll(path)));
+}
+
+/// Activates the pending scroll request for the current frame.
+/// Called by the root probe before any nested value is shown.
+pub(crate) fn start_scroll(ui: &Ui, root: Id) {
+    let id = root.with("ScrollTo");
+    let frame = ui.ctx().frame_nr();
+
+    ui.ctx().data_mut(|d| {
+        if let Some(PendingScroll(path)) = d.remove_temp::<PendingScroll>(id) {
+            d.insert_temp(id, ActiveScroll { path, frame });
+        }
+    });
+}
+
+/// Scrolls to the probed value if it is the requested one or its ancestor.
+///
+/// Values are probed parent first,
+/// so the deepest shown value on the requested path is the one scrolled to.
+pub(crate) fn scroll_if_requested(ui: &Ui, root: Id, path: &str, r: &Response) {
+    let id = root.with("ScrollTo");
+    let Some(active) = ui.ctx().data(|d| d.get_temp::<ActiveScroll>(id)) else {
+        return;
+    };
+
+    if active.frame != ui.ctx().frame_nr() {
+        ui.ctx().data_mut(|d| d.remove::<ActiveScroll>(id));
+        return;
+    }
+
+    let is_ancestor = active
+        .path
+        .strip_prefix(path)
+        .is_some_and(|rest| path.is_empty() || rest.is_empty() || rest.starts_with(['.', '[']));
+
+    if is_ancestor {
+        r.scroll_to_me(Some(Align::TOP));
+    }
+}
@@ -0,0 +1,121 @@
+use hashbrown::HashMap;
+
+use crate::{path, Desc, Value};
+
+/// Descriptors that replace the ones of the root descriptor at specific paths.
+///
+/// Pass to `ValueProbe::with_overrides` to show the values at those paths
+/// with the overriding descriptors.
+#[derive(Clone, Debug, Default)]
+pub struct DescOverrides {
+    descs: HashMap<String, Desc>,
+}
+
+impl DescOverrides {
+    pub fn new() -> Self {
+        DescOverrides::default()
+    }
+
+    /// Overrides the descriptor of the value at `path`, e.g. `servers[0].port`.
+    ///
+    /// `patch` is merged with the descriptor that `root` has at that path, see `Desc::merged`.
+    /// The value at `path` is coerced to the resulting descriptor,
+    /// other values are left untouched.
+    pub fn set(&mut self, root: &Desc, path: impl Into<String>, patch: &Desc, value: &mut Value) {
+        let path = path.into();
+
+        let desc = match path::lookup_desc(root, &path) {
+            Some(base) => base.merged(patch),
+            None => patch.clone(),
+        };
+
+        if let Some(value) = path::lookup_mut(value, &path) {
+            if let Some(coerced) = value.coerce(&desc) {
+                *value = coerced;
+            }
+        }
+
+        self.descs.insert(path, desc);
+    }
+
+    /// Removes the override at `path`.
+    pub fn remove(&mut self, path: &str) -> Option<Desc> {
+        self.descs.remove(path)
+    }
+
+    /// Returns the overriding descriptor at `path`.
+    pub fn get(&self, path: &str) -> Option<&Desc> {
+        self.descs.get(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        tests::{int_range, list_desc, string_desc},
+        StructField,
+    };
+
+    fn servers_desc() -> Desc {
+        let server = Desc::Struct {
+            fields: vec![
+                StructField::new("port", int_range(Some(0), Some(65535))),
+                StructField::new("backlog", int_range(Some(0), None)),
+            ],
+        };
+        Desc::Struct {
+            fields: vec![StructField::new("servers", list_desc(server, None, None))],
+        }
+    }
+
+    fn server(port: i64, backlog: i64) -> Value {
+        [("port".to_owned(), Value::Int(port)), ("backlog".to_owned(), Value::Int(backlog))]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn tightening_range_reclamps_only_targeted_field() {
+        let desc = servers_desc();
+        let mut value: Value = [("servers".to_owned(), Value::from(vec![server(8080, 2000), server(9090, 2000)]))]
+            .into_iter()
+            .collect();
+
+        let mut overrides = DescOverrides::new();
+        overrides.set(&desc, "servers[0].port", &int_range(None, Some(1024)), &mut value);
+
+        let expected: Value = [("servers".to_owned(), Value::from(vec![server(1024, 2000), server(9090, 2000)]))]
+            .into_iter()
+            .collect();
+        assert_eq!(value, expected);
+
+        // The patch is merged with the base descriptor, keeping its lower bound.
+        assert_eq!(overrides.get("servers[0].port"), Some(&int_range(Some(0), Some(1024))));
+        assert_eq!(overrides.get("servers[1].port"), None);
+    }
+
+    #[test]
+    fn override_at_unknown_path_is_kept_as_is() {
+        let mut value = Value::Null;
+        let mut overrides = DescOverrides::new();
+        overrides.set(&servers_desc(), "clients", &Desc::Bool, &mut value);
+
+        assert_eq!(value, Value::Null);
+        assert_eq!(overrides.get("clients"), Some(&Desc::Bool));
+        assert_eq!(overrides.remove("clients"), Some(Desc::Bool));
+        assert_eq!(overrides.get("clients"), None);
+    }
+
+    #[test]
+    fn patch_turns_flags_off() {
+        let mut base = string_desc(None, Some(1), None);
+        if let Desc::String { trim, multiline, .. } = &mut base {
+            *trim = true;
+            *multiline = true;
+        }
+
+        let merged = base.merged(&string_desc(None, None, None));
+        assert_eq!(merged, string_desc(None, Some(1), None));
+    }
+}
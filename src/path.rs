@@ -0,0 +1,272 @@
+use crate::{Desc, Value};
+
+/// Appends a probe label to the path of its parent.
+///
+/// List indices are already formatted as `[idx]` and are appended as is,
+/// map keys and field names are separated with a dot.
+pub(crate) fn join(parent: &str, label: &str) -> String {
+    if parent.is_empty() || label.starts_with('[') {
+        format!("{parent}{label}")
+    } else {
+        format!("{parent}.{label}")
+    }
+}
+
+/// Single step of a path to a nested value, see `Value::get_path`.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub enum PathSeg {
+    /// Key of a map entry, or name of the variant of an enum.
+    Key(String),
+
+    /// Index of a list element.
+    Index(usize),
+}
+
+impl PathSeg {
+    /// Parses a path like `servers[0].address`, as used in `ProbeError` paths.
+    ///
+    /// Returns `None` if an index is not a valid number or is not closed.
+    pub fn parse(path: &str) -> Option<Vec<PathSeg>> {
+        let segments = segments(path)?
+            .into_iter()
+            .map(|segment| match segment {
+                Segment::Key(key) => PathSeg::Key(key.to_owned()),
+                Segment::Index(idx) => PathSeg::Index(idx),
+            })
+            .collect();
+        Some(segments)
+    }
+}
+
+/// Single step of a path.
+enum Segment<'a> {
+    Key(&'a str),
+    Index(usize),
+}
+
+/// Splits a path like `servers[0].address` into segments.
+///
+/// Returns `None` if an index is not a valid number or is not closed.
+fn segments(path: &str) -> Option<Vec<Segment<'_>>> {
+    let mut segments = Vec::new();
+    let mut rest = path;
+
+    while !rest.is_empty() {
+        if let Some(after) = rest.strip_prefix('[') {
+            let end = after.find(']')?;
+            segments.push(Segment::Index(after[..end].parse().ok()?));
+            rest = &after[end + 1..];
+        } else {
+            let rest_key = rest.strip_prefix('.').unwrap_or(rest);
+            let end = rest_key.find(['.', '[']).unwrap_or(rest_key.len());
+            segments.push(Segment::Key(&rest_key[..end]));
+            rest = &rest_key[end..];
+        }
+    }
+
+    Some(segments)
+}
+
+/// Returns labels of the segments of `path` with paths of the values they lead to,
+/// e.g. `("servers", "servers")`, `("[0]", "servers[0]")` for `servers[0]`.
+///
+/// A malformed path is returned as a single segment.
+pub(crate) fn ancestors(path: &str) -> Vec<(String, String)> {
+    let Some(segments) = segments(path) else {
+        return vec![(path.to_owned(), path.to_owned())];
+    };
+
+    let mut prefix = String::new();
+    segments
+        .into_iter()
+        .map(|segment| {
+            let label = match segment {
+                Segment::Key(key) => key.to_owned(),
+                Segment::Index(idx) => format!("[{idx}]"),
+            };
+            prefix = join(&prefix, &label);
+            (label, prefix.clone())
+        })
+        .collect()
+}
+
+/// Finds the nested value at `path`.
+pub(crate) fn lookup<'a>(value: &'a Value, path: &str) -> Option<&'a Value> {
+    segments(path)?
+        .into_iter()
+        .try_fold(value, |value, segment| match (segment, value) {
+            (Segment::Key(key), Value::Map(values)) => values.get(key),
+            (Segment::Key(key), Value::Enum { variant, value }) if variant == key => Some(&**value),
+            (Segment::Index(idx), Value::List(elems)) => elems.get(idx),
+            _ => None,
+        })
+}
+
+/// Finds the nested value at `path` for modification.
+pub(crate) fn lookup_mut<'a>(value: &'a mut Value, path: &str) -> Option<&'a mut Value> {
+    segments(path)?
+        .into_iter()
+        .try_fold(value, |value, segment| match (segment, value) {
+            (Segment::Key(key), Value::Map(values)) => values.get_mut(key),
+            (Segment::Key(key), Value::Enum { variant, value }) => (variant == key).then_some(&mut **value),
+            (Segment::Index(idx), Value::List(elems)) => elems.get_mut(idx),
+            _ => None,
+        })
+}
+
+/// Finds the descriptor of the nested value at `path`.
+///
+/// Optional, documented and defaulted values are looked through, as they don't add a path segment.
+pub(crate) fn lookup_desc<'a>(desc: &'a Desc, path: &str) -> Option<&'a Desc> {
+    segments(path)?
+        .into_iter()
+        .try_fold(desc, |mut desc, segment| {
+            while let Desc::Optional { inner } | Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner } = desc {
+                desc = inner;
+            }
+            match (segment, desc) {
+                (Segment::Key(_), Desc::Map { value_desc, .. }) => value_desc.as_deref(),
+                (Segment::Key(key), Desc::Enum { variants }) => {
+                    variants.iter().find(|variant| variant.name == key).and_then(|variant| variant.desc.as_ref())
+                }
+                (Segment::Key(key), Desc::Struct { fields }) => {
+                    fields.iter().find(|field| field.name == key).map(|field| &field.desc)
+                }
+                (Segment::Index(_), Desc::List { elem_desc, .. }) => elem_desc.as_deref(),
+                (Segment::Index(idx), Desc::Tuple { elems }) => elems.get(idx),
+                _ => None,
+            }
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StructField;
+
+    fn key(key: &str) -> PathSeg {
+        PathSeg::Key(key.to_owned())
+    }
+
+    /// `{"servers": [{"port": 80}, {"port": 443}]}`
+    fn servers() -> Value {
+        let server = |port: i64| [("port".to_owned(), Value::from(port))].into_iter().collect::<Value>();
+        [("servers".to_owned(), Value::from(vec![server(80), server(443)]))]
+            .into_iter()
+            .collect()
+    }
+
+    #[test]
+    fn get_path_follows_map_list_map() {
+        let value = servers();
+        let path = [key("servers"), PathSeg::Index(1), key("port")];
+        assert_eq!(value.get_path(&path), Some(&Value::Int(443)));
+        assert_eq!(value.get_path(&[]), Some(&value));
+    }
+
+    #[test]
+    fn get_path_mut_edits_in_place() {
+        let mut value = servers();
+        let path = [key("servers"), PathSeg::Index(0), key("port")];
+        *value.get_path_mut(&path).unwrap() = Value::Int(8080);
+        assert_eq!(value.get_path(&path), Some(&Value::Int(8080)));
+    }
+
+    #[test]
+    fn invalid_path_returns_none() {
+        let mut value = servers();
+        for path in [
+            vec![key("clients")],
+            vec![key("servers"), PathSeg::Index(2)],
+            vec![PathSeg::Index(0)],
+            vec![key("servers"), key("port")],
+            vec![key("servers"), PathSeg::Index(0), key("port"), key("number")],
+        ] {
+            assert_eq!(value.get_path(&path), None, "{path:?}");
+            assert_eq!(value.get_path_mut(&path), None, "{path:?}");
+        }
+    }
+
+    #[test]
+    fn key_selects_enum_variant_value() {
+        let value = Value::Enum {
+            variant: "Some".to_owned(),
+            value: Box::new(Value::Int(1)),
+        };
+        assert_eq!(value.get_path(&[key("Some")]), Some(&Value::Int(1)));
+        assert_eq!(value.get_path(&[key("None")]), None);
+    }
+
+    #[test]
+    fn parse_splits_keys_and_indices() {
+        assert_eq!(
+            PathSeg::parse("servers[0].port"),
+            Some(vec![key("servers"), PathSeg::Index(0), key("port")])
+        );
+        assert_eq!(PathSeg::parse("[1][2]"), Some(vec![PathSeg::Index(1), PathSeg::Index(2)]));
+        assert_eq!(PathSeg::parse(""), Some(Vec::new()));
+        assert_eq!(PathSeg::parse("servers[x]"), None);
+        assert_eq!(PathSeg::parse("servers[0"), None);
+    }
+
+    #[test]
+    fn parsed_path_matches_lookup() {
+        let value = servers();
+        let path = "servers[1].port";
+        assert_eq!(value.get_path(&PathSeg::parse(path).unwrap()), lookup(&value, path));
+    }
+
+
+    #[test]
+    fn join_separates_keys_but_not_indices() {
+        assert_eq!(join("", "servers"), "servers");
+        assert_eq!(join("servers", "[0]"), "servers[0]");
+        assert_eq!(join("servers[0]", "port"), "servers[0].port");
+        assert_eq!(join("", "[1]"), "[1]");
+    }
+
+    #[test]
+    fn ancestors_lead_to_each_segment() {
+        let found = ancestors("servers[0].port");
+        let expected = [("servers", "servers"), ("[0]", "servers[0]"), ("port", "servers[0].port")];
+        assert_eq!(found.len(), expected.len());
+        for ((label, path), (expected_label, expected_path)) in found.iter().zip(expected) {
+            assert_eq!((label.as_str(), path.as_str()), (expected_label, expected_path));
+        }
+
+        assert_eq!(ancestors("servers[x"), [("servers[x".to_owned(), "servers[x".to_owned())]);
+    }
+
+    #[test]
+    fn lookup_mut_edits_nested_value() {
+        let mut value = servers();
+        *lookup_mut(&mut value, "servers[0].port").unwrap() = Value::Int(8080);
+        assert_eq!(lookup(&value, "servers[0].port"), Some(&Value::Int(8080)));
+        assert!(lookup_mut(&mut value, "servers.port").is_none());
+        assert!(lookup(&value, "servers[").is_none());
+    }
+
+    #[test]
+    fn lookup_desc_looks_through_wrappers() {
+        let port = Desc::from_value(&Value::Int(0));
+        let server = Desc::Struct {
+            fields: vec![StructField::new("port", port.clone())],
+        };
+        let servers = Desc::List {
+            elem_desc: Some(Box::new(Desc::Optional { inner: Box::new(server) })),
+            empty_hint: None,
+            collapsed_by_default: false,
+            min_len: None,
+            max_len: None,
+            multi_select: false,
+            unique_by: None,
+        };
+        let desc = Desc::Struct {
+            fields: vec![StructField::new("servers", servers.advanced())],
+        };
+
+        assert_eq!(lookup_desc(&desc, "servers[3].port"), Some(&port));
+        assert_eq!(lookup_desc(&desc, "servers[3].address"), None);
+        assert_eq!(lookup_desc(&desc, "servers.port"), None);
+    }
+}
@@ -0,0 +1,98 @@
+use egui::{Context, Id};
+
+/// Pattern of `Desc::String` compiled for the whole string, or the error compiling it.
+#[cfg(feature = "regex")]
+#[derive(Clone)]
+struct Compiled {
+    pattern: String,
+    regex: Result<regex::Regex, String>,
+}
+
+#[cfg(feature = "regex")]
+impl Compiled {
+    fn new(pattern: &str) -> Self {
+        // Check the pattern alone first, so errors don't point into the anchors.
+        let regex = regex::Regex::new(pattern)
+            .and_then(|_| regex::Regex::new(&format!("^(?:{pattern})$")))
+            .map_err(|err| {
+                let err = err.to_string();
+                let last = err.lines().last().unwrap_or_default();
+                last.strip_prefix("error: ").unwrap_or(last).to_owned()
+            });
+
+        Compiled {
+            pattern: pattern.to_owned(),
+            regex,
+        }
+    }
+
+    fn matches(&self, text: &str) -> Result<bool, String> {
+        match &self.regex {
+            Ok(regex) => Ok(regex.is_match(text)),
+            Err(err) => Err(err.clone()),
+        }
+    }
+}
+
+/// Returns `true` if the whole `text` matches `pattern`,
+/// or the error message if the pattern is invalid.
+///
+/// Without the `regex` feature every text matches.
+#[cfg_attr(not(feature = "regex"), allow(unused_variables))]
+pub(crate) fn matches(pattern: &str, text: &str) -> Result<bool, String> {
+    #[cfg(feature = "regex")]
+    return Compiled::new(pattern).matches(text);
+
+    #[cfg(not(feature = "regex"))]
+    Ok(true)
+}
+
+/// Like `matches`, but keeps the compiled pattern of the field `id` between frames.
+#[cfg_attr(not(feature = "regex"), allow(unused_variables))]
+pub(crate) fn matches_cached(ctx: &Context, id: Id, pattern: &str, text: &str) -> Result<bool, String> {
+    #[cfg(feature = "regex")]
+    {
+        let id = id.with("Pattern");
+        let compiled = ctx.data_mut(|d| {
+            match d.get_temp::<Compiled>(id) {
+                Some(compiled) if compiled.pattern == pattern => compiled,
+                _ => {
+                    let compiled = Compiled::new(pattern);
+                    d.insert_temp(id, compiled.clone());
+                    compiled
+                }
+            }
+        });
+        compiled.matches(text)
+    }
+
+    #[cfg(not(feature = "regex"))]
+    Ok(true)
+}
+
+#[cfg(all(test, feature = "regex"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_whole_text() {
+        assert_eq!(matches("[a-z]+", "abc"), Ok(true));
+        assert_eq!(matches("[a-z]+", "abc1"), Ok(false));
+        assert_eq!(matches("a|b", "ab"), Ok(false));
+    }
+
+    #[test]
+    fn invalid_pattern_is_an_error() {
+        let err = matches("[a-", "a").unwrap_err();
+        assert!(!err.is_empty());
+        assert!(!err.starts_with("error: "));
+    }
+
+    #[test]
+    fn cached_pattern_follows_changes() {
+        let ctx = Context::default();
+        let id = Id::new("field");
+        assert_eq!(matches_cached(&ctx, id, "[0-9]+", "42"), Ok(true));
+        assert_eq!(matches_cached(&ctx, id, "[a-z]+", "42"), Ok(false));
+    }
+}
@@ -0,0 +1,34 @@
+use std::hash::Hash;
+
+use egui::{Id, Response, Ui};
+use egui_probe::Probe;
+
+use crate::{Desc, ValueProbe};
+
+/// Read-only preview of the default value of a descriptor.
+///
+/// The value is created with `Desc::default_value` every frame,
+/// so the preview follows the descriptor while it is edited.
+pub struct DefaultPreview<'a> {
+    desc: &'a Desc,
+    id_source: Id,
+}
+
+impl<'a> DefaultPreview<'a> {
+    pub fn new(desc: &'a Desc, id_source: impl Hash) -> Self {
+        DefaultPreview {
+            desc,
+            id_source: Id::new(id_source),
+        }
+    }
+
+    pub fn show(self, ui: &mut Ui) -> Response {
+        let mut value = self.desc.default_value();
+
+        ui.add_enabled_ui(false, |ui| {
+            let mut probe = ValueProbe::new(Some(self.desc), &mut value, self.id_source);
+            Probe::new("Default", &mut probe).show(ui)
+        })
+        .inner
+    }
+}
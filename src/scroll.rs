@@ -0,0 +1,97 @@
+use egui::{scroll_area::ScrollAreaOutput, Id, Ui};
+
+/// Keeps vertical scroll areas, e.g. a descriptor panel and the value panel beside it,
+/// scrolled to the same relative position.
+///
+/// Scrolling one panel moves the others to the matching fraction of their own scroll range.
+#[derive(Clone, Debug, Default)]
+pub struct ScrollSync {
+    fraction: f32,
+    max: Vec<f32>,
+}
+
+impl ScrollSync {
+    pub fn new() -> Self {
+        ScrollSync::default()
+    }
+
+    /// Shows `add_contents` in the vertical scroll area of `panel`,
+    /// any small number distinguishing the synchronized panels.
+    pub fn show<R>(&mut self, ui: &mut Ui, panel: usize, add_contents: impl FnOnce(&mut Ui) -> R) -> ScrollAreaOutput<R> {
+        if self.max.len() <= panel {
+            self.max.resize(panel + 1, 0.0);
+        }
+
+        let output = egui::ScrollArea::vertical()
+            .id_source(Id::new("ScrollSync").with(panel))
+            .vertical_scroll_offset(self.fraction * self.max[panel])
+            .show(ui, add_contents);
+
+        // Panels that couldn't scroll yet have no offset to follow.
+        let max = (output.content_size.y - output.inner_rect.height()).max(0.0);
+        if max > 0.0 && self.max[panel] > 0.0 {
+            self.fraction = output.state.offset.y / max;
+        }
+        self.max[panel] = max;
+        output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use egui::{pos2, vec2, Context, Event, Pos2, RawInput, Rect};
+
+    use super::*;
+
+    /// Shows two panels side by side, the right one about twice as tall as the left one,
+    /// and returns how far each is scrolled as a fraction of its scroll range.
+    fn show_panels(ctx: &Context, sync: &mut ScrollSync, events: Vec<Event>) -> [f32; 2] {
+        let input = RawInput {
+            screen_rect: Some(Rect::from_min_size(pos2(0.0, 0.0), vec2(400.0, 200.0))),
+            events,
+            ..Default::default()
+        };
+        let mut fractions = [0.0; 2];
+        let mut show = |ui: &mut Ui, panel: usize, height: f32| {
+            let output = sync.show(ui, panel, |ui| ui.allocate_space(vec2(ui.available_width(), height)));
+            fractions[panel] = output.state.offset.y / (output.content_size.y - output.inner_rect.height());
+        };
+        let _ = ctx.run(input, |ctx| {
+            egui::SidePanel::left("left").exact_width(200.0).show(ctx, |ui| show(ui, 0, 1200.0));
+            egui::CentralPanel::default().show(ctx, |ui| show(ui, 1, 2200.0));
+        });
+        fractions
+    }
+
+    /// Scrolls by `delta` with the pointer at `pos` and returns the fractions once scrolling settles.
+    fn scroll(ctx: &Context, sync: &mut ScrollSync, pos: Pos2, delta: f32) -> [f32; 2] {
+        show_panels(ctx, sync, vec![Event::PointerMoved(pos), Event::Scroll(vec2(0.0, delta))]);
+        let mut fractions = show_panels(ctx, sync, Vec::new());
+        for _ in 0..100 {
+            let settled = show_panels(ctx, sync, Vec::new());
+            if settled == fractions {
+                break;
+            }
+            fractions = settled;
+        }
+        fractions
+    }
+
+    #[test]
+    fn scrolling_one_panel_moves_the_other() {
+        let ctx = Context::default();
+        let mut sync = ScrollSync::new();
+        for _ in 0..2 {
+            show_panels(&ctx, &mut sync, Vec::new());
+        }
+
+        let [left, right] = scroll(&ctx, &mut sync, pos2(300.0, 100.0), -600.0);
+        assert!(right > 0.0, "{right}");
+        assert!((left - right).abs() < 1e-3, "{left} {right}");
+
+        let scrolled = left;
+        let [left, right] = scroll(&ctx, &mut sync, pos2(100.0, 100.0), 100.0);
+        assert!(left > 0.0 && left < scrolled, "{left} {scrolled}");
+        assert!((left - right).abs() < 1e-3, "{left} {right}");
+    }
+}
@@ -0,0 +1,213 @@
+use std::fmt;
+
+use indexmap::IndexMap;
+use serde::{
+    de::{self, MapAccess, SeqAccess, Visitor},
+    ser::SerializeMap,
+    Deserialize, Deserializer, Serialize, Serializer,
+};
+
+use crate::{color, Value};
+
+/// Values are serialized in their natural form without variant tags:
+/// scalars as scalars, byte blobs as bytes, lists as sequences
+/// and maps as maps with keys in insertion order.
+/// Colors are written as `#rrggbb` or `#rrggbbaa` strings and are deserialized back as strings.
+/// Enums are written as a map with the variant name as the single key
+/// and are deserialized back as such a map.
+/// `Null` is written as a none value and is read back as `Null`.
+///
+/// Formats without non-finite floats, like JSON, may write them as `null`,
+/// which is deserialized back as `Null` as well.
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Value::Bool(value) => serializer.serialize_bool(*value),
+            Value::Int(value) => serializer.serialize_i64(*value),
+            Value::Uint(value) => serializer.serialize_u64(*value),
+            Value::Float(value) => serializer.serialize_f64(*value),
+            Value::String(value) => serializer.serialize_str(value),
+            Value::Bytes(bytes) => serializer.serialize_bytes(bytes),
+            Value::Color(rgba) => serializer.serialize_str(&color::format(*rgba)),
+            Value::List(elems) => serializer.collect_seq(elems),
+            Value::Map(values) => serializer.collect_map(values),
+            Value::Enum { variant, value } => {
+                let mut map = serializer.serialize_map(Some(1))?;
+                map.serialize_entry(variant, value)?;
+                map.end()
+            }
+            Value::Null => serializer.serialize_none(),
+        }
+    }
+}
+
+/// Integers are deserialized as `Value::Int`, so `Value::Uint` is read back as an int,
+/// and integers that don't fit `i64` are deserialized as floats,
+/// `null` as `Value::Null` and bytes as `Value::Bytes`.
+/// Formats without bytes, like JSON, write them as a sequence of integers,
+/// which is deserialized back as a list.
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(ValueVisitor)
+    }
+}
+
+struct ValueVisitor;
+
+impl<'de> Visitor<'de> for ValueVisitor {
+    type Value = Value;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a boolean, number, null, string, bytes, sequence or map")
+    }
+
+    fn visit_bool<E: de::Error>(self, value: bool) -> Result<Value, E> {
+        Ok(Value::Bool(value))
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<Value, E> {
+        Ok(Value::Int(value))
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<Value, E> {
+        Ok(i64::try_from(value).map_or(Value::Float(value as f64), Value::Int))
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<Value, E> {
+        Ok(Value::Float(value))
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Value, E> {
+        Ok(Value::Null)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<Value, E> {
+        Ok(Value::String(value.to_owned()))
+    }
+
+    fn visit_string<E: de::Error>(self, value: String) -> Result<Value, E> {
+        Ok(Value::String(value))
+    }
+
+    fn visit_bytes<E: de::Error>(self, value: &[u8]) -> Result<Value, E> {
+        Ok(Value::Bytes(value.to_vec()))
+    }
+
+    fn visit_byte_buf<E: de::Error>(self, value: Vec<u8>) -> Result<Value, E> {
+        Ok(Value::Bytes(value))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Value, A::Error> {
+        let mut elems = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+        while let Some(elem) = seq.next_element()? {
+            elems.push(elem);
+        }
+        Ok(Value::List(elems))
+    }
+
+    fn visit_map<A: MapAccess<'de>>(self, mut map: A) -> Result<Value, A::Error> {
+        let mut values = IndexMap::with_capacity(map.size_hint().unwrap_or(0));
+        while let Some((key, value)) = map.next_entry()? {
+            values.insert(key, value);
+        }
+        Ok(Value::Map(values))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(value: &Value) -> Value {
+        serde_json::from_str(&serde_json::to_string(value).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn scalars_round_trip() {
+        for value in [
+            Value::Bool(true),
+            Value::Int(-7),
+            Value::Int(i64::MAX),
+            Value::Float(1.5),
+            Value::String("text".to_owned()),
+        ] {
+            assert_eq!(round_trip(&value), value);
+        }
+    }
+
+    #[test]
+    fn collections_are_natural_json() {
+        let inner: Value = [("flag".to_owned(), Value::Bool(false))].into_iter().collect();
+        let value: Value = [
+            ("list".to_owned(), Value::from(vec![Value::Int(1), inner.clone()])),
+            ("map".to_owned(), inner),
+        ]
+        .into_iter()
+        .collect();
+
+        assert_eq!(
+            serde_json::to_string(&value).unwrap(),
+            r#"{"list":[1,{"flag":false}],"map":{"flag":false}}"#
+        );
+        assert_eq!(round_trip(&value), value);
+    }
+
+    #[test]
+    fn untagged_kinds_read_back_as_natural_kinds() {
+        assert_eq!(round_trip(&Value::Uint(3)), Value::Int(3));
+        assert_eq!(round_trip(&Value::Uint(u64::MAX)), Value::Float(u64::MAX as f64));
+        assert_eq!(round_trip(&Value::Color([255, 0, 0, 255])), Value::String("#ff0000".to_owned()));
+        assert_eq!(round_trip(&Value::Bytes(vec![1, 2])), Value::from(vec![Value::Int(1), Value::Int(2)]));
+
+        let variant = Value::Enum {
+            variant: "Some".to_owned(),
+            value: Box::new(Value::Int(1)),
+        };
+        assert_eq!(round_trip(&variant), [("Some".to_owned(), Value::Int(1))].into_iter().collect());
+    }
+
+
+    #[test]
+    fn nested_map_with_list_round_trips() {
+        let point: Value = [("x".to_owned(), Value::Float(0.5)), ("y".to_owned(), Value::Int(-2))]
+            .into_iter()
+            .collect();
+        let value: Value = [
+            ("points".to_owned(), Value::from(vec![point.clone(), point])),
+            ("name".to_owned(), Value::from("path")),
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(round_trip(&value), value);
+    }
+
+    #[test]
+    fn non_finite_floats_do_not_panic() {
+        for value in [f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert_eq!(serde_json::to_string(&Value::Float(value)).unwrap(), "null");
+        }
+    }
+
+
+    #[test]
+    fn null_round_trips_as_null() {
+        assert_eq!(serde_json::to_string(&Value::Null).unwrap(), "null");
+        assert_eq!(round_trip(&Value::Null), Value::Null);
+        assert_eq!(round_trip(&Value::Float(f64::NAN)), Value::Null);
+
+        let value: Value = [("parent".to_owned(), Value::Null)].into_iter().collect();
+        assert_eq!(round_trip(&value), value);
+    }
+
+
+    #[test]
+    fn map_serializes_in_insertion_order() {
+        let value: Value = ["c", "a", "b"].into_iter().map(|key| (key.to_owned(), Value::Bool(true))).collect();
+
+        assert_eq!(serde_json::to_string(&value).unwrap(), r#"{"c":true,"a":true,"b":true}"#);
+    }
+}
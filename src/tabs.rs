@@ -0,0 +1,78 @@
+use egui::{Response, Ui};
+use egui_probe::Probe;
+
+use crate::{path, Desc, Value, ValueProbe};
+
+impl ValueProbe<'_> {
+    /// Shows map entries grouped into tabs as defined by `Desc::Map::tabs`.
+    ///
+    /// Entries whose keys are not listed in any tab are shown in the trailing "Other" tab.
+    /// Values that are not maps with tabs are shown with the regular `Probe`.
+    pub fn show_tabs(&mut self, ui: &mut Ui) -> Response {
+        let (value_desc, tabs) = match self.desc.map(Desc::unwrapped) {
+            Some(Desc::Map { value_desc, tabs, .. }) if !tabs.is_empty() => (value_desc, tabs),
+            _ => return Probe::new("value", self).show(ui),
+        };
+
+        self.prepare_root(ui);
+
+        let Value::Map(values) = &mut *self.value else {
+            return Probe::new("value", self).show(ui);
+        };
+
+        let other: Vec<&String> = values
+            .keys()
+            .filter(|key| !tabs.iter().any(|tab| tab.keys.contains(key)))
+            .collect();
+
+        let id = ui.make_persistent_id(self.id_source.with("Tabs"));
+        let mut selected = ui.ctx().data(|d| d.get_temp::<usize>(id)).unwrap_or(0);
+
+        let tab_count = tabs.len() + usize::from(!other.is_empty());
+        if selected >= tab_count {
+            selected = 0;
+        }
+
+        let keys: Vec<String> = match tabs.get(selected) {
+            Some(tab) => tab.keys.clone(),
+            None => other.into_iter().cloned().collect(),
+        };
+
+        let r = ui
+            .vertical(|ui| {
+                ui.horizontal(|ui| {
+                    for (idx, tab) in tabs.iter().enumerate() {
+                        ui.selectable_value(&mut selected, idx, &tab.name);
+                    }
+                    if tab_count > tabs.len() {
+                        ui.selectable_value(&mut selected, tabs.len(), "Other");
+                    }
+                });
+                ui.separator();
+
+                let id = self.id_source.with("Map");
+                for key in &keys {
+                    let path = path::join(&self.path, key);
+                    if !self.inherited.is_visible(&path) {
+                        continue;
+                    }
+
+                    if let Some(value) = values.get_mut(key) {
+                        let mut probe = ValueProbe::nested(
+                            value_desc.as_deref(),
+                            value,
+                            id.with(key),
+                            path,
+                            self.depth + 1,
+                            self.inherited.reborrow(),
+                        );
+                        probe.show(ui, key);
+                    }
+                }
+            })
+            .response;
+
+        ui.ctx().data_mut(|d| d.insert_temp(id, selected));
+        r
+    }
+}
@@ -0,0 +1,529 @@
+use crate::{bounds::Bound, path, pattern, Desc, EnumVariant, Kind, ProbeError, Value};
+
+impl Desc {
+    /// Checks the value against this descriptor without showing it.
+    ///
+    /// Reports the same problems that `ValueProbe` shows,
+    /// with paths like `items[3].name`, in the order of the value tree.
+    pub fn validate(&self, value: &Value) -> Result<(), Vec<ProbeError>> {
+        let mut errors = Vec::new();
+        validate(self, value, value, "", &mut errors);
+        match errors.is_empty() {
+            true => Ok(()),
+            false => Err(errors),
+        }
+    }
+}
+
+impl Value {
+    /// Checks this value against `desc` without showing it, see `Desc::validate`.
+    pub fn validate(&self, desc: &Desc) -> Result<(), Vec<ProbeError>> {
+        desc.validate(self)
+    }
+}
+
+/// Collects errors of `value` located at `path` in `root` against `desc`.
+///
+/// Reports the same problems that `ValueProbe` shows for the value.
+pub(crate) fn validate(desc: &Desc, value: &Value, root: &Value, path: &str, errors: &mut Vec<ProbeError>) {
+    let mismatch = |expected: &'static str| ProbeError::TypeMismatch {
+        path: path.to_owned(),
+        expected,
+        found: value.kind(),
+    };
+
+    match (desc, value) {
+        (Desc::Bool, Value::Bool(_)) => {}
+        (Desc::Int { min, max, min_path, max_path, .. }, Value::Int(value)) => {
+            let min = bound(root, min_path, *min);
+            let max = bound(root, max_path, *max);
+            check_range(path, *value, min, max, errors);
+        }
+        (Desc::Uint { min, max }, Value::Uint(value)) => check_range(path, *value, *min, *max, errors),
+        (Desc::Float { min, max, min_path, max_path, .. }, Value::Float(value)) => {
+            let min = bound(root, min_path, *min);
+            let max = bound(root, max_path, *max);
+            check_range(path, *value, min, max, errors);
+        }
+        (Desc::String { variants, min_len, max_len, pattern, .. }, Value::String(value)) => match variants {
+            Some(variants) => {
+                if !variants.iter().any(|v| v.name == *value) {
+                    errors.push(ProbeError::NotInVariants {
+                        path: path.to_owned(),
+                        value: value.clone(),
+                    });
+                }
+            }
+            None => {
+                let len = value.chars().count();
+                if min_len.is_some_and(|min| len < min) || max_len.is_some_and(|max| len > max) {
+                    errors.push(ProbeError::TextLength {
+                        path: path.to_owned(),
+                        min: *min_len,
+                        max: *max_len,
+                        found: len,
+                    });
+                }
+                if let Some(pattern) = pattern {
+                    match pattern::matches(pattern, value) {
+                        Ok(true) => {}
+                        Ok(false) => errors.push(ProbeError::PatternMismatch {
+                            path: path.to_owned(),
+                            value: value.clone(),
+                            pattern: pattern.clone(),
+                        }),
+                        Err(error) => errors.push(ProbeError::InvalidPattern {
+                            path: path.to_owned(),
+                            pattern: pattern.clone(),
+                            error,
+                        }),
+                    }
+                }
+            }
+        },
+        (Desc::Color { .. }, Value::Color(_)) => {}
+        (Desc::Bytes { max_len }, Value::Bytes(bytes)) => {
+            if let Some(max) = max_len.filter(|&max| bytes.len() > max) {
+                errors.push(ProbeError::TooLong {
+                    path: path.to_owned(),
+                    limit: max,
+                    found: bytes.len(),
+                });
+            }
+        }
+        (Desc::List { elem_desc, min_len, max_len, unique_by, .. }, Value::List(elems)) => {
+            if let Some(field) = unique_by {
+                for idx in duplicates(elems, field) {
+                    errors.push(ProbeError::NotUnique {
+                        path: path::join(path, &format!("[{idx}]")),
+                        field: field.clone(),
+                    });
+                }
+            }
+            if let Some(min) = min_len.filter(|&min| elems.len() < min) {
+                errors.push(ProbeError::TooShort {
+                    path: path.to_owned(),
+                    limit: min,
+                    found: elems.len(),
+                });
+            }
+            if let Some(max) = max_len.filter(|&max| elems.len() > max) {
+                errors.push(ProbeError::TooLong {
+                    path: path.to_owned(),
+                    limit: max,
+                    found: elems.len(),
+                });
+            }
+            if let Some(elem_desc) = elem_desc {
+                for (idx, elem) in elems.iter().enumerate() {
+                    let path = path::join(path, &format!("[{idx}]"));
+                    validate(elem_desc, elem, root, &path, errors);
+                }
+            }
+        }
+        (
+            Desc::Map { value_desc, max_entries, required_keys, required_if, strict_keys, .. },
+            Value::Map(values),
+        ) => {
+            if let Some(max) = max_entries.filter(|&max| values.len() > max) {
+                errors.push(ProbeError::TooLong {
+                    path: path.to_owned(),
+                    limit: max,
+                    found: values.len(),
+                });
+            }
+
+            let mut missing: Vec<String> = required_keys
+                .iter()
+                .chain(required_if.iter().filter(|req| req.when.holds(root)).map(|req| &req.key))
+                .filter(|key| !values.contains_key(*key))
+                .cloned()
+                .collect();
+            if !missing.is_empty() {
+                missing.sort();
+                errors.push(ProbeError::MissingKeys {
+                    path: path.to_owned(),
+                    keys: missing,
+                });
+            }
+
+            if *strict_keys {
+                let mut unexpected: Vec<String> = values
+                    .keys()
+                    .filter(|key| !required_keys.contains(key) && !required_if.iter().any(|req| req.key == **key))
+                    .cloned()
+                    .collect();
+                if !unexpected.is_empty() {
+                    unexpected.sort();
+                    errors.push(ProbeError::UnexpectedKeys {
+                        path: path.to_owned(),
+                        keys: unexpected,
+                    });
+                }
+            }
+
+            if let Some(value_desc) = value_desc {
+                let mut keys: Vec<&String> = values.keys().collect();
+                keys.sort();
+                for key in keys {
+                    let path = path::join(path, key);
+                    validate(value_desc, &values[key], root, &path, errors);
+                }
+            }
+        }
+        (&Desc::Matrix { rows, cols, min, max, .. }, Value::List(elems)) => {
+            if elems.len() != rows * cols {
+                errors.push(ProbeError::WrongLength {
+                    path: path.to_owned(),
+                    expected: rows * cols,
+                    found: elems.len(),
+                });
+                return;
+            }
+            for (idx, elem) in elems.iter().enumerate() {
+                let path = path::join(path, &format!("[{idx}]"));
+                match elem {
+                    Value::Float(value) => check_range(&path, *value, min, max, errors),
+                    _ => errors.push(ProbeError::TypeMismatch {
+                        path,
+                        expected: Kind::Float.name(),
+                        found: elem.kind(),
+                    }),
+                }
+            }
+        }
+        (Desc::Enum { variants }, Value::Enum { variant, value }) => {
+            match variants.iter().find(|v| v.name == *variant) {
+                Some(EnumVariant { desc: Some(desc), .. }) => {
+                    validate(desc, value, root, &path::join(path, variant), errors)
+                }
+                Some(_) if !matches!(**value, Value::Null) => errors.push(ProbeError::TypeMismatch {
+                    path: path::join(path, variant),
+                    expected: "null",
+                    found: value.kind(),
+                }),
+                Some(_) => {}
+                None => errors.push(ProbeError::NotInVariants {
+                    path: path.to_owned(),
+                    value: variant.clone(),
+                }),
+            }
+        }
+        (Desc::Struct { fields }, Value::Map(values)) => {
+            let missing: Vec<String> = fields
+                .iter()
+                .filter(|field| !values.contains_key(&field.name))
+                .map(|field| field.name.clone())
+                .collect();
+            if !missing.is_empty() {
+                errors.push(ProbeError::MissingKeys {
+                    path: path.to_owned(),
+                    keys: missing,
+                });
+            }
+
+            let mut extra: Vec<String> = values
+                .keys()
+                .filter(|key| !fields.iter().any(|field| field.name == **key))
+                .cloned()
+                .collect();
+            if !extra.is_empty() {
+                extra.sort();
+                errors.push(ProbeError::ExtraFields {
+                    path: path.to_owned(),
+                    fields: extra,
+                });
+            }
+
+            for field in fields {
+                if let Some(value) = values.get(&field.name) {
+                    validate(&field.desc, value, root, &path::join(path, &field.name), errors);
+                }
+            }
+        }
+        (Desc::Tuple { elems: descs }, Value::List(elems)) => {
+            if elems.len() != descs.len() {
+                errors.push(ProbeError::WrongLength {
+                    path: path.to_owned(),
+                    expected: descs.len(),
+                    found: elems.len(),
+                });
+            }
+            for (idx, (desc, elem)) in descs.iter().zip(elems).enumerate() {
+                validate(desc, elem, root, &path::join(path, &format!("[{idx}]")), errors);
+            }
+        }
+        (Desc::Optional { .. }, Value::Null) => {}
+        (Desc::Optional { inner } | Desc::Documented { inner, .. } | Desc::WithDefault { inner, .. } | Desc::Advanced { inner }, value) => validate(inner, value, root, path, errors),
+        (desc, _) => errors.push(mismatch(desc.kind())),
+    }
+}
+
+/// Returns the bound referenced by `bound_path` or `fallback`,
+/// like `bounds::resolve` does for shown values.
+fn bound<T: Bound>(root: &Value, bound_path: &Option<String>, fallback: Option<T>) -> Option<T> {
+    let Some(bound_path) = bound_path else {
+        return fallback;
+    };
+
+    path::lookup(root, bound_path).and_then(T::from_value).or(fallback)
+}
+
+fn check_range<T: Bound>(path: &str, value: T, min: Option<T>, max: Option<T>, errors: &mut Vec<ProbeError>) {
+    match (min, max) {
+        (Some(min), Some(max)) if min > max => errors.push(ProbeError::InvalidRange {
+            path: path.to_owned(),
+            min: min.to_string(),
+            max: max.to_string(),
+        }),
+        _ if min.is_some_and(|min| value < min) || max.is_some_and(|max| value > max) => {
+            errors.push(ProbeError::OutOfRange {
+                path: path.to_owned(),
+                value: value.to_f64(),
+                min: min.map(T::to_f64),
+                max: max.map(T::to_f64),
+            })
+        }
+        _ => {}
+    }
+}
+
+/// Returns indices of elements whose `field` equals the `field` of another element.
+///
+/// Elements without the field are never duplicates.
+pub(crate) fn duplicates(elems: &[Value], field: &str) -> Vec<usize> {
+    let fields: Vec<Option<&Value>> = elems.iter().map(|elem| path::lookup(elem, field)).collect();
+
+    (0..fields.len())
+        .filter(|&idx| {
+            fields[idx].is_some_and(|a| {
+                fields
+                    .iter()
+                    .enumerate()
+                    .any(|(other, b)| other != idx && b.is_some_and(|b| a == b))
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::StructField;
+
+    fn int(min: Option<i64>, max: Option<i64>, max_path: Option<&str>) -> Desc {
+        Desc::Int {
+            min,
+            max,
+            min_path: None,
+            max_path: max_path.map(str::to_owned),
+            humanize: None,
+            stepper: None,
+            step: None,
+        }
+    }
+
+    #[test]
+    fn int_bounds_keep_precision() {
+        let max = (1 << 60) + 1;
+        let desc = int(None, Some(max), None);
+
+        assert_eq!(desc.validate(&Value::Int(max)), Ok(()));
+        assert!(matches!(
+            desc.validate(&Value::Int(max + 1)).unwrap_err()[..],
+            [ProbeError::OutOfRange { .. }]
+        ));
+    }
+
+    #[test]
+    fn uint_bounds_keep_precision() {
+        let desc = Desc::Uint { min: None, max: Some(u64::MAX - 1) };
+
+        assert_eq!(desc.validate(&Value::Uint(u64::MAX - 1)), Ok(()));
+        assert!(desc.validate(&Value::Uint(u64::MAX)).is_err());
+    }
+
+    #[test]
+    fn int_bound_follows_path() {
+        let desc = Desc::Struct {
+            fields: vec![
+                StructField::new("limit", Desc::Uint { min: None, max: None }),
+                StructField::new("count", int(Some(0), Some(100), Some("limit"))),
+            ],
+        };
+        let mut value: Value = [("limit".to_owned(), Value::Uint(10)), ("count".to_owned(), Value::Int(20))]
+            .into_iter()
+            .collect();
+
+        let errors = desc.validate(&value).unwrap_err();
+        assert!(matches!(
+            &errors[..],
+            [ProbeError::OutOfRange { path, max: Some(max), .. }] if path == "count" && *max == 10.0
+        ));
+
+        // Raising the referenced bound makes the dependent field valid again.
+        *path::lookup_mut(&mut value, "limit").unwrap() = Value::Uint(50);
+        assert_eq!(desc.validate(&value), Ok(()));
+
+        // Missing bound falls back to the static one.
+        *path::lookup_mut(&mut value, "limit").unwrap() = Value::from("many");
+        let errors = desc.validate(&value).unwrap_err();
+        assert!(matches!(&errors[..], [ProbeError::TypeMismatch { path, .. }] if path == "limit"));
+    }
+
+    #[test]
+    fn crossed_int_bounds_are_invalid_range() {
+        let errors = int(Some(5), Some(1), None).validate(&Value::Int(3)).unwrap_err();
+        assert!(matches!(
+            &errors[..],
+            [ProbeError::InvalidRange { min, max, .. }] if min == "5" && max == "1"
+        ));
+    }
+
+
+    #[test]
+    fn reports_nested_paths() {
+        let item = Desc::Struct {
+            fields: vec![
+                StructField::new("name", Desc::from_value(&Value::from("x"))),
+                StructField::new("count", int(Some(0), Some(10), None)),
+            ],
+        };
+        let items = Desc::List {
+            elem_desc: Some(Box::new(item)),
+            empty_hint: None,
+            collapsed_by_default: false,
+            min_len: None,
+            max_len: None,
+            multi_select: false,
+            unique_by: None,
+        };
+        let desc = Desc::Struct { fields: vec![StructField::new("items", items)] };
+
+        let good: Value = [("name".to_owned(), Value::from("a")), ("count".to_owned(), Value::Int(1))]
+            .into_iter()
+            .collect();
+        let bad: Value = [("name".to_owned(), Value::Int(3)), ("count".to_owned(), Value::Int(11))]
+            .into_iter()
+            .collect();
+        let value: Value = [("items".to_owned(), Value::from(vec![good.clone(), good.clone(), good, bad]))]
+            .into_iter()
+            .collect();
+
+        let errors = desc.validate(&value).unwrap_err();
+        let paths: Vec<&str> = errors.iter().map(ProbeError::path).collect();
+        assert_eq!(paths, ["items[3].name", "items[3].count"]);
+        assert!(matches!(errors[0], ProbeError::TypeMismatch { expected: "string", found: "int", .. }));
+        assert!(matches!(errors[1], ProbeError::OutOfRange { .. }));
+    }
+
+    #[test]
+    fn reports_kind_mismatch_and_unknown_variant() {
+        let errors = Desc::Bool.validate(&Value::Float(1.0)).unwrap_err();
+        assert!(matches!(&errors[..], [ProbeError::TypeMismatch { path, expected: "bool", found: "float" }] if path.is_empty()));
+
+        let desc = Desc::String {
+            variants: Some(vec![crate::Variant::new("a"), crate::Variant::new("b")]),
+            trim: false,
+            collapse_whitespace: false,
+            min_len: None,
+            max_len: None,
+            pattern: None,
+            multiline: false,
+        };
+        assert_eq!(desc.validate(&Value::from("b")), Ok(()));
+        assert!(matches!(
+            &desc.validate(&Value::from("c")).unwrap_err()[..],
+            [ProbeError::NotInVariants { value, .. }] if value == "c"
+        ));
+    }
+
+
+    #[test]
+    fn finds_wrong_leaf_in_list_of_maps() {
+        let row = |leaf: Value| -> Value {
+            let cell: Value = [("value".to_owned(), leaf)].into_iter().collect();
+            [("cells".to_owned(), Value::from(vec![cell]))].into_iter().collect()
+        };
+        let value = Value::from(vec![row(Value::Int(1)), row(Value::Int(2)), row(Value::from("three"))]);
+        let desc = Desc::from_value(&Value::from(vec![row(Value::Int(0))]));
+
+        let errors = desc.validate(&value).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].path_segments(), ["[2]", "cells", "[0]", "value"]);
+        assert!(matches!(errors[0], ProbeError::TypeMismatch { expected: "int", found: "string", .. }));
+    }
+
+    #[test]
+    fn reports_wrong_enum_variant() {
+        let desc = Desc::Enum {
+            variants: vec![EnumVariant::new("Some", Desc::Bool)],
+        };
+        let variant = |name: &str, value: Value| Value::Enum {
+            variant: name.to_owned(),
+            value: Box::new(value),
+        };
+
+        assert_eq!(desc.validate(&variant("Some", Value::Bool(true))), Ok(()));
+        assert!(matches!(
+            &desc.validate(&variant("None", Value::Null)).unwrap_err()[..],
+            [ProbeError::NotInVariants { value, .. }] if value == "None"
+        ));
+        assert!(matches!(
+            &desc.validate(&variant("Some", Value::Int(1))).unwrap_err()[..],
+            [ProbeError::TypeMismatch { path, .. }] if path == "Some"
+        ));
+    }
+
+    #[test]
+    fn value_validate_matches_desc_validate() {
+        let desc = Desc::Float {
+            min: Some(1.0),
+            max: Some(0.0),
+            min_path: None,
+            max_path: None,
+            stepper: None,
+            step: None,
+        };
+        let value = Value::Float(0.5);
+        assert_eq!(value.validate(&desc), desc.validate(&value));
+        assert!(matches!(&value.validate(&desc).unwrap_err()[..], [ProbeError::InvalidRange { .. }]));
+    }
+
+
+    #[test]
+    fn duplicates_finds_equal_elements() {
+        let elems = [Value::Int(1), Value::Int(2), Value::Int(1)];
+        assert_eq!(duplicates(&elems, ""), [0, 2]);
+        assert!(duplicates(&[Value::Int(1), Value::Uint(1)], "").is_empty());
+    }
+
+    #[test]
+    fn duplicates_compares_field_and_skips_missing() {
+        let item = |id: Option<i64>| -> Value {
+            id.map(|id| ("id".to_owned(), Value::Int(id))).into_iter().collect()
+        };
+        let elems = [item(Some(1)), item(None), item(Some(1)), item(None), item(Some(2))];
+        assert_eq!(duplicates(&elems, "id"), [0, 2]);
+    }
+
+    #[test]
+    fn unique_list_reports_each_duplicate() {
+        let desc = Desc::List {
+            elem_desc: Some(Box::new(int(None, None, None))),
+            empty_hint: None,
+            collapsed_by_default: false,
+            min_len: None,
+            max_len: None,
+            multi_select: false,
+            unique_by: Some(String::new()),
+        };
+        let value = Value::from(vec![Value::Int(7), Value::Int(3), Value::Int(7)]);
+
+        let errors = desc.validate(&value).unwrap_err();
+        let paths: Vec<_> = errors.iter().map(ProbeError::path).collect();
+        assert_eq!(paths, ["[0]", "[2]"]);
+        assert!(errors.iter().all(|error| matches!(error, ProbeError::NotUnique { .. })));
+        assert_eq!(desc.validate(&Value::from(vec![Value::Int(7), Value::Int(3)])), Ok(()));
+    }
+}
@@ -0,0 +1,68 @@
+use egui_any::{Desc, DescribeValue, Value};
+
+#[derive(Debug, PartialEq, DescribeValue)]
+enum Mode {
+    Fast,
+    Safe,
+}
+
+#[derive(Debug, PartialEq, DescribeValue)]
+struct Limits {
+    max: u32,
+    ratio: f64,
+}
+
+#[derive(Debug, PartialEq, DescribeValue)]
+struct Config {
+    name: String,
+    mode: Mode,
+    limits: Limits,
+    tags: Vec<String>,
+    parent: Option<String>,
+}
+
+fn config() -> Config {
+    Config {
+        name: "main".to_owned(),
+        mode: Mode::Safe,
+        limits: Limits { max: 10, ratio: 0.5 },
+        tags: vec!["a".to_owned(), "b".to_owned()],
+        parent: None,
+    }
+}
+
+#[test]
+fn struct_round_trips() {
+    let value = config().to_value();
+
+    let fields = value.as_map().unwrap();
+    assert_eq!(fields["limits"].as_map().unwrap()["max"], Value::Int(10));
+    assert_eq!(fields["mode"], Value::String("Safe".to_owned()));
+    assert_eq!(fields["parent"], Value::Null);
+
+    assert_eq!(Config::from_value(&value), Some(config()));
+    assert_eq!(Config::describe().validate(&value), Ok(()));
+}
+
+#[test]
+fn struct_with_missing_field_is_rejected() {
+    let mut value = config().to_value();
+    if let Value::Map(fields) = &mut value {
+        fields.shift_remove("limits");
+    }
+    assert_eq!(Config::from_value(&value), None);
+}
+
+#[test]
+fn enum_is_string_with_variants() {
+    let Desc::String { variants: Some(variants), .. } = Mode::describe() else {
+        panic!("enum is not described as a string with variants");
+    };
+    let names: Vec<&str> = variants.iter().map(|variant| variant.name.as_str()).collect();
+    assert_eq!(names, ["Fast", "Safe"]);
+
+    assert_eq!(Mode::Fast.to_value(), Value::String("Fast".to_owned()));
+    assert_eq!(Mode::from_value(&Value::String("Safe".to_owned())), Some(Mode::Safe));
+    assert_eq!(Mode::from_value(&Value::String("Slow".to_owned())), None);
+    assert_eq!(Mode::from_value(&Value::Int(0)), None);
+}